@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+fn test_name(day: usize, stem: &str, part: usize) -> String {
+    let stem: String = stem.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' }).collect();
+    return format!("day{:02}_{}_part{}", day, stem, part);
+}
+
+fn parse_answers(path: &Path) -> Vec<(usize, String)> {
+    let source = fs::read_to_string(path).unwrap_or_else(|_| panic!("{}: could not read", path.display()));
+    let mut answers: Vec<(usize, String)> = vec![];
+    for (index, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line.split_once(':')
+            .unwrap_or_else(|| panic!("{}:{}: expected \"part<N>: <answer>\"", path.display(), index + 1));
+        let part = key.trim().strip_prefix("part").and_then(|n| n.parse::<usize>().ok())
+            .filter(|n| *n == 1 || *n == 2)
+            .unwrap_or_else(|| panic!("{}:{}: expected part1 or part2, found \"{}\"", path.display(), index + 1, key.trim()));
+        answers.push((part, value.trim().to_string()));
+    }
+    return answers;
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=data");
+    let mut tests = String::new();
+    let mut test_sources: HashMap<String, String> = HashMap::new();
+    let mut day_directories: Vec<_> = fs::read_dir("data").unwrap().map(|entry| entry.unwrap().path()).collect();
+    day_directories.sort();
+    for directory in day_directories {
+        let name = directory.file_name().unwrap().to_string_lossy().to_string();
+        let day = match name.strip_prefix("day").and_then(|n| n.parse::<usize>().ok()) {
+            Some(day) => day,
+            None => continue,
+        };
+        let mut files: Vec<_> = fs::read_dir(&directory).unwrap().map(|entry| entry.unwrap().path()).collect();
+        files.sort();
+        for answers_file in files.iter().filter(|path| path.extension().is_some_and(|e| e == "answers")) {
+            let example = answers_file.with_extension("txt");
+            if !example.exists() {
+                panic!("{}: no example file {}", answers_file.display(), example.display());
+            }
+            let stem = answers_file.file_stem().unwrap().to_string_lossy().to_string();
+            let filename = example.to_string_lossy().replace('\\', "/");
+            for (part, expected) in parse_answers(answers_file) {
+                let name = test_name(day, &stem, part);
+                let source = format!("{} part{}", answers_file.display(), part);
+                if let Some(previous) = test_sources.insert(name.clone(), source.clone()) {
+                    panic!("{} and {} both generate the test {}; rename one of them", previous, source, name);
+                }
+                tests.push_str(&format!(
+                    "#[test]\nfn {}() {{\n    check_example({}, {}, {:?}, {:?});\n}}\n\n",
+                    name, day, part, filename, expected,
+                ));
+            }
+        }
+    }
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("examples.rs"), tests).unwrap();
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+use std::str::FromStr;
+use num::BigInt;
+
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(BigInt),
+    Text(String),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseAnswerError {
+    pub message: String,
+}
+
+impl fmt::Display for ParseAnswerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}", self.message);
+    }
+}
+
+impl std::error::Error for ParseAnswerError {}
+
+impl Answer {
+    pub fn is_integer(&self) -> bool {
+        return !matches!(self, Answer::Text(_));
+    }
+
+    pub fn to_big_int(&self) -> Option<BigInt> {
+        return match self {
+            Answer::Signed(value) => Some(BigInt::from(*value)),
+            Answer::Unsigned(value) => Some(BigInt::from(*value)),
+            Answer::Big(value) => Some(value.clone()),
+            Answer::Text(_) => None,
+        };
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        return match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Signed(a), Answer::Signed(b)) => a == b,
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            _ => self.is_integer() && other.is_integer() && self.to_big_int() == other.to_big_int(),
+        };
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            Answer::Signed(value) => write!(f, "{}", value),
+            Answer::Unsigned(value) => write!(f, "{}", value),
+            Answer::Big(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+        };
+    }
+}
+
+fn is_canonical_integer(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return false;
+    }
+    if digits == "0" {
+        return digits.len() == text.len();
+    }
+    return !digits.starts_with('0');
+}
+
+#[cfg(test)]
+mod is_canonical_integer_tests {
+    use super::*;
+
+    #[test]
+    fn test_is_canonical_integer() {
+        assert_eq!(is_canonical_integer("0"), true);
+        assert_eq!(is_canonical_integer("7"), true);
+        assert_eq!(is_canonical_integer("-7"), true);
+        assert_eq!(is_canonical_integer("184467440737095516150"), true);
+    }
+
+    #[test]
+    fn test_is_canonical_integer_rejects_other_spellings() {
+        assert_eq!(is_canonical_integer("007"), false);
+        assert_eq!(is_canonical_integer("-0"), false);
+        assert_eq!(is_canonical_integer("+7"), false);
+        assert_eq!(is_canonical_integer("-"), false);
+        assert_eq!(is_canonical_integer("1_000"), false);
+    }
+}
+
+impl FromStr for Answer {
+    type Err = ParseAnswerError;
+
+    fn from_str(source: &str) -> Result<Answer, ParseAnswerError> {
+        let text = source.trim();
+        if text.is_empty() {
+            return Err(ParseAnswerError { message: "empty answer".to_string() });
+        }
+        if !is_canonical_integer(text) {
+            return Ok(Answer::Text(text.to_string()));
+        }
+        if let Ok(value) = text.parse::<i64>() {
+            return Ok(Answer::Signed(value));
+        }
+        if let Ok(value) = text.parse::<u64>() {
+            return Ok(Answer::Unsigned(value));
+        }
+        if let Ok(value) = text.parse::<BigInt>() {
+            return Ok(Answer::Big(value));
+        }
+        return Ok(Answer::Text(text.to_string()));
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Answer {
+        return Answer::Signed(value as i64);
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Answer {
+        return Answer::Signed(value);
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Answer {
+        return Answer::Unsigned(value as u64);
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Answer {
+        return Answer::Unsigned(value);
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Answer {
+        return Answer::Unsigned(value as u64);
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Answer {
+        return Answer::Big(value);
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        return Answer::Text(value);
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Answer {
+        return Answer::Text(value.to_string());
+    }
+}
+
+#[cfg(test)]
+mod answer_tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(-42).to_string(), "-42");
+        assert_eq!(Answer::from(6725usize).to_string(), "6725");
+        assert_eq!(Answer::from(BigInt::from(u64::MAX) * 10).to_string(), "184467440737095516150");
+        assert_eq!(Answer::from("LRRL").to_string(), "LRRL");
+    }
+
+    #[test]
+    fn test_equality_across_widths() {
+        assert_eq!(Answer::from(55017i32), Answer::from(55017i64));
+        assert_eq!(Answer::from(55017i32), Answer::from(55017usize));
+        assert_eq!(Answer::from(55017u64), Answer::from(BigInt::from(55017)));
+        assert_ne!(Answer::from(-1), Answer::from(u64::MAX));
+        assert_ne!(Answer::from(1), Answer::from(2u32));
+    }
+
+    #[test]
+    fn test_equality_text() {
+        assert_eq!(Answer::from("abc"), Answer::from("abc".to_string()));
+        assert_ne!(Answer::from("42"), Answer::from(42));
+    }
+
+    #[test]
+    fn test_parse_integers() {
+        assert_eq!("288".parse::<Answer>(), Ok(Answer::Signed(288)));
+        assert_eq!(" -7\n".parse::<Answer>(), Ok(Answer::Signed(-7)));
+        assert_eq!("18446744073709551615".parse::<Answer>(), Ok(Answer::Unsigned(u64::MAX)));
+        assert_eq!("184467440737095516150".parse::<Answer>(), Ok(Answer::Big(BigInt::from(u64::MAX) * 10)));
+    }
+
+    #[test]
+    fn test_parse_text() {
+        assert_eq!("LRRL".parse::<Answer>(), Ok(Answer::Text("LRRL".to_string())));
+        assert_eq!("  ".parse::<Answer>().is_err(), true);
+    }
+
+    #[test]
+    fn test_parse_non_canonical_integers_as_text() {
+        assert_eq!("007".parse::<Answer>(), Ok(Answer::Text("007".to_string())));
+        assert_eq!("+7".parse::<Answer>(), Ok(Answer::Text("+7".to_string())));
+        assert_ne!("007".parse::<Answer>(), Ok(Answer::from(7)));
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        for answer in [Answer::from(-3), Answer::from(u64::MAX), Answer::from(BigInt::from(i64::MIN) * 3), Answer::from("x,y")] {
+            assert_eq!(answer.to_string().parse::<Answer>(), Ok(answer));
+        }
+    }
+}
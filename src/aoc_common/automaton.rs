@@ -0,0 +1,144 @@
+use std::collections::VecDeque;
+
+const NO_STATE: u32 = u32::MAX;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Automaton {
+    transitions: Vec<[u32;256]>,
+    outputs: Vec<Vec<usize>>,
+    lengths: Vec<usize>,
+}
+
+impl Automaton {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Automaton {
+        let mut transitions = vec![[NO_STATE;256]];
+        let mut outputs: Vec<Vec<usize>> = vec![vec![]];
+        let mut lengths = vec![];
+        for (index, pattern) in patterns.iter().enumerate() {
+            let bytes = pattern.as_ref().as_bytes();
+            lengths.push(bytes.len());
+            if bytes.is_empty() {
+                continue;
+            }
+            let mut state = 0;
+            for &byte in bytes {
+                if transitions[state][byte as usize] == NO_STATE {
+                    transitions.push([NO_STATE;256]);
+                    outputs.push(vec![]);
+                    transitions[state][byte as usize] = (transitions.len() - 1) as u32;
+                }
+                state = transitions[state][byte as usize] as usize;
+            }
+            outputs[state].push(index);
+        }
+
+        let mut fail = vec![0;transitions.len()];
+        let mut queue = VecDeque::new();
+        for byte in 0..256 {
+            match transitions[0][byte] {
+                NO_STATE => transitions[0][byte] = 0,
+                next => queue.push_back(next as usize),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let inherited = outputs[fail[state]].clone();
+            outputs[state].extend(inherited);
+            for byte in 0..256 {
+                let fallback = transitions[fail[state]][byte];
+                match transitions[state][byte] {
+                    NO_STATE => transitions[state][byte] = fallback,
+                    next => {
+                        fail[next as usize] = fallback as usize;
+                        queue.push_back(next as usize);
+                    }
+                }
+            }
+        }
+        return Automaton { transitions, outputs, lengths };
+    }
+
+    #[allow(dead_code)]
+    pub fn pattern_count(&self) -> usize {
+        return self.lengths.len();
+    }
+
+    #[allow(dead_code)]
+    pub fn state_count(&self) -> usize {
+        return self.transitions.len();
+    }
+
+    pub fn for_each_match<F: FnMut(Match)>(&self, text: &str, mut f: F) {
+        let mut state = 0;
+        for (index, &byte) in text.as_bytes().iter().enumerate() {
+            state = self.transitions[state][byte as usize] as usize;
+            for &pattern in &self.outputs[state] {
+                f(Match { pattern, start: index + 1 - self.lengths[pattern], end: index + 1 });
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn find_all(&self, text: &str) -> Vec<Match> {
+        let mut matches = vec![];
+        self.for_each_match(text, |found| matches.push(found));
+        return matches;
+    }
+}
+
+#[cfg(test)]
+mod automaton_tests {
+    use super::*;
+
+    fn found(automaton: &Automaton, text: &str) -> Vec<(usize, usize, usize)> {
+        return automaton.find_all(text).iter().map(|m| (m.pattern, m.start, m.end)).collect();
+    }
+
+    #[test]
+    fn test_find_all_overlapping() {
+        let automaton = Automaton::new(&["eight", "two", "one"]);
+        assert_eq!(found(&automaton, "eightwone"), vec![(0, 0, 5), (1, 4, 7), (2, 6, 9)]);
+    }
+
+    #[test]
+    fn test_find_all_suffix_patterns() {
+        let automaton = Automaton::new(&["he", "she", "his", "hers"]);
+        assert_eq!(found(&automaton, "ushers"), vec![(1, 1, 4), (0, 2, 4), (3, 2, 6)]);
+    }
+
+    #[test]
+    fn test_find_all_repeated_text() {
+        let automaton = Automaton::new(&["aa"]);
+        assert_eq!(found(&automaton, "aaaa"), vec![(0, 0, 2), (0, 1, 3), (0, 2, 4)]);
+    }
+
+    #[test]
+    fn test_find_all_multibyte() {
+        let automaton = Automaton::new(&["fünf", "1"]);
+        let text = "xfünf1";
+        let matches = automaton.find_all(text);
+        assert_eq!(&text[matches[0].start..matches[0].end], "fünf");
+        assert_eq!(&text[matches[1].start..matches[1].end], "1");
+    }
+
+    #[test]
+    fn test_no_patterns() {
+        let automaton = Automaton::new::<&str>(&[]);
+        assert_eq!(automaton.find_all("anything").is_empty(), true);
+        assert_eq!(automaton.state_count(), 1);
+    }
+
+    #[test]
+    fn test_shared_prefixes() {
+        let automaton = Automaton::new(&["sei", "seis", "siete"]);
+        assert_eq!(automaton.pattern_count(), 3);
+        assert_eq!(automaton.state_count(), 9);
+        assert_eq!(found(&automaton, "seis"), vec![(0, 0, 3), (1, 0, 4)]);
+    }
+}
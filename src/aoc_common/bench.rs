@@ -0,0 +1,74 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub name: String,
+    pub size: usize,
+    pub iterations: u32,
+    pub total: Duration,
+}
+
+impl Measurement {
+    pub fn per_iteration(&self) -> Duration {
+        return self.total / self.iterations.max(1);
+    }
+}
+
+pub fn measure<R, F: FnMut() -> R>(name: &str, size: usize, iterations: u32, mut f: F) -> Measurement {
+    black_box(f());
+    let started = Instant::now();
+    for _ in 0..iterations {
+        black_box(f());
+    }
+    return Measurement { name: name.to_string(), size, iterations, total: started.elapsed() };
+}
+
+pub fn report(measurements: &[Measurement]) -> String {
+    let width = measurements.iter().map(|m| m.name.len()).max().unwrap_or(0).max("approach".len());
+    let mut lines = vec![format!("{:>10}  {:<width$}  {:>12}  {:>8}", "size", "approach", "per run", "speedup")];
+    for measurement in measurements {
+        let baseline = measurements.iter().find(|m| m.size == measurement.size).unwrap();
+        let speedup = baseline.per_iteration().as_secs_f64() / measurement.per_iteration().as_secs_f64().max(f64::MIN_POSITIVE);
+        lines.push(format!(
+            "{:>10}  {:<width$}  {:>10.3}ms  {:>7.2}x",
+            measurement.size, measurement.name, measurement.per_iteration().as_secs_f64() * 1000.0, speedup,
+        ));
+    }
+    return lines.join("\n");
+}
+
+#[cfg(test)]
+mod bench_tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_counts_iterations() {
+        let mut calls = 0;
+        let measurement = measure("count", 10, 3, || calls += 1);
+        assert_eq!(calls, 4);
+        assert_eq!(measurement.iterations, 3);
+        assert_eq!(measurement.size, 10);
+    }
+
+    #[test]
+    fn test_per_iteration() {
+        let measurement = Measurement { name: "a".to_string(), size: 1, iterations: 4, total: Duration::from_millis(10) };
+        assert_eq!(measurement.per_iteration(), Duration::from_micros(2500));
+    }
+
+    #[test]
+    fn test_report_speedup_against_first_of_each_size() {
+        let measurements = vec![
+            Measurement { name: "regex".to_string(), size: 100, iterations: 1, total: Duration::from_millis(8) },
+            Measurement { name: "automaton".to_string(), size: 100, iterations: 2, total: Duration::from_millis(4) },
+            Measurement { name: "regex".to_string(), size: 1000, iterations: 1, total: Duration::from_millis(80) },
+        ];
+        let report = report(&measurements);
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "      size  approach        per run   speedup");
+        assert_eq!(lines[1], "       100  regex           8.000ms     1.00x");
+        assert_eq!(lines[2], "       100  automaton       2.000ms     4.00x");
+        assert_eq!(lines[3], "      1000  regex          80.000ms     1.00x");
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt;
+use std::fmt::Display;
+use num::{CheckedAdd, CheckedMul, CheckedSub, Num};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OverflowError {
+    pub operation: String,
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "arithmetic overflow in {}", self.operation);
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Arithmetic {
+    #[default]
+    Checked,
+    Big,
+}
+
+pub trait Accumulator: Num + CheckedAdd + CheckedMul + CheckedSub + Clone + Display {}
+
+impl<T: Num + CheckedAdd + CheckedMul + CheckedSub + Clone + Display> Accumulator for T {}
+
+pub fn checked_add<T: CheckedAdd + Display>(a: T, b: T) -> Result<T, OverflowError> {
+    return a.checked_add(&b).ok_or_else(|| OverflowError { operation: format!("{} + {}", a, b) });
+}
+
+#[cfg(test)]
+mod checked_add_tests {
+    use num::BigInt;
+    use super::*;
+
+    #[test]
+    fn test_checked_add() {
+        assert_eq!(checked_add(2, 3), Ok(5));
+    }
+
+    #[test]
+    fn test_checked_add_overflow() {
+        assert_eq!(checked_add(i32::MAX, 1), Err(OverflowError { operation: "2147483647 + 1".to_string() }));
+    }
+
+    #[test]
+    fn test_checked_add_big() {
+        let result = checked_add(BigInt::from(i64::MAX), BigInt::from(1));
+        assert_eq!(result.unwrap().to_string(), "9223372036854775808");
+    }
+}
+
+pub fn checked_sub<T: CheckedSub + Display>(a: T, b: T) -> Result<T, OverflowError> {
+    return a.checked_sub(&b).ok_or_else(|| OverflowError { operation: format!("{} - {}", a, b) });
+}
+
+#[cfg(test)]
+mod checked_sub_tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_sub() {
+        assert_eq!(checked_sub(2, 3), Ok(-1));
+    }
+
+    #[test]
+    fn test_checked_sub_overflow() {
+        assert_eq!(checked_sub(i64::MIN, 1).is_err(), true);
+    }
+}
+
+pub fn checked_mul<T: CheckedMul + Display>(a: T, b: T) -> Result<T, OverflowError> {
+    return a.checked_mul(&b).ok_or_else(|| OverflowError { operation: format!("{} * {}", a, b) });
+}
+
+#[cfg(test)]
+mod checked_mul_tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_mul() {
+        assert_eq!(checked_mul(6, 7), Ok(42));
+    }
+
+    #[test]
+    fn test_checked_mul_overflow() {
+        assert_eq!(checked_mul(i32::MAX, 2), Err(OverflowError { operation: "2147483647 * 2".to_string() }));
+    }
+}
+
+pub fn checked_sum<T: Accumulator, I: IntoIterator<Item = T>>(values: I) -> Result<T, OverflowError> {
+    let mut total = T::zero();
+    for value in values {
+        total = checked_add(total, value)?;
+    }
+    return Ok(total);
+}
+
+#[cfg(test)]
+mod checked_sum_tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_sum_empty() {
+        assert_eq!(checked_sum(Vec::<i32>::new()), Ok(0));
+    }
+
+    #[test]
+    fn test_checked_sum() {
+        assert_eq!(checked_sum(vec![1, 2, 3]), Ok(6));
+    }
+
+    #[test]
+    fn test_checked_sum_overflow() {
+        assert_eq!(checked_sum(vec![i32::MAX, 1, -5]).is_err(), true);
+    }
+}
+
+pub fn checked_product<T: Accumulator, I: IntoIterator<Item = T>>(values: I) -> Result<T, OverflowError> {
+    let mut total = T::one();
+    for value in values {
+        total = checked_mul(total, value)?;
+    }
+    return Ok(total);
+}
+
+#[cfg(test)]
+mod checked_product_tests {
+    use num::BigInt;
+    use super::*;
+
+    #[test]
+    fn test_checked_product_empty() {
+        assert_eq!(checked_product(Vec::<i64>::new()), Ok(1));
+    }
+
+    #[test]
+    fn test_checked_product() {
+        assert_eq!(checked_product(vec![4, 8, 9]), Ok(288));
+    }
+
+    #[test]
+    fn test_checked_product_overflow() {
+        assert_eq!(checked_product(vec![1_000_000i64; 4]).is_err(), true);
+    }
+
+    #[test]
+    fn test_checked_product_big() {
+        let result = checked_product(vec![BigInt::from(1_000_000); 4]);
+        assert_eq!(result.unwrap().to_string(), "1000000000000000000000000");
+    }
+}
+
+pub fn checked_append_digit<T: Accumulator + From<u8>>(number: T, digit: u8) -> Result<T, OverflowError> {
+    return checked_add(checked_mul(number, T::from(10))?, T::from(digit));
+}
+
+#[cfg(test)]
+mod checked_append_digit_tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_append_digit() {
+        assert_eq!(checked_append_digit(46, 7), Ok(467));
+    }
+
+    #[test]
+    fn test_checked_append_digit_overflow() {
+        assert_eq!(checked_append_digit(i32::MAX / 10, 9).is_err(), true);
+    }
+}
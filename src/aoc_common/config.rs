@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigError {
+    pub source: String,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.source, line, self.message),
+            None => write!(f, "{}: {}", self.source, self.message),
+        };
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub source: String,
+    pub data_dir: String,
+    pub input: String,
+    pub days: BTreeMap<usize, BTreeMap<String, String>>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        return Config {
+            source: "aoc.toml".to_string(),
+            data_dir: "data".to_string(),
+            input: "input.txt".to_string(),
+            days: BTreeMap::new(),
+        };
+    }
+}
+
+fn parse_value(raw: &str) -> Result<String, String> {
+    let raw = raw.trim();
+    if let Some(quoted) = raw.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = quoted.chars();
+        while let Some(chr) = chars.next() {
+            match chr {
+                '"' => {
+                    let rest = chars.as_str().trim();
+                    if !rest.is_empty() && !rest.starts_with('#') {
+                        return Err(format!("unexpected text after closing quote: {}", rest));
+                    }
+                    return Ok(value);
+                }
+                '\\' => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    Some(other) => return Err(format!("unknown escape \\{}", other)),
+                    None => break,
+                },
+                _ => value.push(chr),
+            }
+        }
+        return Err("unterminated string".to_string());
+    }
+    let bare = raw.split('#').next().unwrap().trim();
+    if bare.is_empty() {
+        return Err("missing value".to_string());
+    }
+    if bare.contains(char::is_whitespace) {
+        return Err(format!("values containing spaces must be quoted: {}", bare));
+    }
+    return Ok(bare.to_string());
+}
+
+#[cfg(test)]
+mod parse_value_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_value_quoted() {
+        assert_eq!(parse_value(" \"12 red, 13 green\" # comment"), Ok("12 red, 13 green".to_string()));
+        assert_eq!(parse_value("\"a \\\"b\\\"\""), Ok("a \"b\"".to_string()));
+    }
+
+    #[test]
+    fn test_parse_value_bare() {
+        assert_eq!(parse_value("words # rule"), Ok("words".to_string()));
+        assert_eq!(parse_value("42"), Ok("42".to_string()));
+    }
+
+    #[test]
+    fn test_parse_value_errors() {
+        assert_eq!(parse_value("\"open"), Err("unterminated string".to_string()));
+        assert_eq!(parse_value(""), Err("missing value".to_string()));
+        assert_eq!(parse_value("12 red").is_err(), true);
+        assert_eq!(parse_value("\"a\" b").is_err(), true);
+    }
+}
+
+fn parse_day_section(name: &str) -> Option<usize> {
+    let number = name.strip_prefix("day")?;
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    return number.parse::<usize>().ok().filter(|day| *day > 0);
+}
+
+impl Config {
+    pub fn parse(source: &str, text: &str) -> Result<Config, ConfigError> {
+        let mut config = Config { source: source.to_string(), ..Default::default() };
+        let mut section: Option<usize> = None;
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| ConfigError { source: source.to_string(), line: Some(index + 1), message };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix('[') {
+                let name = header.split('#').next().unwrap().trim().strip_suffix(']')
+                    .ok_or_else(|| error(format!("unclosed section header: {}", line)))?.trim();
+                let day = parse_day_section(name)
+                    .ok_or_else(|| error(format!("unknown section [{}]; sections are named [day01], [day02], ...", name)))?;
+                config.days.entry(day).or_default();
+                section = Some(day);
+                continue;
+            }
+            let (key, raw) = line.split_once('=').ok_or_else(|| error(format!("expected key = value, found: {}", line)))?;
+            let key = key.trim();
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(error(format!("invalid key \"{}\"", key)));
+            }
+            let value = parse_value(raw).map_err(|message| error(format!("{}: {}", key, message)))?;
+            match section {
+                None => match key {
+                    "data_dir" => config.data_dir = value,
+                    "input" => config.input = value,
+                    _ => return Err(error(format!("unknown top-level key \"{}\" (expected data_dir or input)", key))),
+                },
+                Some(day) => {
+                    let parameters = config.days.get_mut(&day).unwrap();
+                    if parameters.insert(key.to_string(), value).is_some() {
+                        return Err(error(format!("duplicate key \"{}\" in [day{:02}]", key, day)));
+                    }
+                }
+            }
+        }
+        return Ok(config);
+    }
+
+    pub fn load(path: &str) -> Result<Config, ConfigError> {
+        let text = fs::read_to_string(path)
+            .map_err(|error| ConfigError { source: path.to_string(), line: None, message: error.to_string() })?;
+        return Config::parse(path, &text);
+    }
+
+    pub fn load_optional(path: &str) -> Result<Config, ConfigError> {
+        return match fs::read_to_string(path) {
+            Ok(text) => Config::parse(path, &text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(error) => Err(ConfigError { source: path.to_string(), line: None, message: error.to_string() }),
+        };
+    }
+
+    pub fn set_parameter(&mut self, day: usize, name: &str, value: &str) {
+        self.days.entry(day).or_default().insert(name.to_string(), value.to_string());
+    }
+
+    pub fn input_path(&self, day: usize) -> String {
+        let input = self.days.get(&day).and_then(|parameters| parameters.get("input")).unwrap_or(&self.input);
+        return format!("{}/day{:02}/{}", self.data_dir.trim_end_matches('/'), day, input);
+    }
+
+    pub fn day_parameters(&self, day: usize, known: &[&str]) -> Result<BTreeMap<String, String>, ConfigError> {
+        let mut parameters = self.days.get(&day).cloned().unwrap_or_default();
+        parameters.remove("input");
+        for name in parameters.keys() {
+            if !known.contains(&name.as_str()) {
+                let expected = if known.is_empty() {
+                    "this day takes no parameters besides input".to_string()
+                } else {
+                    format!("expected one of: input, {}", known.join(", "))
+                };
+                return Err(ConfigError {
+                    source: self.source.clone(),
+                    line: None,
+                    message: format!("unknown parameter \"{}\" for day{:02}; {}", name, day, expected),
+                });
+            }
+        }
+        return Ok(parameters);
+    }
+}
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+
+    const SAMPLE: &str = "# puzzle settings\n\
+        data_dir = \"puzzles/\"\n\
+        \n\
+        [day01]\n\
+        part2_rule = digits\n\
+        \n\
+        [day02] # bags\n\
+        bag = \"1 red, 2 green, 3 blue\"\n\
+        input = \"big.txt\"\n";
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse("aoc.toml", SAMPLE).unwrap();
+        assert_eq!(config.data_dir, "puzzles/");
+        assert_eq!(config.input, "input.txt");
+        assert_eq!(config.days[&1]["part2_rule"], "digits");
+        assert_eq!(config.days[&2]["bag"], "1 red, 2 green, 3 blue");
+    }
+
+    #[test]
+    fn test_input_path() {
+        let config = Config::parse("aoc.toml", SAMPLE).unwrap();
+        assert_eq!(config.input_path(1), "puzzles/day01/input.txt");
+        assert_eq!(config.input_path(2), "puzzles/day02/big.txt");
+        assert_eq!(Config::default().input_path(10), "data/day10/input.txt");
+    }
+
+    #[test]
+    fn test_day_parameters() {
+        let config = Config::parse("aoc.toml", SAMPLE).unwrap();
+        let parameters = config.day_parameters(2, &["bag"]).unwrap();
+        assert_eq!(parameters.len(), 1);
+        assert_eq!(parameters["bag"], "1 red, 2 green, 3 blue");
+        assert_eq!(config.day_parameters(3, &[]).unwrap().is_empty(), true);
+    }
+
+    #[test]
+    fn test_day_parameters_unknown() {
+        let config = Config::parse("aoc.toml", SAMPLE).unwrap();
+        let error = config.day_parameters(1, &["part1_rule"]).unwrap_err();
+        assert_eq!(error.to_string(), "aoc.toml: unknown parameter \"part2_rule\" for day01; expected one of: input, part1_rule");
+    }
+
+    #[test]
+    fn test_set_parameter_overrides() {
+        let mut config = Config::parse("aoc.toml", SAMPLE).unwrap();
+        config.set_parameter(2, "bag", "5 red");
+        config.set_parameter(4, "input", "other.txt");
+        assert_eq!(config.days[&2]["bag"], "5 red");
+        assert_eq!(config.input_path(4), "puzzles/day04/other.txt");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Config::parse("aoc.toml", "data_dir = data\n[days]\n").unwrap_err();
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.to_string(), "aoc.toml:2: unknown section [days]; sections are named [day01], [day02], ...");
+        assert_eq!(Config::parse("aoc.toml", "colour = red").unwrap_err().message, "unknown top-level key \"colour\" (expected data_dir or input)");
+        assert_eq!(Config::parse("aoc.toml", "[day02]\nbag = 12 red").unwrap_err().line, Some(2));
+        assert_eq!(Config::parse("aoc.toml", "[day02]\nbag\n").unwrap_err().message, "expected key = value, found: bag");
+        assert_eq!(Config::parse("aoc.toml", "[day02]\na = 1\na = 2\n").unwrap_err().message, "duplicate key \"a\" in [day02]");
+    }
+
+    #[test]
+    fn test_load_optional_missing_file() {
+        assert_eq!(Config::load_optional("does/not/exist.toml"), Ok(Config::default()));
+        assert_eq!(Config::load("does/not/exist.toml").is_err(), true);
+    }
+}
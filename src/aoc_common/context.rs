@@ -0,0 +1,204 @@
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::aoc_common::checked::{Arithmetic, OverflowError};
+use crate::aoc_common::terminal::Animation;
+use crate::info;
+
+const CHECK_EVERY: u64 = 4096;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SolveError {
+    Overflow(OverflowError),
+    TimedOut(Duration),
+    Cancelled,
+    InvalidParameter { name: String, message: String },
+    InvalidInput { file: String, message: String },
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            SolveError::Overflow(error) => write!(f, "{}", error),
+            SolveError::TimedOut(elapsed) => write!(f, "timed out after {:.1}s", elapsed.as_secs_f64()),
+            SolveError::Cancelled => write!(f, "cancelled"),
+            SolveError::InvalidParameter { name, message } => write!(f, "invalid parameter {}: {}", name, message),
+            SolveError::InvalidInput { file, message } => write!(f, "{}: {}", file, message),
+        };
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<OverflowError> for SolveError {
+    fn from(error: OverflowError) -> SolveError {
+        return SolveError::Overflow(error);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Context {
+    pub arithmetic: Arithmetic,
+    pub animation: Option<Animation>,
+    pub timeout: Option<Duration>,
+    pub progress_interval: Option<Duration>,
+    pub parameters: BTreeMap<String, String>,
+    started: Instant,
+    last_progress: Cell<Instant>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Default for Context {
+    fn default() -> Context {
+        let now = Instant::now();
+        return Context {
+            arithmetic: Arithmetic::default(),
+            animation: None,
+            timeout: None,
+            progress_interval: None,
+            parameters: BTreeMap::new(),
+            started: now,
+            last_progress: Cell::new(now),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+    }
+}
+
+impl Context {
+    pub fn with_timeout(mut self, timeout: Duration) -> Context {
+        self.timeout = Some(timeout);
+        return self;
+    }
+
+    pub fn with_progress_interval(mut self, interval: Duration) -> Context {
+        self.progress_interval = Some(interval);
+        return self;
+    }
+
+    #[allow(dead_code)]
+    pub fn with_parameter(mut self, name: &str, value: &str) -> Context {
+        self.parameters.insert(name.to_string(), value.to_string());
+        return self;
+    }
+
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        return self.parameters.get(name).map(|value| value.as_str());
+    }
+
+    pub fn cancel_flag(&self) -> Arc<AtomicBool> {
+        return Arc::clone(&self.cancelled);
+    }
+
+    #[allow(dead_code)]
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        return self.started.elapsed();
+    }
+
+    pub fn require_checked(&self, solver: &str) -> Result<(), SolveError> {
+        if self.arithmetic == Arithmetic::Big {
+            return Err(SolveError::InvalidParameter {
+                name: "--big".to_string(),
+                message: format!("{} has no big-number mode", solver),
+            });
+        }
+        return Ok(());
+    }
+
+    pub fn checkpoint(&self, label: &str, done: u64, total: Option<u64>) -> Result<(), SolveError> {
+        if !done.is_multiple_of(CHECK_EVERY) {
+            return Ok(());
+        }
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(SolveError::Cancelled);
+        }
+        let elapsed = self.elapsed();
+        if self.timeout.is_some_and(|timeout| elapsed >= timeout) {
+            return Err(SolveError::TimedOut(elapsed));
+        }
+        if let Some(interval) = self.progress_interval {
+            if self.last_progress.get().elapsed() >= interval {
+                self.last_progress.set(Instant::now());
+                info!("{}", progress_line(label, done, total, elapsed));
+            }
+        }
+        return Ok(());
+    }
+}
+
+fn progress_line(label: &str, done: u64, total: Option<u64>, elapsed: Duration) -> String {
+    return match total {
+        Some(total) if total > 0 => format!(
+            "[{}] {}/{} ({:.1}%) after {:.1}s",
+            label, done, total, done as f64 * 100.0 / total as f64, elapsed.as_secs_f64(),
+        ),
+        _ => format!("[{}] {} after {:.1}s", label, done, elapsed.as_secs_f64()),
+    };
+}
+
+#[cfg(test)]
+mod context_tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_without_limits() {
+        assert_eq!(Context::default().checkpoint("scan", 0, None), Ok(()));
+    }
+
+    #[test]
+    fn test_checkpoint_timed_out() {
+        let context = Context::default().with_timeout(Duration::ZERO);
+        assert_eq!(matches!(context.checkpoint("scan", 0, None), Err(SolveError::TimedOut(_))), true);
+    }
+
+    #[test]
+    fn test_checkpoint_only_checks_periodically() {
+        let context = Context::default().with_timeout(Duration::ZERO);
+        assert_eq!(context.checkpoint("scan", 1, None), Ok(()));
+        assert_eq!(context.checkpoint("scan", CHECK_EVERY, None).is_err(), true);
+    }
+
+    #[test]
+    fn test_checkpoint_cancelled() {
+        let context = Context::default();
+        context.cancel_flag().store(true, Ordering::Relaxed);
+        assert_eq!(context.checkpoint("scan", 0, None), Err(SolveError::Cancelled));
+    }
+
+    #[test]
+    fn test_require_checked() {
+        let mut context = Context::default();
+        assert_eq!(context.require_checked("day10"), Ok(()));
+        context.arithmetic = Arithmetic::Big;
+        assert_eq!(context.require_checked("day10").unwrap_err().to_string(), "invalid parameter --big: day10 has no big-number mode");
+    }
+
+    #[test]
+    fn test_parameter() {
+        let context = Context::default().with_parameter("bag", "1 red");
+        assert_eq!(context.parameter("bag"), Some("1 red"));
+        assert_eq!(context.parameter("rule"), None);
+    }
+
+    #[test]
+    fn test_progress_line() {
+        assert_eq!(progress_line("smallest_time", 25, Some(200), Duration::from_millis(1500)), "[smallest_time] 25/200 (12.5%) after 1.5s");
+        assert_eq!(progress_line("count_steps", 8192, None, Duration::ZERO), "[count_steps] 8192 after 0.0s");
+    }
+
+    #[test]
+    fn test_solve_error_display() {
+        assert_eq!(SolveError::TimedOut(Duration::from_millis(2300)).to_string(), "timed out after 2.3s");
+        let error = SolveError::InvalidParameter { name: "bag".to_string(), message: "expected a count".to_string() };
+        assert_eq!(error.to_string(), "invalid parameter bag: expected a count");
+        let error = SolveError::InvalidInput { file: "input.txt".to_string(), message: "line 3 has no digit".to_string() };
+        assert_eq!(error.to_string(), "input.txt: line 3 has no digit");
+        assert_eq!(SolveError::from(OverflowError { operation: "1 + 2".to_string() }).to_string(), "arithmetic overflow in 1 + 2");
+    }
+}
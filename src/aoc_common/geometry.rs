@@ -0,0 +1,358 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+use crate::aoc_common::math::gcd;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point { pub x: i64, pub y: i64 }
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Vector { pub dx: i64, pub dy: i64 }
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction { North, East, South, West }
+
+impl Point {
+    #[allow(dead_code)]
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Self {
+        return Point { x, y };
+    }
+
+    pub fn step(&self, dir: Direction) -> Point {
+        return *self + dir.delta();
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours(&self) -> [Point; 4] {
+        return Direction::ALL.map(|dir| self.step(dir));
+    }
+
+    #[allow(dead_code)]
+    pub fn manhattan_distance(&self, other: &Point) -> i64 {
+        return (self.x - other.x).abs() + (self.y - other.y).abs();
+    }
+}
+
+impl Vector {
+    #[allow(dead_code)]
+    pub fn new(dx: i64, dy: i64) -> Self {
+        return Vector { dx, dy };
+    }
+
+    #[allow(dead_code)]
+    pub fn manhattan_length(&self) -> i64 {
+        return self.dx.abs() + self.dy.abs();
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Point {
+        return Point { x: self.x + rhs.dx, y: self.y + rhs.dy };
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        self.x += rhs.dx;
+        self.y += rhs.dy;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Point {
+        return Point { x: self.x - rhs.dx, y: self.y - rhs.dy };
+    }
+}
+
+impl Sub<Point> for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Vector {
+        return Vector { dx: self.x - rhs.x, dy: self.y - rhs.y };
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        return Vector { dx: self.dx + rhs.dx, dy: self.dy + rhs.dy };
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i64) -> Vector {
+        return Vector { dx: self.dx * rhs, dy: self.dy * rhs };
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        return Vector { dx: -self.dx, dy: -self.dy };
+    }
+}
+
+#[cfg(test)]
+mod point_tests {
+    use super::*;
+
+    #[test]
+    fn test_step_north_from_origin() {
+        assert_eq!(Point::ORIGIN.step(Direction::North), Point::new(0, -1));
+    }
+
+    #[test]
+    fn test_step_west_from_origin() {
+        assert_eq!(Point::ORIGIN.step(Direction::West), Point::new(-1, 0));
+    }
+
+    #[test]
+    fn test_step_south_and_east() {
+        assert_eq!(Point::new(4, 7).step(Direction::South).step(Direction::East), Point::new(5, 8));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        assert_eq!(Point::new(4, 7).neighbours(), [
+            Point::new(4, 6),
+            Point::new(5, 7),
+            Point::new(4, 8),
+            Point::new(3, 7),
+        ]);
+    }
+
+    #[test]
+    fn test_manhattan_distance() {
+        assert_eq!(Point::new(1, 6).manhattan_distance(&Point::new(5, 11)), 9);
+        assert_eq!(Point::new(5, 11).manhattan_distance(&Point::new(1, 6)), 9);
+        assert_eq!(Point::new(-3, -3).manhattan_distance(&Point::new(-3, -3)), 0);
+    }
+
+    #[test]
+    fn test_point_arithmetic() {
+        let a = Point::new(2, 3);
+        let b = Point::new(-1, 7);
+        assert_eq!(b - a, Vector::new(-3, 4));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(b - (b - a), a);
+        let mut c = a;
+        c += Vector::new(1, 1);
+        assert_eq!(c, Point::new(3, 4));
+    }
+}
+
+#[cfg(test)]
+mod vector_tests {
+    use super::*;
+
+    #[test]
+    fn test_vector_arithmetic() {
+        assert_eq!(Vector::new(1, -2) + Vector::new(3, 4), Vector::new(4, 2));
+        assert_eq!(Vector::new(1, -2) * 3, Vector::new(3, -6));
+        assert_eq!(-Vector::new(1, -2), Vector::new(-1, 2));
+    }
+
+    #[test]
+    fn test_manhattan_length() {
+        assert_eq!(Vector::new(-3, 4).manhattan_length(), 7);
+    }
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+    pub fn delta(&self) -> Vector {
+        match self {
+            Direction::North => Vector { dx: 0, dy: -1 },
+            Direction::East  => Vector { dx: 1, dy: 0 },
+            Direction::South => Vector { dx: 0, dy: 1 },
+            Direction::West  => Vector { dx: -1, dy: 0 },
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East  => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West  => Direction::North,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn turn_left(&self) -> Direction {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East  => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West  => Direction::South,
+        }
+    }
+
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East  => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West  => Direction::East,
+        }
+    }
+}
+
+#[cfg(test)]
+mod direction_tests {
+    use super::*;
+
+    #[test]
+    fn test_delta() {
+        assert_eq!(Direction::North.delta(), Vector::new(0, -1));
+        assert_eq!(Direction::East.delta(), Vector::new(1, 0));
+        assert_eq!(Direction::South.delta(), Vector::new(0, 1));
+        assert_eq!(Direction::West.delta(), Vector::new(-1, 0));
+    }
+
+    #[test]
+    fn test_turn_right() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+    }
+
+    #[test]
+    fn test_turn_left() {
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::East.turn_left(), Direction::North);
+    }
+
+    #[test]
+    fn test_turns_are_inverses() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.opposite());
+        }
+    }
+
+    #[test]
+    fn test_opposite() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.opposite().opposite(), dir);
+            assert_eq!(dir.delta() + dir.opposite().delta(), Vector::default());
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub fn shoelace_double_area(vertices: &[Point]) -> i64 {
+    let mut total = 0;
+    for index in 0..vertices.len() {
+        let a = vertices[index];
+        let b = vertices[(index + 1) % vertices.len()];
+        total += a.x * b.y - b.x * a.y;
+    }
+    return total.abs();
+}
+
+#[cfg(test)]
+mod shoelace_double_area_tests {
+    use super::*;
+
+    #[test]
+    fn test_shoelace_double_area_empty() {
+        assert_eq!(shoelace_double_area(&[]), 0);
+    }
+
+    #[test]
+    fn test_shoelace_double_area_square() {
+        let square = [Point::new(0, 0), Point::new(4, 0), Point::new(4, 4), Point::new(0, 4)];
+        assert_eq!(shoelace_double_area(&square), 32);
+    }
+
+    #[test]
+    fn test_shoelace_double_area_is_orientation_independent() {
+        let triangle = [Point::new(0, 0), Point::new(3, 0), Point::new(0, 3)];
+        let reversed = [Point::new(0, 3), Point::new(3, 0), Point::new(0, 0)];
+        assert_eq!(shoelace_double_area(&triangle), 9);
+        assert_eq!(shoelace_double_area(&reversed), 9);
+    }
+}
+
+#[allow(dead_code)]
+pub fn shoelace_area(vertices: &[Point]) -> f64 {
+    return shoelace_double_area(vertices) as f64 / 2.0;
+}
+
+#[cfg(test)]
+mod shoelace_area_tests {
+    use super::*;
+
+    #[test]
+    fn test_shoelace_area_triangle() {
+        let triangle = [Point::new(0, 0), Point::new(3, 0), Point::new(0, 3)];
+        assert_eq!(shoelace_area(&triangle), 4.5);
+    }
+}
+
+#[allow(dead_code)]
+pub fn boundary_points(vertices: &[Point]) -> i64 {
+    let mut total = 0;
+    for index in 0..vertices.len() {
+        let edge = vertices[(index + 1) % vertices.len()] - vertices[index];
+        total += gcd(edge.dx, edge.dy);
+    }
+    return total;
+}
+
+#[cfg(test)]
+mod boundary_points_tests {
+    use super::*;
+
+    #[test]
+    fn test_boundary_points_square() {
+        let square = [Point::new(0, 0), Point::new(4, 0), Point::new(4, 4), Point::new(0, 4)];
+        assert_eq!(boundary_points(&square), 16);
+    }
+
+    #[test]
+    fn test_boundary_points_diagonal_edges() {
+        let triangle = [Point::new(0, 0), Point::new(3, 0), Point::new(0, 3)];
+        assert_eq!(boundary_points(&triangle), 9);
+    }
+}
+
+#[allow(dead_code)]
+pub fn interior_points(vertices: &[Point]) -> i64 {
+    if vertices.len() < 3 {
+        return 0;
+    }
+    return (shoelace_double_area(vertices) - boundary_points(vertices) + 2) / 2;
+}
+
+#[cfg(test)]
+mod interior_points_tests {
+    use super::*;
+
+    #[test]
+    fn test_interior_points_degenerate() {
+        assert_eq!(interior_points(&[Point::new(0, 0), Point::new(5, 0)]), 0);
+    }
+
+    #[test]
+    fn test_interior_points_square() {
+        let square = [Point::new(0, 0), Point::new(4, 0), Point::new(4, 4), Point::new(0, 4)];
+        assert_eq!(interior_points(&square), 9);
+    }
+
+    #[test]
+    fn test_interior_points_triangle() {
+        let triangle = [Point::new(0, 0), Point::new(3, 0), Point::new(0, 3)];
+        assert_eq!(interior_points(&triangle), 1);
+    }
+}
@@ -0,0 +1,521 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+#[derive(Clone, Debug)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    edges: Vec<Vec<(usize, u64)>>,
+}
+
+impl<N: Clone + Eq + Hash> Default for Graph<N> {
+    fn default() -> Self {
+        return Graph { nodes: vec![], index: HashMap::new(), edges: vec![] };
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Self {
+        return Graph::default();
+    }
+
+    pub fn from_edges<I: IntoIterator<Item = (N, N)>>(edges: I) -> Self {
+        let mut graph = Graph::new();
+        for (from, to) in edges {
+            graph.add_edge(from, to, 1);
+        }
+        return graph;
+    }
+
+    pub fn from_weighted_edges<I: IntoIterator<Item = (N, N, u64)>>(edges: I) -> Self {
+        let mut graph = Graph::new();
+        for (from, to, weight) in edges {
+            graph.add_edge(from, to, weight);
+        }
+        return graph;
+    }
+
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(index) = self.index.get(&node) {
+            return *index;
+        }
+        let index = self.nodes.len();
+        self.index.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.edges.push(vec![]);
+        return index;
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N, weight: u64) {
+        let from_index = self.add_node(from);
+        let to_index = self.add_node(to);
+        self.edges[from_index].push((to_index, weight));
+    }
+
+    pub fn add_undirected_edge(&mut self, a: N, b: N, weight: u64) {
+        self.add_edge(a.clone(), b.clone(), weight);
+        self.add_edge(b, a, weight);
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        return self.index.contains_key(node);
+    }
+
+    pub fn node_count(&self) -> usize {
+        return self.nodes.len();
+    }
+
+    pub fn edge_count(&self) -> usize {
+        return self.edges.iter().map(|edges| edges.len()).sum();
+    }
+
+    pub fn nodes(&self) -> std::slice::Iter<'_, N> {
+        return self.nodes.iter();
+    }
+
+    pub fn neighbours(&self, node: &N) -> Vec<(&N, u64)> {
+        return match self.index.get(node) {
+            Some(index) => self.edges[*index].iter().map(|(to, weight)| (&self.nodes[*to], *weight)).collect(),
+            None => vec![],
+        };
+    }
+
+    fn path_to(&self, previous: &[Option<usize>], goal: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[goal].clone()];
+        let mut current = goal;
+        while let Some(before) = previous[current] {
+            path.push(self.nodes[before].clone());
+            current = before;
+        }
+        path.reverse();
+        return path;
+    }
+}
+
+#[cfg(test)]
+mod graph_tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_graph() {
+        let graph: Graph<i32> = Graph::new();
+        assert_eq!(graph.node_count(), 0);
+        assert_eq!(graph.edge_count(), 0);
+        assert_eq!(graph.neighbours(&1), vec![]);
+    }
+
+    #[test]
+    fn test_from_edges() {
+        let graph = Graph::from_edges([("AAA", "BBB"), ("AAA", "CCC"), ("BBB", "AAA")]);
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.contains(&"CCC"), true);
+        assert_eq!(graph.contains(&"ZZZ"), false);
+        assert_eq!(graph.neighbours(&"AAA"), vec![(&"BBB", 1), (&"CCC", 1)]);
+        assert_eq!(graph.nodes().cloned().collect::<Vec<_>>(), vec!["AAA", "BBB", "CCC"]);
+    }
+
+    #[test]
+    fn test_add_undirected_edge() {
+        let mut graph = Graph::new();
+        graph.add_undirected_edge('a', 'b', 5);
+        assert_eq!(graph.neighbours(&'a'), vec![(&'b', 5)]);
+        assert_eq!(graph.neighbours(&'b'), vec![(&'a', 5)]);
+    }
+
+    #[test]
+    fn test_add_node_is_idempotent() {
+        let mut graph = Graph::new();
+        assert_eq!(graph.add_node(7), 0);
+        assert_eq!(graph.add_node(8), 1);
+        assert_eq!(graph.add_node(7), 0);
+        assert_eq!(graph.node_count(), 2);
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn bfs_distances(&self, start: &N) -> HashMap<N, usize> {
+        let mut distances: HashMap<N, usize> = HashMap::new();
+        let start_index = match self.index.get(start) {
+            Some(index) => *index,
+            None => return distances,
+        };
+        let mut seen = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::from([(start_index, 0)]);
+        seen[start_index] = true;
+        while let Some((current, distance)) = queue.pop_front() {
+            distances.insert(self.nodes[current].clone(), distance);
+            for (next, _) in &self.edges[current] {
+                if !seen[*next] {
+                    seen[*next] = true;
+                    queue.push_back((*next, distance + 1));
+                }
+            }
+        }
+        return distances;
+    }
+}
+
+#[cfg(test)]
+mod bfs_distances_tests {
+    use super::*;
+
+    #[test]
+    fn test_bfs_distances_missing_start() {
+        let graph = Graph::from_edges([(1, 2)]);
+        assert_eq!(graph.bfs_distances(&9).is_empty(), true);
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let graph = Graph::from_edges([(1, 2), (2, 3), (1, 3), (3, 4), (5, 1)]);
+        let distances = graph.bfs_distances(&1);
+        assert_eq!(distances.len(), 4);
+        assert_eq!(distances[&1], 0);
+        assert_eq!(distances[&2], 1);
+        assert_eq!(distances[&3], 1);
+        assert_eq!(distances[&4], 2);
+        assert_eq!(distances.get(&5), None);
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn dijkstra(&self, start: &N) -> HashMap<N, u64> {
+        let mut result: HashMap<N, u64> = HashMap::new();
+        let start_index = match self.index.get(start) {
+            Some(index) => *index,
+            None => return result,
+        };
+        let mut distances: Vec<Option<u64>> = vec![None; self.nodes.len()];
+        let mut heap = BinaryHeap::from([Reverse((0, start_index))]);
+        distances[start_index] = Some(0);
+        while let Some(Reverse((distance, current))) = heap.pop() {
+            if distances[current].is_some_and(|best| best < distance) {
+                continue;
+            }
+            for (next, weight) in &self.edges[current] {
+                let next_distance = distance + weight;
+                if distances[*next].is_none_or(|best| next_distance < best) {
+                    distances[*next] = Some(next_distance);
+                    heap.push(Reverse((next_distance, *next)));
+                }
+            }
+        }
+        for (index, distance) in distances.iter().enumerate() {
+            if let Some(distance) = distance {
+                result.insert(self.nodes[index].clone(), *distance);
+            }
+        }
+        return result;
+    }
+}
+
+#[cfg(test)]
+mod dijkstra_tests {
+    use super::*;
+
+    #[test]
+    fn test_dijkstra_missing_start() {
+        let graph = Graph::from_weighted_edges([('a', 'b', 1)]);
+        assert_eq!(graph.dijkstra(&'z').is_empty(), true);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let graph = Graph::from_weighted_edges([
+            ('a', 'b', 7), ('a', 'c', 9), ('a', 'f', 14),
+            ('b', 'c', 10), ('b', 'd', 15),
+            ('c', 'd', 11), ('c', 'f', 2),
+            ('d', 'e', 6), ('f', 'e', 9),
+        ]);
+        let distances = graph.dijkstra(&'a');
+        assert_eq!(distances[&'a'], 0);
+        assert_eq!(distances[&'b'], 7);
+        assert_eq!(distances[&'c'], 9);
+        assert_eq!(distances[&'d'], 20);
+        assert_eq!(distances[&'e'], 20);
+        assert_eq!(distances[&'f'], 11);
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn a_star<H: Fn(&N) -> u64>(&self, start: &N, goal: &N, heuristic: H) -> Option<(u64, Vec<N>)> {
+        let start_index = *self.index.get(start)?;
+        let goal_index = *self.index.get(goal)?;
+        let mut distances: Vec<Option<u64>> = vec![None; self.nodes.len()];
+        let mut previous: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut heap = BinaryHeap::from([Reverse((heuristic(start), 0, start_index))]);
+        distances[start_index] = Some(0);
+        while let Some(Reverse((_, distance, current))) = heap.pop() {
+            if current == goal_index {
+                return Some((distance, self.path_to(&previous, goal_index)));
+            }
+            if distances[current].is_some_and(|best| best < distance) {
+                continue;
+            }
+            for (next, weight) in &self.edges[current] {
+                let next_distance = distance + weight;
+                if distances[*next].is_none_or(|best| next_distance < best) {
+                    distances[*next] = Some(next_distance);
+                    previous[*next] = Some(current);
+                    heap.push(Reverse((next_distance + heuristic(&self.nodes[*next]), next_distance, *next)));
+                }
+            }
+        }
+        return None;
+    }
+}
+
+#[cfg(test)]
+mod a_star_tests {
+    use super::*;
+
+    fn grid_graph(rows: &[&str]) -> Graph<(i64, i64)> {
+        let mut graph = Graph::new();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, chr) in row.chars().enumerate() {
+                if chr == '#' {
+                    continue;
+                }
+                let here = (x as i64, y as i64);
+                graph.add_node(here);
+                if x > 0 && row.as_bytes()[x - 1] != b'#' {
+                    graph.add_undirected_edge(here, (x as i64 - 1, y as i64), 1);
+                }
+                if y > 0 && rows[y - 1].as_bytes()[x] != b'#' {
+                    graph.add_undirected_edge(here, (x as i64, y as i64 - 1), 1);
+                }
+            }
+        }
+        return graph;
+    }
+
+    #[test]
+    fn test_a_star_grid() {
+        let graph = grid_graph(&[
+            "....#",
+            ".##.#",
+            ".#...",
+            "...#.",
+        ]);
+        let goal = (4, 3);
+        let (distance, path) = graph.a_star(&(0, 0), &goal, |(x, y)| ((goal.0 - x).abs() + (goal.1 - y).abs()) as u64).unwrap();
+        assert_eq!(distance, 7);
+        assert_eq!(path.len(), 8);
+        assert_eq!(path[0], (0, 0));
+        assert_eq!(path[7], goal);
+    }
+
+    #[test]
+    fn test_a_star_matches_dijkstra_with_zero_heuristic() {
+        let graph = Graph::from_weighted_edges([(1, 2, 4), (1, 3, 1), (3, 2, 1), (2, 4, 5)]);
+        assert_eq!(graph.a_star(&1, &4, |_| 0), Some((7, vec![1, 3, 2, 4])));
+        assert_eq!(graph.dijkstra(&1)[&4], 7);
+    }
+
+    #[test]
+    fn test_a_star_unreachable() {
+        let graph = Graph::from_edges([(1, 2), (3, 4)]);
+        assert_eq!(graph.a_star(&1, &4, |_| 0), None);
+        assert_eq!(graph.a_star(&1, &9, |_| 0), None);
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn topological_sort(&self) -> Option<Vec<N>> {
+        let mut in_degree = vec![0; self.nodes.len()];
+        for edges in &self.edges {
+            for (to, _) in edges {
+                in_degree[*to] += 1;
+            }
+        }
+        let mut queue: VecDeque<usize> = (0..self.nodes.len()).filter(|index| in_degree[*index] == 0).collect();
+        let mut order: Vec<N> = vec![];
+        while let Some(current) = queue.pop_front() {
+            order.push(self.nodes[current].clone());
+            for (next, _) in &self.edges[current] {
+                in_degree[*next] -= 1;
+                if in_degree[*next] == 0 {
+                    queue.push_back(*next);
+                }
+            }
+        }
+        if order.len() != self.nodes.len() {
+            return None;
+        }
+        return Some(order);
+    }
+}
+
+#[cfg(test)]
+mod topological_sort_tests {
+    use super::*;
+
+    #[test]
+    fn test_topological_sort() {
+        let graph = Graph::from_edges([("shirt", "tie"), ("tie", "jacket"), ("trousers", "shoes"), ("trousers", "belt"), ("belt", "jacket"), ("shirt", "belt")]);
+        let order = graph.topological_sort().unwrap();
+        let position = |node: &str| order.iter().position(|n| *n == node).unwrap();
+        assert_eq!(order.len(), 6);
+        assert!(position("shirt") < position("tie"));
+        assert!(position("tie") < position("jacket"));
+        assert!(position("belt") < position("jacket"));
+        assert!(position("trousers") < position("shoes"));
+    }
+
+    #[test]
+    fn test_topological_sort_cycle() {
+        let graph = Graph::from_edges([(1, 2), (2, 3), (3, 1)]);
+        assert_eq!(graph.topological_sort(), None);
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    fn finish_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = vec![];
+        let mut seen = vec![false; self.nodes.len()];
+        for root in 0..self.nodes.len() {
+            if seen[root] {
+                continue;
+            }
+            seen[root] = true;
+            let mut stack = vec![(root, 0)];
+            while let Some((current, edge)) = stack.pop() {
+                if edge < self.edges[current].len() {
+                    stack.push((current, edge + 1));
+                    let (next, _) = self.edges[current][edge];
+                    if !seen[next] {
+                        seen[next] = true;
+                        stack.push((next, 0));
+                    }
+                } else {
+                    order.push(current);
+                }
+            }
+        }
+        return order;
+    }
+
+    pub fn strongly_connected_components(&self) -> Vec<Vec<N>> {
+        let mut reversed: Vec<Vec<usize>> = vec![vec![]; self.nodes.len()];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for (to, _) in edges {
+                reversed[*to].push(from);
+            }
+        }
+        let mut components: Vec<Vec<N>> = vec![];
+        let mut assigned = vec![false; self.nodes.len()];
+        for root in self.finish_order().into_iter().rev() {
+            if assigned[root] {
+                continue;
+            }
+            assigned[root] = true;
+            let mut component: Vec<N> = vec![];
+            let mut stack = vec![root];
+            while let Some(current) = stack.pop() {
+                component.push(self.nodes[current].clone());
+                for previous in &reversed[current] {
+                    if !assigned[*previous] {
+                        assigned[*previous] = true;
+                        stack.push(*previous);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        return components;
+    }
+}
+
+#[cfg(test)]
+mod strongly_connected_components_tests {
+    use super::*;
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let graph = Graph::from_edges([
+            ('a', 'b'), ('b', 'c'), ('c', 'a'),
+            ('b', 'd'), ('d', 'e'), ('e', 'd'),
+            ('e', 'f'),
+        ]);
+        let mut components: Vec<Vec<char>> = graph.strongly_connected_components().into_iter().map(|mut component| {
+            component.sort();
+            component
+        }).collect();
+        components.sort();
+        assert_eq!(components, vec![vec!['a', 'b', 'c'], vec!['d', 'e'], vec!['f']]);
+    }
+
+    #[test]
+    fn test_strongly_connected_components_are_in_topological_order() {
+        let graph = Graph::from_edges([(3, 4), (1, 2), (2, 1), (2, 3)]);
+        let components = graph.strongly_connected_components();
+        assert_eq!(components.len(), 3);
+        assert_eq!(components[0].contains(&1), true);
+        assert_eq!(components[1], vec![3]);
+        assert_eq!(components[2], vec![4]);
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn find_cycle(&self) -> Option<Vec<N>> {
+        const UNVISITED: u8 = 0;
+        const ON_STACK: u8 = 1;
+        const DONE: u8 = 2;
+        let mut state = vec![UNVISITED; self.nodes.len()];
+        for root in 0..self.nodes.len() {
+            if state[root] != UNVISITED {
+                continue;
+            }
+            state[root] = ON_STACK;
+            let mut stack = vec![(root, 0)];
+            while let Some((current, edge)) = stack.pop() {
+                if edge == self.edges[current].len() {
+                    state[current] = DONE;
+                    continue;
+                }
+                stack.push((current, edge + 1));
+                let (next, _) = self.edges[current][edge];
+                if state[next] == ON_STACK {
+                    let start = stack.iter().position(|(node, _)| *node == next).unwrap();
+                    return Some(stack[start..].iter().map(|(node, _)| self.nodes[*node].clone()).collect());
+                }
+                if state[next] == UNVISITED {
+                    state[next] = ON_STACK;
+                    stack.push((next, 0));
+                }
+            }
+        }
+        return None;
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        return self.find_cycle().is_some();
+    }
+}
+
+#[cfg(test)]
+mod find_cycle_tests {
+    use super::*;
+
+    #[test]
+    fn test_find_cycle_none() {
+        let graph = Graph::from_edges([(1, 2), (2, 3), (1, 3)]);
+        assert_eq!(graph.find_cycle(), None);
+        assert_eq!(graph.has_cycle(), false);
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let graph = Graph::from_edges([(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]);
+        assert_eq!(graph.find_cycle(), Some(vec![1, 2, 3]));
+        assert_eq!(graph.has_cycle(), true);
+    }
+
+    #[test]
+    fn test_find_cycle_self_loop() {
+        let graph = Graph::from_edges([("AAA", "BBB"), ("ZZZ", "ZZZ")]);
+        assert_eq!(graph.find_cycle(), Some(vec!["ZZZ"]));
+    }
+}
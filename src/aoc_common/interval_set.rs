@@ -0,0 +1,381 @@
+use std::ops::Range;
+use crate::aoc_common::checked::{checked_add, checked_sub, Accumulator, OverflowError};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Range<T>>,
+}
+
+impl<T: Accumulator + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        return IntervalSet { intervals: vec![] };
+    }
+
+    pub fn from_ranges<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        let mut sorted: Vec<Range<T>> = ranges.into_iter().filter(|range| range.start < range.end).collect();
+        sorted.sort_by(|a, b| a.start.cmp(&b.start));
+        let mut intervals: Vec<Range<T>> = vec![];
+        for range in sorted {
+            match intervals.last_mut() {
+                Some(last) if range.start <= last.end => {
+                    if range.end > last.end {
+                        last.end = range.end.clone();
+                    }
+                }
+                _ => intervals.push(range),
+            }
+        }
+        return IntervalSet { intervals };
+    }
+
+    pub fn from_start_and_len(start: T, len: T) -> Result<Self, OverflowError> {
+        let end = checked_add(start.clone(), len)?;
+        return Ok(IntervalSet::from_ranges([start..end]));
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        let mut ranges = std::mem::take(&mut self.intervals);
+        ranges.push(range);
+        *self = IntervalSet::from_ranges(ranges);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.intervals.is_empty();
+    }
+
+    pub fn len(&self) -> usize {
+        return self.intervals.len();
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Range<T>> {
+        return self.intervals.iter();
+    }
+
+    pub fn min(&self) -> Option<T> {
+        return self.intervals.first().map(|range| range.start.clone());
+    }
+
+    pub fn max(&self) -> Option<T> {
+        return self.intervals.last().map(|range| range.end.clone() - T::one());
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        return self.intervals.iter().any(|range| range.start <= value && value < range.end);
+    }
+
+    pub fn total_len(&self) -> Result<T, OverflowError> {
+        let mut total = T::zero();
+        for range in &self.intervals {
+            total = checked_add(total, checked_sub(range.end.clone(), range.start.clone())?)?;
+        }
+        return Ok(total);
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        return IntervalSet::from_ranges(self.intervals.iter().chain(other.intervals.iter()).cloned());
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals: Vec<Range<T>> = vec![];
+        let mut a = 0;
+        let mut b = 0;
+        while a < self.intervals.len() && b < other.intervals.len() {
+            let left = &self.intervals[a];
+            let right = &other.intervals[b];
+            let start = std::cmp::max(&left.start, &right.start).clone();
+            let end = std::cmp::min(&left.end, &right.end).clone();
+            if start < end {
+                intervals.push(start..end);
+            }
+            if left.end < right.end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+        return IntervalSet { intervals };
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals: Vec<Range<T>> = vec![];
+        let mut b = 0;
+        for range in &self.intervals {
+            let mut start = range.start.clone();
+            while b < other.intervals.len() && other.intervals[b].end <= start {
+                b += 1;
+            }
+            let mut index = b;
+            while index < other.intervals.len() && other.intervals[index].start < range.end {
+                let hole = &other.intervals[index];
+                if hole.start > start {
+                    intervals.push(start.clone()..hole.start.clone());
+                }
+                if hole.end > start {
+                    start = hole.end.clone();
+                }
+                index += 1;
+            }
+            if start < range.end {
+                intervals.push(start..range.end.clone());
+            }
+        }
+        return IntervalSet { intervals };
+    }
+
+    pub fn shift(&self, offset: T) -> Result<IntervalSet<T>, OverflowError> {
+        let mut intervals: Vec<Range<T>> = vec![];
+        for range in &self.intervals {
+            let start = checked_add(range.start.clone(), offset.clone())?;
+            let end = checked_add(range.end.clone(), offset.clone())?;
+            intervals.push(start..end);
+        }
+        return Ok(IntervalSet { intervals });
+    }
+
+    pub fn split_at(&self, point: T) -> (IntervalSet<T>, IntervalSet<T>) {
+        let mut below: Vec<Range<T>> = vec![];
+        let mut above: Vec<Range<T>> = vec![];
+        for range in &self.intervals {
+            if range.end <= point {
+                below.push(range.clone());
+            } else if range.start >= point {
+                above.push(range.clone());
+            } else {
+                below.push(range.start.clone()..point.clone());
+                above.push(point.clone()..range.end.clone());
+            }
+        }
+        return (IntervalSet { intervals: below }, IntervalSet { intervals: above });
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = std::slice::Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.intervals.iter();
+    }
+}
+
+#[cfg(test)]
+mod interval_set_tests {
+    use num::BigInt;
+    use super::*;
+
+    fn set(ranges: &[(i64, i64)]) -> IntervalSet<i64> {
+        return IntervalSet::from_ranges(ranges.iter().map(|(start, end)| *start..*end));
+    }
+
+    fn ranges(set: &IntervalSet<i64>) -> Vec<(i64, i64)> {
+        return set.iter().map(|range| (range.start, range.end)).collect();
+    }
+
+    #[test]
+    fn test_from_ranges_empty() {
+        assert_eq!(set(&[]).is_empty(), true);
+        assert_eq!(set(&[(5, 5), (7, 3)]).is_empty(), true);
+    }
+
+    #[test]
+    fn test_from_ranges_merges_overlapping_and_adjacent() {
+        assert_eq!(ranges(&set(&[(10, 20), (0, 5), (5, 8), (15, 25), (30, 31)])), vec![(0, 8), (10, 25), (30, 31)]);
+    }
+
+    #[test]
+    fn test_from_start_and_len() {
+        assert_eq!(ranges(&IntervalSet::from_start_and_len(79, 14).unwrap()), vec![(79, 93)]);
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut intervals = set(&[(0, 5), (10, 15)]);
+        intervals.insert(4..11);
+        assert_eq!(ranges(&intervals), vec![(0, 15)]);
+    }
+
+    #[test]
+    fn test_min_max_contains() {
+        let intervals = set(&[(3, 5), (10, 15)]);
+        assert_eq!(intervals.min(), Some(3));
+        assert_eq!(intervals.max(), Some(14));
+        assert_eq!(intervals.contains(4), true);
+        assert_eq!(intervals.contains(5), false);
+        assert_eq!(intervals.contains(14), true);
+        assert_eq!(set(&[]).min(), None);
+    }
+
+    #[test]
+    fn test_total_len() {
+        assert_eq!(set(&[(3, 5), (10, 15)]).total_len(), Ok(7));
+        assert_eq!(set(&[]).total_len(), Ok(0));
+    }
+
+    #[test]
+    fn test_union() {
+        assert_eq!(ranges(&set(&[(0, 5), (20, 25)]).union(&set(&[(3, 10)]))), vec![(0, 10), (20, 25)]);
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(ranges(&set(&[(0, 5), (8, 25)]).intersection(&set(&[(3, 10), (20, 30)]))), vec![(3, 5), (8, 10), (20, 25)]);
+    }
+
+    #[test]
+    fn test_difference() {
+        assert_eq!(ranges(&set(&[(0, 10), (20, 30)]).difference(&set(&[(2, 4), (6, 22), (29, 40)]))), vec![(0, 2), (4, 6), (22, 29)]);
+    }
+
+    #[test]
+    fn test_shift() {
+        assert_eq!(ranges(&set(&[(0, 5), (8, 10)]).shift(-3).unwrap()), vec![(-3, 2), (5, 7)]);
+    }
+
+    #[test]
+    fn test_shift_overflow() {
+        assert_eq!(set(&[(0, 5), (i64::MAX - 10, i64::MAX - 2)]).shift(3).is_err(), true);
+    }
+
+    #[test]
+    fn test_from_start_and_len_overflow() {
+        assert_eq!(IntervalSet::from_start_and_len(i64::MAX - 1, 5).is_err(), true);
+    }
+
+    #[test]
+    fn test_total_len_overflow() {
+        assert_eq!(set(&[(i64::MIN, 0), (1, 2)]).total_len().is_err(), true);
+    }
+
+    #[test]
+    fn test_big() {
+        let start = BigInt::from(i64::MAX);
+        let intervals = IntervalSet::from_start_and_len(start.clone(), BigInt::from(10)).unwrap().shift(BigInt::from(5)).unwrap();
+        assert_eq!(intervals.min(), Some(start.clone() + 5));
+        assert_eq!(intervals.max(), Some(start + 14));
+        assert_eq!(intervals.total_len(), Ok(BigInt::from(10)));
+    }
+
+    #[test]
+    fn test_split_at() {
+        let (below, above) = set(&[(0, 5), (8, 12)]).split_at(10);
+        assert_eq!(ranges(&below), vec![(0, 5), (8, 10)]);
+        assert_eq!(ranges(&above), vec![(10, 12)]);
+    }
+}
+
+#[cfg(test)]
+mod interval_set_property_tests {
+    use std::collections::BTreeSet;
+    use super::*;
+
+    struct Xorshift(u64);
+
+    impl Xorshift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            return self.0;
+        }
+
+        fn below(&mut self, limit: i64) -> i64 {
+            return (self.next() % limit as u64) as i64;
+        }
+    }
+
+    fn random_set(rng: &mut Xorshift) -> IntervalSet<i64> {
+        let count = rng.below(6);
+        return IntervalSet::from_ranges((0..count).map(|_| {
+            let start = rng.below(60) - 10;
+            start..start + rng.below(15)
+        }));
+    }
+
+    fn model(set: &IntervalSet<i64>) -> BTreeSet<i64> {
+        return set.iter().flat_map(|range| range.clone()).collect();
+    }
+
+    fn is_normalized(set: &IntervalSet<i64>) -> bool {
+        let intervals: Vec<&Range<i64>> = set.iter().collect();
+        return intervals.iter().all(|range| range.start < range.end)
+            && intervals.windows(2).all(|pair| pair[0].end < pair[1].start);
+    }
+
+    fn check_cases(check: fn(&IntervalSet<i64>, &IntervalSet<i64>, i64)) {
+        let mut rng = Xorshift(0x2023_1205);
+        for _ in 0..500 {
+            let a = random_set(&mut rng);
+            let b = random_set(&mut rng);
+            let value = rng.below(80) - 20;
+            check(&a, &b, value);
+        }
+    }
+
+    #[test]
+    fn test_union_matches_model() {
+        check_cases(|a, b, _| {
+            let result = a.union(b);
+            assert!(is_normalized(&result));
+            assert_eq!(model(&result), model(a).union(&model(b)).copied().collect());
+        });
+    }
+
+    #[test]
+    fn test_intersection_matches_model() {
+        check_cases(|a, b, _| {
+            let result = a.intersection(b);
+            assert!(is_normalized(&result));
+            assert_eq!(model(&result), model(a).intersection(&model(b)).copied().collect());
+        });
+    }
+
+    #[test]
+    fn test_difference_matches_model() {
+        check_cases(|a, b, _| {
+            let result = a.difference(b);
+            assert!(is_normalized(&result));
+            assert_eq!(model(&result), model(a).difference(&model(b)).copied().collect());
+        });
+    }
+
+    #[test]
+    fn test_shift_matches_model() {
+        check_cases(|a, _, offset| {
+            let result = a.shift(offset).unwrap();
+            assert!(is_normalized(&result));
+            assert_eq!(model(&result), model(a).iter().map(|n| n + offset).collect());
+        });
+    }
+
+    #[test]
+    fn test_split_at_matches_model() {
+        check_cases(|a, _, point| {
+            let (below, above) = a.split_at(point);
+            assert!(is_normalized(&below));
+            assert!(is_normalized(&above));
+            assert_eq!(model(&below), model(a).iter().filter(|n| **n < point).copied().collect());
+            assert_eq!(model(&above), model(a).iter().filter(|n| **n >= point).copied().collect());
+        });
+    }
+
+    #[test]
+    fn test_total_len_matches_model() {
+        check_cases(|a, _, _| {
+            assert_eq!(a.total_len(), Ok(model(a).len() as i64));
+        });
+    }
+
+    #[test]
+    fn test_contains_matches_model() {
+        check_cases(|a, _, value| {
+            assert_eq!(a.contains(value), model(a).contains(&value));
+        });
+    }
+
+    #[test]
+    fn test_difference_and_intersection_partition() {
+        check_cases(|a, b, _| {
+            assert_eq!(a.difference(b).union(&a.intersection(b)), *a);
+        });
+    }
+}
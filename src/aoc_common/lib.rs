@@ -1,7 +1,9 @@
 use std::fs::File;
 use std::io::{BufRead, BufReader, Lines};
-use std::iter::FilterMap;
-use num::Num;
+use std::iter::MapWhile;
+use crate::aoc_common::answer::Answer;
+use crate::aoc_common::checked::{checked_add, Accumulator, OverflowError};
+use crate::aoc_common::context::SolveError;
 
 pub fn get_series_of_ints<T: std::str::FromStr>(source: &str) -> Vec<T> {
     return source.split(" ").filter_map(|n| n.parse::<T>().ok()).collect();
@@ -24,24 +26,12 @@ mod get_series_of_ints_tests {
     }
 }
 
-pub fn sum<T: Num>(a: T, b: T) -> T {
-    return a + b;
-}
-
-#[cfg(test)]
-mod sum_tests {
-    use super::*;
-
-    #[test]
-    fn test_sum() {
-        assert_eq!(sum(3, 4), 7);
-    }
-}
+type LineIterator = MapWhile<Lines<BufReader<File>>, fn(std::io::Result<String>) -> Option<String>>;
 
-pub fn line_iterator(filename: &str) -> FilterMap<Lines<BufReader<File>>, fn(std::io::Result<String>) -> Option<String>> {
-    return BufReader::new(File::open(filename).expect(&*format!("Failed to open {}", filename)))
+pub fn line_iterator(filename: &str) -> LineIterator {
+    return BufReader::new(File::open(filename).unwrap_or_else(|_| panic!("Failed to open {}", filename)))
         .lines()
-        .filter_map(|result| result.ok());
+        .map_while(|result| result.ok());
 }
 
 #[cfg(test)]
@@ -58,52 +48,58 @@ mod line_iterator_tests {
     }
 }
 
-pub fn sum_up<T: Num>(filename: &str, map_function: fn(&str) -> T, reduce_function: fn(T, T) -> T) -> T {
-    return line_iterator(filename)
-        .map(|line| map_function(line.as_str()))
-        .reduce(reduce_function)
-        .unwrap_or(num::zero::<T>());
+pub fn sum_up<T: Accumulator + From<V>, V>(filename: &str, map_function: fn(&str) -> Result<V, OverflowError>) -> Result<T, OverflowError> {
+    let mut total = T::zero();
+    for line in line_iterator(filename) {
+        total = checked_add(total, T::from(map_function(line.as_str())?))?;
+    }
+    return Ok(total);
 }
 
 #[cfg(test)]
 mod sum_up_tests {
+    use num::BigInt;
     use super::*;
 
-    fn int_value(line: &str) -> i32 {
-        return line.parse::<i32>().unwrap_or(0);
+    fn int_value(line: &str) -> Result<i32, OverflowError> {
+        return Ok(line.parse::<i32>().unwrap_or(0));
+    }
+
+    fn huge_value(line: &str) -> Result<i64, OverflowError> {
+        return Ok(line.parse::<i64>().unwrap_or(0) * (i64::MAX / 8));
     }
 
     #[test]
     fn test_sum_up() {
-        assert_eq!(sum_up("data/aoc_common/test_data.txt", int_value, sum), 15);
+        assert_eq!(sum_up::<i32, i32>("data/aoc_common/test_data.txt", int_value), Ok(15));
     }
-}
 
-pub fn sum_up_with_rule<T: Num>(filename: &str, map_function: fn(&str, &str) -> T, reduce_function: fn(T, T) -> T, rule: &str) -> T {
-    return line_iterator(filename)
-        .map(|line| map_function(line.as_str(), rule))
-        .reduce(reduce_function)
-        .unwrap_or(num::zero::<T>());
-}
-
-#[cfg(test)]
-mod sum_up_with_rule_tests {
-    use super::*;
-
-    fn int_value_with_rule(line: &str, rule: &str) -> i32 {
-        if rule == "count" {
-            return line.parse::<i32>().map(|_a| 1).unwrap_or(0);
-        }
-        return line.parse::<i32>().unwrap_or(0);
+    #[test]
+    fn test_sum_up_overflow() {
+        assert_eq!(sum_up::<i64, i64>("data/aoc_common/test_data.txt", huge_value).is_err(), true);
     }
 
     #[test]
-    fn test_sum_up_with_rule_empty() {
-        assert_eq!(sum_up_with_rule("data/aoc_common/test_data.txt", int_value_with_rule, sum, ""), 15);
+    fn test_sum_up_big() {
+        let total = sum_up::<BigInt, i64>("data/aoc_common/test_data.txt", huge_value).unwrap();
+        assert_eq!(total, BigInt::from(15) * BigInt::from(i64::MAX / 8));
     }
+}
 
-    #[test]
-    fn test_sum_up_with_rule_count() {
-        assert_eq!(sum_up_with_rule("data/aoc_common/test_data.txt", int_value_with_rule, sum, "count"), 5);
+pub fn print_result(day: i32, part: i32, result: Result<Answer, SolveError>) {
+    match result {
+        Ok(answer) => println!("Day {} Part {} result: {}", day, part, answer),
+        Err(SolveError::Overflow(error)) => {
+            eprintln!("Day {} Part {} failed: {}", day, part, error);
+            std::process::exit(2);
+        }
+        Err(error @ (SolveError::InvalidParameter { .. } | SolveError::InvalidInput { .. })) => {
+            eprintln!("Day {} Part {}: {}", day, part, error);
+            std::process::exit(1);
+        }
+        Err(error) => {
+            eprintln!("Day {} Part {} {}", day, part, error);
+            std::process::exit(3);
+        }
     }
 }
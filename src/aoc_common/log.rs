@@ -0,0 +1,253 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+pub const ENV_VAR: &str = "AOC_LOG";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Level {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+const LEVELS: [Level;6] = [Level::Off, Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace];
+
+impl Level {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Level::Off => "OFF",
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+    }
+
+    pub fn shifted(self, steps: i32) -> Level {
+        let index = (self as i32 + steps).clamp(0, LEVELS.len() as i32 - 1);
+        return LEVELS[index as usize];
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Level, String> {
+        return LEVELS.iter()
+            .find(|level| level.name().eq_ignore_ascii_case(text.trim()))
+            .copied()
+            .ok_or_else(|| format!("unknown log level \"{}\" (expected off, error, warn, info, debug or trace)", text.trim()));
+    }
+}
+
+#[cfg(test)]
+mod level_tests {
+    use super::*;
+
+    #[test]
+    fn test_level_from_str() {
+        assert_eq!("debug".parse::<Level>(), Ok(Level::Debug));
+        assert_eq!(" WARN ".parse::<Level>(), Ok(Level::Warn));
+        assert_eq!("loud".parse::<Level>().is_err(), true);
+    }
+
+    #[test]
+    fn test_level_shifted() {
+        assert_eq!(Level::Warn.shifted(1), Level::Info);
+        assert_eq!(Level::Warn.shifted(-1), Level::Error);
+        assert_eq!(Level::Warn.shifted(10), Level::Trace);
+        assert_eq!(Level::Warn.shifted(-10), Level::Off);
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Filter {
+    pub default: Level,
+    pub modules: Vec<(String, Level)>,
+}
+
+impl Default for Filter {
+    fn default() -> Filter {
+        return Filter::new(Level::Warn);
+    }
+}
+
+fn module_matches(module: &str, target: &str) -> bool {
+    return module == target || module.strip_prefix(target).is_some_and(|rest| rest.starts_with("::"));
+}
+
+impl Filter {
+    pub const fn new(default: Level) -> Filter {
+        return Filter { default, modules: Vec::new() };
+    }
+
+    pub fn parse(spec: &str) -> Result<Filter, String> {
+        let mut filter = Filter::default();
+        for directive in spec.split(',').map(str::trim).filter(|directive| !directive.is_empty()) {
+            match directive.split_once('=') {
+                Some((module, level)) => {
+                    let module = module.trim();
+                    if module.is_empty() {
+                        return Err(format!("missing module name in \"{}\"", directive));
+                    }
+                    filter.modules.push((module.to_string(), level.parse()?));
+                }
+                None => filter.default = directive.parse()?,
+            }
+        }
+        return Ok(filter);
+    }
+
+    pub fn with_verbosity(mut self, steps: i32) -> Filter {
+        self.default = self.default.shifted(steps);
+        return self;
+    }
+
+    pub fn with_minimum(mut self, module: &str, level: Level) -> Filter {
+        let named = self.modules.iter().any(|(target, _)| module_matches(module, target));
+        if !named && self.level_for(module) < level {
+            self.modules.push((module.to_string(), level));
+        }
+        return self;
+    }
+
+    pub fn level_for(&self, module: &str) -> Level {
+        return self.modules.iter()
+            .filter(|(target, _)| module_matches(module, target))
+            .max_by_key(|(target, _)| target.len())
+            .map_or(self.default, |(_, level)| *level);
+    }
+
+    pub fn enabled(&self, level: Level, module: &str) -> bool {
+        return level != Level::Off && level <= self.level_for(module);
+    }
+}
+
+#[cfg(test)]
+mod filter_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let filter = Filter::parse("info, day08=trace,aoc_common::context=off").unwrap();
+        assert_eq!(filter.default, Level::Info);
+        assert_eq!(filter.modules, vec![("day08".to_string(), Level::Trace), ("aoc_common::context".to_string(), Level::Off)]);
+        assert_eq!(Filter::parse(""), Ok(Filter::default()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Filter::parse("day08=noisy").is_err(), true);
+        assert_eq!(Filter::parse("=debug"), Err("missing module name in \"=debug\"".to_string()));
+    }
+
+    #[test]
+    fn test_level_for_most_specific_module() {
+        let filter = Filter::parse("error,day08=debug,day08::day08=trace").unwrap();
+        assert_eq!(filter.level_for("day08::day08"), Level::Trace);
+        assert_eq!(filter.level_for("day08::other"), Level::Debug);
+        assert_eq!(filter.level_for("day080"), Level::Error);
+        assert_eq!(filter.level_for("day05::day05"), Level::Error);
+    }
+
+    #[test]
+    fn test_enabled() {
+        let filter = Filter::parse("warn,day05=debug").unwrap().with_verbosity(-1);
+        assert_eq!(filter.enabled(Level::Warn, "day03::day03"), false);
+        assert_eq!(filter.enabled(Level::Error, "day03::day03"), true);
+        assert_eq!(filter.enabled(Level::Debug, "day05::day05"), true);
+        assert_eq!(filter.enabled(Level::Off, "day05::day05"), false);
+    }
+
+    #[test]
+    fn test_with_minimum() {
+        let filter = Filter::default().with_minimum("aoc_common::context", Level::Info);
+        assert_eq!(filter.level_for("aoc_common::context"), Level::Info);
+        assert_eq!(filter.level_for("day05::day05"), Level::Warn);
+        let filter = Filter::parse("trace").unwrap().with_minimum("aoc_common::context", Level::Info);
+        assert_eq!(filter.level_for("aoc_common::context"), Level::Trace);
+    }
+
+    #[test]
+    fn test_with_minimum_keeps_explicit_directive() {
+        let filter = Filter::parse("aoc_common::context=off").unwrap().with_minimum("aoc_common::context", Level::Info);
+        assert_eq!(filter.level_for("aoc_common::context"), Level::Off);
+    }
+}
+
+static FILTER: RwLock<Filter> = RwLock::new(Filter::new(Level::Warn));
+
+pub fn set_filter(filter: Filter) {
+    *FILTER.write().unwrap() = filter;
+}
+
+pub fn module_name(module_path: &str) -> &str {
+    return module_path.split_once("::").map_or("main", |(_, rest)| rest);
+}
+
+pub fn format_line(level: Level, module: &str, args: fmt::Arguments) -> String {
+    return format!("[{:<5} {}] {}", level.name(), module, args);
+}
+
+#[cfg(test)]
+mod format_line_tests {
+    use super::*;
+
+    #[test]
+    fn test_module_name() {
+        assert_eq!(module_name("advent_of_code_2023::day08::day08"), "day08::day08");
+        assert_eq!(module_name("advent_of_code_2023"), "main");
+    }
+
+    #[test]
+    fn test_format_line() {
+        assert_eq!(format_line(Level::Info, "day05::day05", format_args!("{} seeds", 20)), "[INFO  day05::day05] 20 seeds");
+        assert_eq!(format_line(Level::Debug, "day08::day08", format_args!("done")), "[DEBUG day08::day08] done");
+    }
+}
+
+pub fn enabled(level: Level, module_path: &str) -> bool {
+    return FILTER.read().unwrap().enabled(level, module_name(module_path));
+}
+
+pub fn write(level: Level, module_path: &str, args: fmt::Arguments) {
+    if enabled(level, module_path) {
+        eprintln!("{}", format_line(level, module_name(module_path), args));
+    }
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::aoc_common::log::write($crate::aoc_common::log::Level::Error, module_path!(), format_args!($($arg)+)) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::aoc_common::log::write($crate::aoc_common::log::Level::Warn, module_path!(), format_args!($($arg)+)) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::aoc_common::log::write($crate::aoc_common::log::Level::Info, module_path!(), format_args!($($arg)+)) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::aoc_common::log::write($crate::aoc_common::log::Level::Debug, module_path!(), format_args!($($arg)+)) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::aoc_common::log::write($crate::aoc_common::log::Level::Trace, module_path!(), format_args!($($arg)+)) };
+}
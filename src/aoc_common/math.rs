@@ -0,0 +1,346 @@
+use num::{CheckedMul, CheckedSub, Integer, Signed};
+
+#[allow(dead_code)]
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let mut a = a.abs();
+    let mut b = b.abs();
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    return a;
+}
+
+#[cfg(test)]
+mod gcd_tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(17, 5), 1);
+    }
+
+    #[test]
+    fn test_gcd_zero() {
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(gcd(0, 0), 0);
+    }
+
+    #[test]
+    fn test_gcd_negative() {
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(12, -18), 6);
+    }
+}
+
+fn checked_abs<T: Signed + CheckedSub>(value: T) -> Option<T> {
+    if value.is_negative() {
+        return T::zero().checked_sub(&value);
+    }
+    return Some(value);
+}
+
+#[cfg(test)]
+mod checked_abs_tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_abs() {
+        assert_eq!(checked_abs(-7), Some(7));
+        assert_eq!(checked_abs(7), Some(7));
+        assert_eq!(checked_abs(i64::MIN), None);
+    }
+}
+
+pub fn lcm<T: Clone + Integer + Signed + CheckedMul + CheckedSub>(a: T, b: T) -> Option<T> {
+    if a.is_zero() || b.is_zero() {
+        return Some(T::zero());
+    }
+    let a = checked_abs(a)?;
+    let b = checked_abs(b)?;
+    return (a.clone() / a.gcd(&b)).checked_mul(&b);
+}
+
+#[cfg(test)]
+mod lcm_tests {
+    use num::BigInt;
+    use super::*;
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(6, 4), Some(12));
+    }
+
+    #[test]
+    fn test_lcm_repeated_factors() {
+        assert_eq!(lcm(8, 2), Some(8));
+        assert_eq!(lcm(9, 12), Some(36));
+    }
+
+    #[test]
+    fn test_lcm_zero() {
+        assert_eq!(lcm(0, 5), Some(0));
+    }
+
+    #[test]
+    fn test_lcm_overflow() {
+        assert_eq!(lcm(i64::MAX, i64::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_lcm_negative() {
+        assert_eq!(lcm(-6, 4), Some(12));
+        assert_eq!(lcm(6, -4), Some(12));
+    }
+
+    #[test]
+    fn test_lcm_minimum_value() {
+        assert_eq!(lcm(i64::MIN, 1), None);
+        assert_eq!(lcm(i64::MIN, i64::MIN), None);
+        assert_eq!(lcm(1, i64::MIN), None);
+    }
+
+    #[test]
+    fn test_lcm_big() {
+        let result = lcm(BigInt::from(i64::MAX), BigInt::from(i64::MAX - 1));
+        assert_eq!(result, Some(BigInt::from(i64::MAX) * BigInt::from(i64::MAX - 1)));
+    }
+}
+
+pub fn lcm_all<T: Clone + Integer + Signed + CheckedMul + CheckedSub, I: IntoIterator<Item = T>>(values: I) -> Option<T> {
+    let mut result = T::one();
+    for value in values {
+        result = lcm(result, value)?;
+    }
+    return Some(result);
+}
+
+#[cfg(test)]
+mod lcm_all_tests {
+    use super::*;
+
+    #[test]
+    fn test_lcm_all_empty() {
+        assert_eq!(lcm_all(Vec::<i64>::new()), Some(1));
+    }
+
+    #[test]
+    fn test_lcm_all() {
+        assert_eq!(lcm_all(vec![2, 3, 4, 8]), Some(24));
+        assert_eq!(lcm_all(1..=10), Some(2520));
+    }
+
+    #[test]
+    fn test_lcm_all_overflow() {
+        assert_eq!(lcm_all(1..=100i64), None);
+    }
+}
+
+#[allow(dead_code)]
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        return if a < 0 { (-a, -1, 0) } else { (a, 1, 0) };
+    }
+    let (g, x, y) = extended_gcd(b, a % b);
+    return (g, y, x - (a / b) * y);
+}
+
+#[cfg(test)]
+mod extended_gcd_tests {
+    use super::*;
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn test_extended_gcd_negative() {
+        let (g, x, y) = extended_gcd(-35, 15);
+        assert_eq!(g, 5);
+        assert_eq!(-35 * x + 15 * y, 5);
+    }
+}
+
+#[allow(dead_code)]
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    if g != 1 {
+        return None;
+    }
+    return Some(x.rem_euclid(modulus));
+}
+
+#[cfg(test)]
+mod mod_inverse_tests {
+    use super::*;
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(10, 17), Some(12));
+    }
+
+    #[test]
+    fn test_mod_inverse_negative() {
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+    }
+
+    #[test]
+    fn test_mod_inverse_not_coprime() {
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+}
+
+#[allow(dead_code)]
+pub fn mod_pow(base: i64, exponent: u64, modulus: i64) -> i64 {
+    if modulus == 1 {
+        return 0;
+    }
+    let modulus = modulus as i128;
+    let mut result: i128 = 1;
+    let mut base = (base as i128).rem_euclid(modulus);
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    return result as i64;
+}
+
+#[cfg(test)]
+mod mod_pow_tests {
+    use super::*;
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(2, 0, 7), 1);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+    }
+
+    #[test]
+    fn test_mod_pow_large_modulus() {
+        assert_eq!(mod_pow(3, 1_000_000, 1_000_000_007), 64_935_414);
+        assert_eq!(mod_pow(i64::MAX - 1, 2, i64::MAX), 1);
+    }
+
+    #[test]
+    fn test_mod_pow_negative_base() {
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+    }
+}
+
+#[allow(dead_code)]
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut result: i128 = 0;
+    let mut modulus: i128 = 1;
+    for &(residue, next_modulus) in congruences {
+        let next_modulus = next_modulus as i128;
+        let residue = (residue as i128).rem_euclid(next_modulus);
+        let g = gcd(modulus as i64, next_modulus as i64) as i128;
+        if (residue - result) % g != 0 {
+            return None;
+        }
+        let (_, inverse, _) = extended_gcd((modulus / g) as i64, (next_modulus / g) as i64);
+        let step = next_modulus / g;
+        let k = ((residue - result) / g % step) * (inverse as i128 % step) % step;
+        let combined_modulus = modulus * step;
+        if combined_modulus > i64::MAX as i128 {
+            return None;
+        }
+        result = (result + modulus * k).rem_euclid(combined_modulus);
+        modulus = combined_modulus;
+    }
+    return Some((result as i64, modulus as i64));
+}
+
+#[cfg(test)]
+mod crt_tests {
+    use super::*;
+
+    #[test]
+    fn test_crt_empty() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_crt_coprime() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+    }
+
+    #[test]
+    fn test_crt_non_coprime() {
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+    }
+
+    #[test]
+    fn test_crt_inconsistent() {
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn test_crt_negative_residue() {
+        assert_eq!(crt(&[(-1, 5), (0, 3)]), Some((9, 15)));
+    }
+
+    #[test]
+    fn test_crt_large_moduli() {
+        let (result, modulus) = crt(&[(1, 1_000_000_007), (2, 998_244_353)]).unwrap();
+        assert_eq!(modulus, 1_000_000_007 * 998_244_353);
+        assert_eq!(result % 1_000_000_007, 1);
+        assert_eq!(result % 998_244_353, 2);
+    }
+}
+
+#[allow(dead_code)]
+pub fn prime_sieve(limit: usize) -> Vec<usize> {
+    if limit < 2 {
+        return vec![];
+    }
+    let mut is_prime = vec![true; limit + 1];
+    is_prime[0] = false;
+    is_prime[1] = false;
+    let mut index = 2;
+    while index * index <= limit {
+        if is_prime[index] {
+            let mut multiple = index * index;
+            while multiple <= limit {
+                is_prime[multiple] = false;
+                multiple += index;
+            }
+        }
+        index += 1;
+    }
+    return (0..=limit).filter(|n| is_prime[*n]).collect();
+}
+
+#[cfg(test)]
+mod prime_sieve_tests {
+    use super::*;
+
+    #[test]
+    fn test_prime_sieve_small() {
+        assert_eq!(prime_sieve(0), vec![]);
+        assert_eq!(prime_sieve(1), vec![]);
+        assert_eq!(prime_sieve(2), vec![2]);
+    }
+
+    #[test]
+    fn test_prime_sieve() {
+        assert_eq!(prime_sieve(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+    }
+
+    #[test]
+    fn test_prime_sieve_count() {
+        assert_eq!(prime_sieve(10_000).len(), 1229);
+    }
+}
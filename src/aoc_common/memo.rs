@@ -0,0 +1,198 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+pub struct Memo<K, V> {
+    function: fn(&mut Memo<K, V>, &K) -> V,
+    cache: HashMap<K, (V, u64)>,
+    recency: BTreeMap<u64, K>,
+    capacity: Option<usize>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new(function: fn(&mut Memo<K, V>, &K) -> V) -> Self {
+        return Memo {
+            function,
+            cache: HashMap::new(),
+            recency: BTreeMap::new(),
+            capacity: None,
+            tick: 0,
+            hits: 0,
+            misses: 0,
+        };
+    }
+
+    pub fn with_capacity(function: fn(&mut Memo<K, V>, &K) -> V, capacity: usize) -> Self {
+        let mut memo = Memo::new(function);
+        memo.capacity = Some(capacity);
+        return memo;
+    }
+
+    pub fn get(&mut self, key: K) -> V {
+        self.tick += 1;
+        if let Some((value, last_used)) = self.cache.get_mut(&key) {
+            self.hits += 1;
+            if self.capacity.is_some() {
+                self.recency.remove(last_used);
+                self.recency.insert(self.tick, key);
+                *last_used = self.tick;
+            }
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = (self.function)(self, &key);
+        self.insert(key, value.clone());
+        return value;
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == Some(0) {
+            return;
+        }
+        self.tick += 1;
+        if let Some(capacity) = self.capacity {
+            if let Some((_, last_used)) = self.cache.get(&key) {
+                self.recency.remove(last_used);
+            }
+            self.recency.insert(self.tick, key.clone());
+            self.cache.insert(key, (value, self.tick));
+            while self.cache.len() > capacity {
+                let (_, oldest) = self.recency.pop_first().unwrap();
+                self.cache.remove(&oldest);
+            }
+            return;
+        }
+        self.cache.insert(key, (value, self.tick));
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        return self.cache.contains_key(key);
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.recency.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.cache.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.cache.is_empty();
+    }
+
+    pub fn hits(&self) -> u64 {
+        return self.hits;
+    }
+
+    pub fn misses(&self) -> u64 {
+        return self.misses;
+    }
+
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+        return self.hits as f64 / lookups as f64;
+    }
+}
+
+#[cfg(test)]
+mod memo_tests {
+    use super::*;
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: &u64) -> u64 {
+        if *n < 2 {
+            return *n;
+        }
+        return memo.get(n - 1) + memo.get(n - 2);
+    }
+
+    fn arrangements(memo: &mut Memo<(usize, u64), u64>, key: &(usize, u64)) -> u64 {
+        const COINS: [u64; 4] = [1, 2, 5, 10];
+        let (index, remaining) = *key;
+        if remaining == 0 {
+            return 1;
+        }
+        if index == COINS.len() {
+            return 0;
+        }
+        let mut total = memo.get((index + 1, remaining));
+        if COINS[index] <= remaining {
+            total += memo.get((index, remaining - COINS[index]));
+        }
+        return total;
+    }
+
+    #[test]
+    fn test_get_recursive() {
+        let mut memo = Memo::new(fibonacci);
+        assert_eq!(memo.get(90), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+    }
+
+    #[test]
+    fn test_get_tuple_key() {
+        let mut memo = Memo::new(arrangements);
+        assert_eq!(memo.get((0, 10)), 11);
+        assert_eq!(memo.get((0, 100)), 2156);
+    }
+
+    #[test]
+    fn test_hit_rate() {
+        let mut memo = Memo::new(fibonacci);
+        assert_eq!(memo.hit_rate(), 0.0);
+        memo.get(10);
+        assert_eq!(memo.misses(), 11);
+        assert_eq!(memo.hits(), 8);
+        memo.get(10);
+        assert_eq!(memo.hits(), 9);
+        assert_eq!(memo.hit_rate(), 9.0 / 20.0);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut memo = Memo::new(fibonacci);
+        memo.get(10);
+        memo.clear();
+        assert_eq!(memo.is_empty(), true);
+        assert_eq!(memo.hits(), 0);
+        assert_eq!(memo.misses(), 0);
+        assert_eq!(memo.get(10), 55);
+    }
+
+    #[test]
+    fn test_with_capacity_evicts_least_recently_used() {
+        let mut memo: Memo<u64, u64> = Memo::with_capacity(|_, n| n * n, 2);
+        memo.get(1);
+        memo.get(2);
+        memo.get(1);
+        memo.get(3);
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.contains(&1), true);
+        assert_eq!(memo.contains(&2), false);
+        assert_eq!(memo.contains(&3), true);
+    }
+
+    #[test]
+    fn test_with_capacity_recursive() {
+        let mut memo = Memo::with_capacity(fibonacci, 3);
+        assert_eq!(memo.get(50), 12586269025);
+        assert_eq!(memo.len(), 3);
+    }
+
+    #[test]
+    fn test_with_zero_capacity() {
+        let mut memo: Memo<u64, u64> = Memo::with_capacity(|_, n| n + 1, 0);
+        assert_eq!(memo.get(1), 2);
+        assert_eq!(memo.get(1), 2);
+        assert_eq!(memo.is_empty(), true);
+        assert_eq!(memo.hits(), 0);
+    }
+}
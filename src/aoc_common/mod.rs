@@ -1 +1,20 @@
+pub mod answer;
+pub mod automaton;
+pub mod bench;
+pub mod checked;
+pub mod config;
+pub mod context;
+pub mod geometry;
+#[allow(dead_code)]
+pub mod graph;
+pub mod interval_set;
 pub mod lib;
+pub mod log;
+pub mod math;
+#[allow(dead_code)]
+pub mod memo;
+pub mod pattern;
+pub mod render;
+pub mod sections;
+pub mod table;
+pub mod terminal;
@@ -0,0 +1,292 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Part {
+    Literal(String),
+    Capture(String),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pattern {
+    template: String,
+    parts: Vec<Part>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatternError {
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "template column {}: {}", self.column + 1, self.message);
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchError {
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for MatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "column {}: {}", self.column + 1, self.message);
+    }
+}
+
+impl std::error::Error for MatchError {}
+
+impl Pattern {
+    pub fn new(template: &str) -> Result<Pattern, PatternError> {
+        let mut parts: Vec<Part> = vec![];
+        let mut literal = String::new();
+        let mut chars = template.char_indices().peekable();
+        while let Some((column, chr)) = chars.next() {
+            match chr {
+                '{' if chars.peek().map(|(_, next)| *next) == Some('{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek().map(|(_, next)| *next) == Some('}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '}')) => break,
+                            Some((_, next)) => name.push(next),
+                            None => return Err(PatternError { column, message: "unclosed '{'".to_string() }),
+                        }
+                    }
+                    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                        return Err(PatternError { column, message: format!("invalid capture name \"{}\"", name) });
+                    }
+                    if parts.iter().any(|part| *part == Part::Capture(name.clone())) {
+                        return Err(PatternError { column, message: format!("duplicate capture name \"{}\"", name) });
+                    }
+                    if literal.is_empty() && matches!(parts.last(), Some(Part::Capture(_))) {
+                        return Err(PatternError { column, message: format!("capture \"{}\" must be separated from the previous capture by a literal", name) });
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Capture(name));
+                }
+                '}' => return Err(PatternError { column, message: "unmatched '}'".to_string() }),
+                _ => literal.push(chr),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        return Ok(Pattern { template: template.to_string(), parts });
+    }
+
+    #[allow(dead_code)]
+    pub fn template(&self) -> &str {
+        return &self.template;
+    }
+
+    #[allow(dead_code)]
+    pub fn capture_names(&self) -> Vec<&str> {
+        return self.parts.iter().filter_map(|part| match part {
+            Part::Capture(name) => Some(name.as_str()),
+            Part::Literal(_) => None,
+        }).collect();
+    }
+
+    pub fn parse<'a>(&'a self, line: &'a str) -> Result<Captures<'a>, MatchError> {
+        let mut values: Vec<(&'a str, usize, usize)> = vec![];
+        let mut position = 0;
+        let mut pending_capture: Option<&str> = None;
+        for part in &self.parts {
+            match part {
+                Part::Capture(name) => pending_capture = Some(name),
+                Part::Literal(literal) => {
+                    match pending_capture.take() {
+                        None => {
+                            if !line[position..].starts_with(literal.as_str()) {
+                                return Err(MatchError { column: position, message: format!("expected \"{}\"", literal) });
+                            }
+                        }
+                        Some(name) => {
+                            let found = line[position..].find(literal.as_str());
+                            if found.is_none() {
+                                return Err(MatchError { column: line.len(), message: format!("expected \"{}\" after {{{}}}", literal, name) });
+                            }
+                            let end = position + found.unwrap();
+                            values.push((name, position, end));
+                            position = end;
+                        }
+                    }
+                    position += literal.len();
+                }
+            }
+        }
+        match pending_capture {
+            Some(name) => {
+                values.push((name, position, line.len()));
+            }
+            None => {
+                if position != line.len() {
+                    return Err(MatchError { column: position, message: format!("unexpected trailing text \"{}\"", &line[position..]) });
+                }
+            }
+        }
+        return Ok(Captures { line, values });
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = PatternError;
+
+    fn from_str(template: &str) -> Result<Pattern, PatternError> {
+        return Pattern::new(template);
+    }
+}
+
+#[cfg(test)]
+mod pattern_new_tests {
+    use super::*;
+
+    #[test]
+    fn test_new_captures() {
+        let pattern = Pattern::new("{node} = ({left}, {right})").unwrap();
+        assert_eq!(pattern.capture_names(), vec!["node", "left", "right"]);
+        assert_eq!(pattern.template(), "{node} = ({left}, {right})");
+    }
+
+    #[test]
+    fn test_new_escaped_braces() {
+        let pattern = Pattern::new("{{{name}}}").unwrap();
+        assert_eq!(pattern.capture_names(), vec!["name"]);
+        assert_eq!(pattern.parse("{abc}").unwrap().get("name"), Some("abc"));
+    }
+
+    #[test]
+    fn test_new_unclosed_brace() {
+        assert_eq!(Pattern::new("Card {id: x"), Err(PatternError { column: 5, message: "unclosed '{'".to_string() }));
+    }
+
+    #[test]
+    fn test_new_unmatched_brace() {
+        assert_eq!(Pattern::new("a } b").unwrap_err().column, 2);
+    }
+
+    #[test]
+    fn test_new_invalid_name() {
+        assert_eq!(Pattern::new("x {} y").unwrap_err().column, 2);
+        assert_eq!(Pattern::new("x {a b} y").unwrap_err().column, 2);
+    }
+
+    #[test]
+    fn test_new_duplicate_name() {
+        assert_eq!(Pattern::new("{a}, {a}").unwrap_err().column, 5);
+    }
+
+    #[test]
+    fn test_new_adjacent_captures() {
+        assert_eq!(Pattern::new("{a}{b}").unwrap_err().column, 3);
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Captures<'a> {
+    line: &'a str,
+    values: Vec<(&'a str, usize, usize)>,
+}
+
+impl<'a> Captures<'a> {
+    pub fn get(&self, name: &str) -> Option<&'a str> {
+        return self.values.iter().find(|(n, _, _)| *n == name).map(|(_, start, end)| &self.line[*start..*end]);
+    }
+
+    #[allow(dead_code)]
+    pub fn column(&self, name: &str) -> Option<usize> {
+        return self.values.iter().find(|(n, _, _)| *n == name).map(|(_, start, _)| *start);
+    }
+
+    pub fn parse<T: FromStr>(&self, name: &str) -> Result<T, MatchError> {
+        let found = self.values.iter().find(|(n, _, _)| *n == name);
+        if found.is_none() {
+            return Err(MatchError { column: 0, message: format!("no capture named {{{}}}", name) });
+        }
+        let (_, start, end) = *found.unwrap();
+        let raw = &self.line[start..end];
+        let leading = raw.len() - raw.trim_start().len();
+        return raw.trim().parse::<T>().map_err(|_| MatchError {
+            column: start + leading,
+            message: format!("invalid value \"{}\" for {{{}}}", raw.trim(), name),
+        });
+    }
+}
+
+#[cfg(test)]
+mod pattern_parse_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_node() {
+        let pattern = Pattern::new("{node} = ({left}, {right})").unwrap();
+        let captures = pattern.parse("AAA = (BBB, CCC)").unwrap();
+        assert_eq!(captures.get("node"), Some("AAA"));
+        assert_eq!(captures.get("left"), Some("BBB"));
+        assert_eq!(captures.get("right"), Some("CCC"));
+        assert_eq!(captures.get("other"), None);
+        assert_eq!(captures.column("right"), Some(12));
+    }
+
+    #[test]
+    fn test_parse_scratchcard() {
+        let pattern = Pattern::new("Card {id}: {winners} | {picks}").unwrap();
+        let captures = pattern.parse("Card   1: 41 48 83 | 83 86  6").unwrap();
+        assert_eq!(captures.parse::<i32>("id"), Ok(1));
+        assert_eq!(captures.get("winners"), Some("41 48 83"));
+        assert_eq!(captures.get("picks"), Some("83 86  6"));
+    }
+
+    #[test]
+    fn test_parse_trailing_capture_may_be_empty() {
+        let pattern = Pattern::new("Game {id}: {rounds}").unwrap();
+        assert_eq!(pattern.parse("Game 4: ").unwrap().get("rounds"), Some(""));
+    }
+
+    #[test]
+    fn test_parse_leading_literal_mismatch() {
+        let pattern = Pattern::new("Card {id}: {winners}").unwrap();
+        assert_eq!(pattern.parse("Game 1: 2 3"), Err(MatchError { column: 0, message: "expected \"Card \"".to_string() }));
+    }
+
+    #[test]
+    fn test_parse_missing_literal() {
+        let pattern = Pattern::new("{cards} {bid}").unwrap();
+        assert_eq!(pattern.parse("AAAAA"), Err(MatchError { column: 5, message: "expected \" \" after {cards}".to_string() }));
+    }
+
+    #[test]
+    fn test_parse_trailing_text() {
+        let pattern = Pattern::new("{node} = ({left}, {right})").unwrap();
+        assert_eq!(pattern.parse("AAA = (BBB, CCC) x").unwrap_err().column, 16);
+    }
+
+    #[test]
+    fn test_parse_typed_value_error_position() {
+        let pattern = Pattern::new("{cards} {bid}").unwrap();
+        let captures = pattern.parse("AAAAA  12x").unwrap();
+        assert_eq!(captures.parse::<i64>("bid"), Err(MatchError { column: 7, message: "invalid value \"12x\" for {bid}".to_string() }));
+        assert_eq!(captures.parse::<String>("cards"), Ok("AAAAA".to_string()));
+    }
+
+    #[test]
+    fn test_match_error_display() {
+        let error = MatchError { column: 7, message: "invalid value".to_string() };
+        assert_eq!(error.to_string(), "column 8: invalid value");
+    }
+}
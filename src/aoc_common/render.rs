@@ -0,0 +1,143 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Colour {
+    #[allow(dead_code)]
+    pub const BLACK: Colour = Colour::new(0, 0, 0);
+    #[allow(dead_code)]
+    pub const WHITE: Colour = Colour::new(255, 255, 255);
+    pub const GREY: Colour = Colour::new(96, 96, 96);
+    pub const RED: Colour = Colour::new(220, 50, 47);
+    pub const GREEN: Colour = Colour::new(80, 200, 80);
+    pub const BLUE: Colour = Colour::new(38, 139, 210);
+    pub const YELLOW: Colour = Colour::new(250, 200, 30);
+    pub const NAVY: Colour = Colour::new(20, 30, 70);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Colour {
+        return Colour { r, g, b };
+    }
+
+    pub fn hex(&self) -> String {
+        return format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b);
+    }
+}
+
+#[cfg(test)]
+mod colour_tests {
+    use super::*;
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(Colour::new(255, 8, 160).hex(), "#ff08a0");
+        assert_eq!(Colour::BLACK.hex(), "#000000");
+    }
+}
+
+pub fn render_ppm<F: Fn(usize, usize) -> Colour>(width: usize, height: usize, cell_size: usize, colour: F) -> Vec<u8> {
+    let mut image = format!("P6\n{} {}\n255\n", width * cell_size, height * cell_size).into_bytes();
+    for y in 0..height {
+        let row: Vec<Colour> = (0..width).map(|x| colour(x, y)).collect();
+        for _ in 0..cell_size {
+            for cell in &row {
+                for _ in 0..cell_size {
+                    image.extend_from_slice(&[cell.r, cell.g, cell.b]);
+                }
+            }
+        }
+    }
+    return image;
+}
+
+#[cfg(test)]
+mod render_ppm_tests {
+    use super::*;
+
+    #[test]
+    fn test_render_ppm_header() {
+        let image = render_ppm(3, 2, 4, |_, _| Colour::BLACK);
+        assert_eq!(image.starts_with(b"P6\n12 8\n255\n"), true);
+        assert_eq!(image.len(), "P6\n12 8\n255\n".len() + 12 * 8 * 3);
+    }
+
+    #[test]
+    fn test_render_ppm_pixels() {
+        let image = render_ppm(2, 1, 1, |x, _| if x == 0 { Colour::RED } else { Colour::BLUE });
+        let pixels = &image["P6\n2 1\n255\n".len()..];
+        assert_eq!(pixels, &[220, 50, 47, 38, 139, 210]);
+    }
+
+    #[test]
+    fn test_render_ppm_scaled_cells() {
+        let image = render_ppm(2, 1, 2, |x, _| if x == 0 { Colour::WHITE } else { Colour::BLACK });
+        let pixels = &image["P6\n4 2\n255\n".len()..];
+        assert_eq!(pixels, &[255, 255, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0]);
+    }
+}
+
+pub fn render_svg<F: Fn(usize, usize) -> Colour>(width: usize, height: usize, cell_size: usize, colour: F) -> String {
+    let mut image = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" shape-rendering=\"crispEdges\">\n",
+        width * cell_size, height * cell_size,
+    );
+    for y in 0..height {
+        for x in 0..width {
+            image.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                x * cell_size, y * cell_size, cell_size, cell_size, colour(x, y).hex(),
+            ));
+        }
+    }
+    image.push_str("</svg>\n");
+    return image;
+}
+
+#[cfg(test)]
+mod render_svg_tests {
+    use super::*;
+
+    #[test]
+    fn test_render_svg() {
+        let image = render_svg(2, 1, 10, |x, _| if x == 0 { Colour::WHITE } else { Colour::GREEN });
+        assert_eq!(image.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"10\""), true);
+        assert_eq!(image.contains("<rect x=\"0\" y=\"0\" width=\"10\" height=\"10\" fill=\"#ffffff\"/>"), true);
+        assert_eq!(image.contains("<rect x=\"10\" y=\"0\" width=\"10\" height=\"10\" fill=\"#50c850\"/>"), true);
+        assert_eq!(image.ends_with("</svg>\n"), true);
+    }
+}
+
+pub fn write_image<F: Fn(usize, usize) -> Colour>(path: &str, width: usize, height: usize, cell_size: usize, colour: F) -> io::Result<()> {
+    let extension = Path::new(path).extension().map(|e| e.to_string_lossy().to_lowercase());
+    return match extension.as_deref() {
+        Some("ppm") => fs::write(path, render_ppm(width, height, cell_size, colour)),
+        Some("svg") => fs::write(path, render_svg(width, height, cell_size, colour)),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{}: image path must end in .ppm or .svg", path))),
+    };
+}
+
+#[cfg(test)]
+mod write_image_tests {
+    use super::*;
+
+    #[test]
+    fn test_write_image_svg() {
+        let path = std::env::temp_dir().join(format!("aoc_write_image_test_{}.svg", std::process::id()));
+        let path = path.to_str().unwrap();
+        write_image(path, 1, 1, 5, |_, _| Colour::YELLOW).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap().contains("fill=\"#fac81e\""), true);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_write_image_unknown_extension() {
+        let error = write_image("picture.png", 1, 1, 1, |_, _| Colour::BLACK).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::aoc_common::lib::line_iterator;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Section {
+    pub header: Option<String>,
+    pub lines: Vec<String>,
+    pub first_line: usize,
+    pub last_line: usize,
+}
+
+impl Section {
+    pub fn body_line_number(&self, index: usize) -> usize {
+        return self.first_line + index + if self.header.is_some() { 1 } else { 0 };
+    }
+
+    pub fn numbered_lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        return self.lines.iter().enumerate().map(|(index, line)| (self.body_line_number(index), line.as_str()));
+    }
+}
+
+#[cfg(test)]
+mod section_tests {
+    use super::*;
+
+    #[test]
+    fn test_body_line_number_with_header() {
+        let section = Section { header: Some("a map:".to_string()), lines: vec!["1 2 3".to_string()], first_line: 3, last_line: 4 };
+        assert_eq!(section.body_line_number(0), 4);
+    }
+
+    #[test]
+    fn test_body_line_number_without_header() {
+        let section = Section { header: None, lines: vec!["1 2 3".to_string()], first_line: 3, last_line: 3 };
+        assert_eq!(section.body_line_number(0), 3);
+    }
+
+    #[test]
+    fn test_numbered_lines() {
+        let section = Section { header: None, lines: vec!["a".to_string(), "b".to_string()], first_line: 7, last_line: 8 };
+        assert_eq!(section.numbered_lines().collect::<Vec<_>>(), vec![(7, "a"), (8, "b")]);
+    }
+}
+
+pub fn is_colon_header(line: &str) -> bool {
+    return line.ends_with(':');
+}
+
+#[cfg(test)]
+mod is_colon_header_tests {
+    use super::*;
+
+    #[test]
+    fn test_is_colon_header() {
+        assert_eq!(is_colon_header("seed-to-soil map:"), true);
+        assert_eq!(is_colon_header("seeds: 79 14 55 13"), false);
+        assert_eq!(is_colon_header(""), false);
+    }
+}
+
+pub fn split_sections<I: IntoIterator<Item = String>>(lines: I, is_header: fn(&str) -> bool) -> Vec<Section> {
+    let mut sections: Vec<Section> = vec![];
+    let mut current: Option<Section> = None;
+    for (index, line) in lines.into_iter().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            if let Some(section) = current.take() {
+                sections.push(section);
+            }
+            continue;
+        }
+        match current.as_mut() {
+            None => {
+                let mut section = Section { first_line: line_number, last_line: line_number, ..Default::default() };
+                if is_header(&line) {
+                    section.header = Some(line);
+                } else {
+                    section.lines.push(line);
+                }
+                current = Some(section);
+            }
+            Some(section) => {
+                section.lines.push(line);
+                section.last_line = line_number;
+            }
+        }
+    }
+    if let Some(section) = current {
+        sections.push(section);
+    }
+    return sections;
+}
+
+#[cfg(test)]
+mod split_sections_tests {
+    use super::*;
+
+    fn lines(source: &str) -> Vec<String> {
+        return source.split('\n').map(|line| line.to_string()).collect();
+    }
+
+    #[test]
+    fn test_split_sections_empty() {
+        assert_eq!(split_sections(lines(""), is_colon_header), vec![]);
+    }
+
+    #[test]
+    fn test_split_sections_without_headers() {
+        let sections = split_sections(lines("RL\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)"), |_| false);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0], Section { header: None, lines: vec!["RL".to_string()], first_line: 1, last_line: 1 });
+        assert_eq!(sections[1].lines.len(), 2);
+        assert_eq!(sections[1].first_line, 3);
+        assert_eq!(sections[1].last_line, 4);
+    }
+
+    #[test]
+    fn test_split_sections_with_headers() {
+        let sections = split_sections(lines("seeds: 1 2\n\n\na map:\n1 2 3\n4 5 6\n\nb map:\n7 8 9\n"), is_colon_header);
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].header, None);
+        assert_eq!(sections[1].header, Some("a map:".to_string()));
+        assert_eq!(sections[1].lines, vec!["1 2 3".to_string(), "4 5 6".to_string()]);
+        assert_eq!(sections[1].first_line, 4);
+        assert_eq!(sections[1].last_line, 6);
+        assert_eq!(sections[2].body_line_number(0), 9);
+    }
+
+    #[test]
+    fn test_split_sections_header_only() {
+        let sections = split_sections(lines("empty map:\n\n"), is_colon_header);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].lines.is_empty(), true);
+    }
+
+    #[test]
+    fn test_split_sections_whitespace_lines_separate_blocks() {
+        let sections = split_sections(lines("a\n   \nb"), |_| false);
+        assert_eq!(sections.len(), 2);
+    }
+}
+
+pub fn read_sections(filename: &str, is_header: fn(&str) -> bool) -> Vec<Section> {
+    return split_sections(line_iterator(filename), is_header);
+}
+
+#[cfg(test)]
+mod read_sections_tests {
+    use super::*;
+
+    #[test]
+    fn test_read_sections() {
+        let sections = read_sections("data/day05/test.txt", is_colon_header);
+        assert_eq!(sections.len(), 8);
+        assert_eq!(sections[0].header, None);
+        assert_eq!(sections[0].lines, vec!["seeds: 79 14 55 13".to_string()]);
+        assert_eq!(sections[1].header, Some("seed-to-soil map:".to_string()));
+        assert_eq!(sections[1].first_line, 3);
+        assert_eq!(sections[1].lines.len(), 2);
+        assert_eq!(sections[7].header, Some("humidity-to-location map:".to_string()));
+    }
+}
@@ -0,0 +1,102 @@
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TableFormat {
+    #[default]
+    Text,
+    Csv,
+}
+
+impl FromStr for TableFormat {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<TableFormat, String> {
+        return match text {
+            "table" => Ok(TableFormat::Text),
+            "csv" => Ok(TableFormat::Csv),
+            _ => Err(format!("unknown format \"{}\" (expected table or csv)", text)),
+        };
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        return format!("\"{}\"", field.replace('"', "\"\""));
+    }
+    return field.to_string();
+}
+
+fn is_numeric(field: &str) -> bool {
+    return field.parse::<f64>().is_ok();
+}
+
+pub fn format_table<S: AsRef<str>>(header: &[S], rows: &[Vec<String>], format: TableFormat) -> String {
+    let mut lines = vec![];
+    if format == TableFormat::Csv {
+        lines.push(header.iter().map(|field| csv_field(field.as_ref())).collect::<Vec<String>>().join(","));
+        for row in rows {
+            lines.push(row.iter().map(|field| csv_field(field)).collect::<Vec<String>>().join(","));
+        }
+        return lines.join("\n");
+    }
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| rows.iter().map(|row| row[column].chars().count()).chain([header[column].as_ref().chars().count()]).max().unwrap())
+        .collect();
+    let numeric: Vec<bool> = (0..header.len())
+        .map(|column| !rows.is_empty() && rows.iter().all(|row| is_numeric(&row[column])))
+        .collect();
+    let line = |fields: Vec<&str>| {
+        let cells: Vec<String> = fields.iter().enumerate().map(|(column, field)| {
+            if numeric[column] {
+                return format!("{:>width$}", field, width = widths[column]);
+            }
+            return format!("{:<width$}", field, width = widths[column]);
+        }).collect();
+        return cells.join("  ").trim_end().to_string();
+    };
+    lines.push(line(header.iter().map(|field| field.as_ref()).collect()));
+    for row in rows {
+        lines.push(line(row.iter().map(|field| field.as_str()).collect()));
+    }
+    return lines.join("\n");
+}
+
+#[cfg(test)]
+mod table_tests {
+    use super::*;
+
+    fn rows() -> Vec<Vec<String>> {
+        return vec![
+            vec!["1".to_string(), "3 red, 2 blue".to_string(), "yes".to_string()],
+            vec!["12".to_string(), "say \"hi\"".to_string(), "no".to_string()],
+        ];
+    }
+
+    #[test]
+    fn test_table_format_from_str() {
+        assert_eq!("csv".parse::<TableFormat>(), Ok(TableFormat::Csv));
+        assert_eq!("table".parse::<TableFormat>(), Ok(TableFormat::Text));
+        assert_eq!("json".parse::<TableFormat>().is_err(), true);
+    }
+
+    #[test]
+    fn test_format_table_text() {
+        assert_eq!(format_table(&["id", "bag", "ok"], &rows(), TableFormat::Text), "\
+id  bag            ok
+ 1  3 red, 2 blue  yes
+12  say \"hi\"       no");
+    }
+
+    #[test]
+    fn test_format_table_csv() {
+        assert_eq!(format_table(&["id", "bag", "ok"], &rows(), TableFormat::Csv), "\
+id,bag,ok
+1,\"3 red, 2 blue\",yes
+12,\"say \"\"hi\"\"\",no");
+    }
+
+    #[test]
+    fn test_format_table_no_rows() {
+        assert_eq!(format_table(&["id", "bag"], &[], TableFormat::Text), "id  bag");
+    }
+}
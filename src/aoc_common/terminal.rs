@@ -0,0 +1,104 @@
+use std::io::{self, IsTerminal, Write};
+use std::thread;
+use std::time::Duration;
+use crate::aoc_common::render::Colour;
+
+const CLEAR_SCREEN: &str = "\x1b[H\x1b[2J";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Animation {
+    pub delay: Duration,
+    pub every: usize,
+    ansi: bool,
+}
+
+impl Animation {
+    pub fn new(delay: Duration, every: usize) -> Animation {
+        return Animation { delay, every: every.max(1), ansi: io::stdout().is_terminal() };
+    }
+
+    #[allow(dead_code)]
+    pub fn with_ansi(mut self, ansi: bool) -> Animation {
+        self.ansi = ansi;
+        return self;
+    }
+
+    #[allow(dead_code)]
+    pub fn is_ansi(&self) -> bool {
+        return self.ansi;
+    }
+
+    pub fn frame_text<F: FnOnce(bool) -> String>(&self, step: usize, draw: F) -> Option<String> {
+        if !step.is_multiple_of(self.every) {
+            return None;
+        }
+        let body = draw(self.ansi);
+        if self.ansi {
+            return Some(format!("{}{}\n", CLEAR_SCREEN, body));
+        }
+        return Some(format!("{}\n", body));
+    }
+
+    pub fn frame<F: FnOnce(bool) -> String>(&self, step: usize, draw: F) {
+        if let Some(text) = self.frame_text(step, draw) {
+            let mut stdout = io::stdout().lock();
+            let _ = stdout.write_all(text.as_bytes());
+            let _ = stdout.flush();
+            if self.ansi && !self.delay.is_zero() {
+                thread::sleep(self.delay);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod animation_tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_text_every_n_steps() {
+        let animation = Animation::new(Duration::ZERO, 3).with_ansi(false);
+        assert_eq!(animation.frame_text(0, |_| "a".to_string()), Some("a\n".to_string()));
+        assert_eq!(animation.frame_text(1, |_| "b".to_string()), None);
+        assert_eq!(animation.frame_text(6, |_| "c".to_string()), Some("c\n".to_string()));
+    }
+
+    #[test]
+    fn test_frame_text_ansi_clears_screen() {
+        let animation = Animation::new(Duration::ZERO, 1).with_ansi(true);
+        assert_eq!(animation.frame_text(5, |ansi| format!("{}", ansi)), Some("\x1b[H\x1b[2Jtrue\n".to_string()));
+    }
+
+    #[test]
+    fn test_frame_text_plain_draws_without_ansi() {
+        let animation = Animation::new(Duration::ZERO, 1).with_ansi(false);
+        assert_eq!(animation.frame_text(5, |ansi| format!("{}", ansi)), Some("false\n".to_string()));
+    }
+
+    #[test]
+    fn test_new_every_zero_draws_every_step() {
+        assert_eq!(Animation::new(Duration::ZERO, 0).every, 1);
+    }
+}
+
+pub fn paint(text: &str, colour: Colour, ansi: bool) -> String {
+    if !ansi {
+        return text.to_string();
+    }
+    return format!("\x1b[38;2;{};{};{}m{}\x1b[0m", colour.r, colour.g, colour.b, text);
+}
+
+#[cfg(test)]
+mod paint_tests {
+    use super::*;
+
+    #[test]
+    fn test_paint_ansi() {
+        assert_eq!(paint("S", Colour::new(1, 2, 3), true), "\x1b[38;2;1;2;3mS\x1b[0m");
+    }
+
+    #[test]
+    fn test_paint_plain() {
+        assert_eq!(paint("S", Colour::RED, false), "S");
+    }
+}
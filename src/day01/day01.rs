@@ -1,19 +1,23 @@
+use std::fs;
+use num::BigInt;
 use regex::Regex;
-use crate::aoc_common::lib::{sum, sum_up_with_rule};
+use crate::aoc_common::answer::Answer;
+use crate::aoc_common::bench::{measure, Measurement};
+use crate::aoc_common::checked::{checked_add, checked_append_digit, Accumulator, Arithmetic, OverflowError};
+use crate::aoc_common::context::{Context, SolveError};
+use crate::aoc_common::lib::line_iterator;
+use crate::{trace, warn};
+use super::vocabulary::Vocabulary;
 
-const FIRST_DIGIT_ONLY_STR: &str = "\\d";
-const FIRST_DIGIT_OR_WORD_STR: &str = "\\d|one|two|three|four|five|six|seven|eight|nine";
-const LAST_DIGIT_ONLY_STR: &str = ".*(?<digit>\\d)";
-const LAST_DIGIT_OR_WORD_STR: &str = ".*(?<digit>\\d|one|two|three|four|five|six|seven|eight|nine)";
+pub const PARAMETERS: &[&str] = &["part1_rule", "part2_rule", "vocabulary", "extract", "multi_digit_words", "missing_digits"];
 
-const NUMBER_WORDS: [&str;9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+const DEFAULT_VOCABULARY: &str = "english";
+const DEFAULT_EXTRACTION: &str = "first-last";
+const BENCHMARK_SIZES: [usize;3] = [1_000, 10_000, 100_000];
+const MAX_PER_LINE_COMPILE_SIZE: usize = 10_000;
 
-fn digit_value(word: &str) -> i32 {
-    let index = NUMBER_WORDS.iter().position(|&elem| elem == word);
-    if index == None {
-        return word.parse::<i32>().unwrap();
-    }
-    return 1 + index.unwrap() as i32;
+fn digit_value(word: &str, vocabulary: &Vocabulary) -> i32 {
+    return vocabulary.value(word).unwrap();
 }
 
 #[cfg(test)]
@@ -22,81 +26,215 @@ mod digit_value_tests {
 
     #[test]
     fn test_digit_value_one() {
-        assert_eq!(digit_value("one"), 1);
+        assert_eq!(digit_value("one", &Vocabulary::built_in("english").unwrap()), 1);
     }
 
     #[test]
     fn test_digit_value_nine() {
-        assert_eq!(digit_value("nine"), 9);
+        assert_eq!(digit_value("nine", &Vocabulary::built_in("english").unwrap()), 9);
     }
 
     #[test]
     fn test_digit_value_1() {
-        assert_eq!(digit_value("1"), 1);
+        assert_eq!(digit_value("1", &Vocabulary::digits()), 1);
     }
 
     #[test]
     fn test_digit_value_9() {
-        assert_eq!(digit_value("9"), 9);
+        assert_eq!(digit_value("9", &Vocabulary::digits()), 9);
     }
-}
 
-fn first_digit(line: &str, rule: &str) -> i32 {
-    let pattern_str: &str;
-    if rule == "words" {
-        pattern_str = FIRST_DIGIT_OR_WORD_STR;
-    } else {
-        pattern_str = FIRST_DIGIT_ONLY_STR;
+    #[test]
+    fn test_digit_value_german() {
+        assert_eq!(digit_value("sieben", &Vocabulary::built_in("german").unwrap()), 7);
     }
-    let pattern = Regex::new(pattern_str).unwrap();
-    let found_match = pattern.find(line).unwrap();
-    return digit_value(found_match.as_str());
+}
+
+fn first_and_last_digit(line: &str, vocabulary: &Vocabulary) -> Option<(i32, i32)> {
+    let (first, last) = vocabulary.first_and_last(line)?;
+    return Some((digit_value(first, vocabulary), digit_value(last, vocabulary)));
 }
 
 #[cfg(test)]
-mod first_digit_tests {
+mod first_and_last_digit_tests {
     use super::*;
 
     #[test]
     fn test_first_digit_no_words() {
-        assert_eq!(first_digit("one2three4five6seveneight9ten11twelve", "digits"), 2);
+        assert_eq!(first_and_last_digit("one2three4five6seveneight9ten11twelve", &Vocabulary::digits()).unwrap().0, 2);
     }
 
     #[test]
     fn test_first_digit_with_words() {
-        assert_eq!(first_digit("one2three4five6seveneight9ten11twelve", "words"), 1);
+        assert_eq!(first_and_last_digit("one2three4five6seveneight9ten11twelve", &Vocabulary::built_in("english").unwrap()).unwrap().0, 1);
+    }
+
+    #[test]
+    fn test_first_digit_with_spanish_words() {
+        assert_eq!(first_and_last_digit("xcincodos3", &Vocabulary::built_in("spanish").unwrap()).unwrap().0, 5);
+    }
+
+    #[test]
+    fn test_last_digit_no_words() {
+        assert_eq!(first_and_last_digit("one2three4five6seveneight", &Vocabulary::digits()).unwrap().1, 6);
     }
+
+    #[test]
+    fn test_last_digit_with_words() {
+        assert_eq!(first_and_last_digit("one2three4five6seveneight", &Vocabulary::built_in("english").unwrap()).unwrap().1, 8);
+    }
+
+    #[test]
+    fn test_last_digit_overlapping_words() {
+        assert_eq!(first_and_last_digit("3eightwo", &Vocabulary::built_in("english").unwrap()).unwrap().1, 2);
+    }
+
+    #[test]
+    fn test_first_and_last_digit_missing() {
+        assert_eq!(first_and_last_digit("pqrstuvwx", &Vocabulary::built_in("english").unwrap()), None);
+        assert_eq!(first_and_last_digit("twone", &Vocabulary::digits()), None);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Extraction {
+    FirstLast(usize),
+    All,
 }
 
-fn last_digit(line: &str, rule: &str) -> i32 {
-    let pattern_str: &str;
-    if rule == "words" {
-        pattern_str = LAST_DIGIT_OR_WORD_STR;
-    } else {
-        pattern_str = LAST_DIGIT_ONLY_STR;
+fn parse_extraction(text: &str) -> Option<Extraction> {
+    if text == "all" {
+        return Some(Extraction::All);
     }
-    let pattern = Regex::new(pattern_str).unwrap();
-    let captures = pattern.captures(line).unwrap();
-    return digit_value(&captures["digit"]);
+    if text == "first-last" {
+        return Some(Extraction::FirstLast(1));
+    }
+    let count = text.strip_prefix("first-last:")?.parse::<usize>().ok()?;
+    return if count > 0 { Some(Extraction::FirstLast(count)) } else { None };
 }
 
 #[cfg(test)]
-mod last_digit_tests {
+mod parse_extraction_tests {
     use super::*;
 
     #[test]
-    fn test_last_digit_no_words() {
-        assert_eq!(last_digit("one2three4five6seveneight", "digits"), 6);
+    fn test_parse_extraction() {
+        assert_eq!(parse_extraction("first-last"), Some(Extraction::FirstLast(1)));
+        assert_eq!(parse_extraction("first-last:3"), Some(Extraction::FirstLast(3)));
+        assert_eq!(parse_extraction("all"), Some(Extraction::All));
     }
 
     #[test]
-    fn test_last_digit_with_words() {
-        assert_eq!(last_digit("one2three4five6seveneight", "words"), 8);
+    fn test_parse_extraction_invalid() {
+        assert_eq!(parse_extraction("first-last:0"), None);
+        assert_eq!(parse_extraction("first-last:x"), None);
+        assert_eq!(parse_extraction("middle"), None);
     }
 }
 
-fn calibration_value(line: &str, rule: &str) -> i32 {
-    return 10 * first_digit(line, rule) + last_digit(line, rule);
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum MissingDigits {
+    Strict,
+    Lenient,
+}
+
+#[derive(Clone, Debug)]
+struct Calibration {
+    vocabulary: Vocabulary,
+    extraction: Extraction,
+    missing_digits: MissingDigits,
+}
+
+impl Calibration {
+    fn new(vocabulary: Vocabulary, extraction: Extraction) -> Calibration {
+        return Calibration { vocabulary, extraction, missing_digits: MissingDigits::Strict };
+    }
+
+    fn with_missing_digits(mut self, missing_digits: MissingDigits) -> Calibration {
+        self.missing_digits = missing_digits;
+        return self;
+    }
+}
+
+fn extracted_digits(line: &str, calibration: &Calibration) -> Option<Vec<u8>> {
+    let vocabulary = &calibration.vocabulary;
+    if calibration.extraction == Extraction::FirstLast(1) {
+        let (first, last) = first_and_last_digit(line, vocabulary)?;
+        return Some(vec![first.to_string().as_bytes()[0] - b'0', (last % 10) as u8]);
+    }
+    let tokens = vocabulary.tokens(line);
+    if tokens.is_empty() {
+        return None;
+    }
+    let digits: Vec<u8> = tokens.iter()
+        .flat_map(|token| digit_value(token, vocabulary).to_string().into_bytes())
+        .map(|byte| byte - b'0')
+        .collect();
+    return Some(match calibration.extraction {
+        Extraction::All => digits,
+        Extraction::FirstLast(count) => {
+            let count = count.min(digits.len());
+            [&digits[..count], &digits[digits.len() - count..]].concat()
+        }
+    });
+}
+
+#[cfg(test)]
+mod extracted_digits_tests {
+    use super::*;
+
+    fn english(extraction: Extraction) -> Calibration {
+        return Calibration::new(Vocabulary::built_in("english").unwrap(), extraction);
+    }
+
+    fn english_multi_digit(extraction: Extraction) -> Calibration {
+        return Calibration::new(Vocabulary::built_in_multi_digit("english").unwrap(), extraction);
+    }
+
+    #[test]
+    fn test_extracted_digits_first_last() {
+        assert_eq!(extracted_digits("a1b2c3d4e5f", &english(Extraction::FirstLast(1))), Some(vec![1, 5]));
+        assert_eq!(extracted_digits("treb7uchet", &english(Extraction::FirstLast(1))), Some(vec![7, 7]));
+    }
+
+    #[test]
+    fn test_extracted_digits_first_last_n() {
+        assert_eq!(extracted_digits("a1b2c3d4e5f", &english(Extraction::FirstLast(2))), Some(vec![1, 2, 4, 5]));
+        assert_eq!(extracted_digits("two1nine", &english(Extraction::FirstLast(3))), Some(vec![2, 1, 9, 2, 1, 9]));
+        assert_eq!(extracted_digits("treb7uchet", &english(Extraction::FirstLast(2))), Some(vec![7, 7]));
+    }
+
+    #[test]
+    fn test_extracted_digits_all() {
+        assert_eq!(extracted_digits("xtwone3four", &english(Extraction::All)), Some(vec![2, 1, 3, 4]));
+    }
+
+    #[test]
+    fn test_extracted_digits_multi_digit_words() {
+        assert_eq!(extracted_digits("7pqrstsixteen", &english_multi_digit(Extraction::All)), Some(vec![7, 1, 6]));
+        assert_eq!(extracted_digits("ninety-nine", &english_multi_digit(Extraction::FirstLast(1))), Some(vec![9, 9]));
+        assert_eq!(extracted_digits("twenty-one5eleven", &english_multi_digit(Extraction::FirstLast(2))), Some(vec![2, 1, 1, 1]));
+        assert_eq!(extracted_digits("x12twenty", &english_multi_digit(Extraction::FirstLast(1))), Some(vec![1, 0]));
+    }
+
+    #[test]
+    fn test_extracted_digits_missing() {
+        assert_eq!(extracted_digits("pqrstuvwx", &english(Extraction::FirstLast(1))), None);
+        assert_eq!(extracted_digits("pqrstuvwx", &english(Extraction::All)), None);
+    }
+}
+
+fn calibration_value<T: Accumulator + From<u8>>(line: &str, calibration: &Calibration) -> Result<Option<T>, OverflowError> {
+    let digits = match extracted_digits(line, calibration) {
+        Some(digits) => digits,
+        None => return Ok(None),
+    };
+    let mut value = T::zero();
+    for digit in digits {
+        value = checked_append_digit(value, digit)?;
+    }
+    trace!("{} ({}) -> {}", line, calibration.vocabulary.name, value);
+    return Ok(Some(value));
 }
 
 #[cfg(test)]
@@ -105,38 +243,400 @@ mod calibration_value_tests {
 
     #[test]
     fn test_calibration_value() {
-        assert_eq!(calibration_value("one2three4five6seveneight", "digits"), 26);
+        let calibration = Calibration::new(Vocabulary::digits(), Extraction::FirstLast(1));
+        assert_eq!(calibration_value::<i32>("one2three4five6seveneight", &calibration), Ok(Some(26)));
     }
 
     #[test]
     fn test_last_digit_with_words() {
-        assert_eq!(calibration_value("one2three4five6seveneight", "words"), 18);
+        let calibration = Calibration::new(Vocabulary::built_in("english").unwrap(), Extraction::FirstLast(1));
+        assert_eq!(calibration_value::<i32>("one2three4five6seveneight", &calibration), Ok(Some(18)));
+    }
+
+    #[test]
+    fn test_calibration_value_all_overflow() {
+        let calibration = Calibration::new(Vocabulary::digits(), Extraction::All);
+        assert_eq!(calibration_value::<i32>("1234567890123", &calibration).is_err(), true);
+        assert_eq!(calibration_value::<BigInt>("1234567890123", &calibration), Ok(Some(BigInt::from(1234567890123i64))));
+    }
+
+    #[test]
+    fn test_calibration_value_missing() {
+        let calibration = Calibration::new(Vocabulary::digits(), Extraction::All);
+        assert_eq!(calibration_value::<i32>("no digits", &calibration), Ok(None));
     }
 }
 
-fn sum_calibration_values(filename: &str, rule: &str) -> i32 {
-    return sum_up_with_rule(filename, calibration_value, sum, rule);
+fn missing_digits_report(lines: &Vec<(usize, String)>) -> String {
+    let mut report = format!("{} {} without a digit", lines.len(), if lines.len() == 1 { "line" } else { "lines" });
+    for (line_number, line) in lines {
+        report.push_str(&format!("\n  line {}: \"{}\"", line_number, line));
+    }
+    return report;
+}
+
+#[cfg(test)]
+mod missing_digits_report_tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_digits_report() {
+        let lines = vec![(2, "pqr".to_string()), (6, "xyz".to_string())];
+        assert_eq!(missing_digits_report(&lines), "2 lines without a digit\n  line 2: \"pqr\"\n  line 6: \"xyz\"");
+        assert_eq!(missing_digits_report(&vec![(1, "".to_string())]), "1 line without a digit\n  line 1: \"\"");
+    }
+
+    #[test]
+    fn test_missing_digits_report_lists_every_line() {
+        let lines: Vec<(usize, String)> = (1..=12).map(|n| (n, "x".to_string())).collect();
+        let report = missing_digits_report(&lines);
+        assert_eq!(report.lines().count(), 13);
+        assert_eq!(report.ends_with("  line 11: \"x\"\n  line 12: \"x\""), true);
+    }
+}
+
+fn sum_calibration_values<T: Accumulator + From<u8>>(filename: &str, calibration: &Calibration) -> Result<T, SolveError> {
+    let mut total = T::zero();
+    let mut missing: Vec<(usize, String)> = vec![];
+    for (index, line) in line_iterator(filename).enumerate() {
+        match calibration_value::<T>(&line, calibration)? {
+            Some(value) => total = checked_add(total, value)?,
+            None => missing.push((index + 1, line)),
+        }
+    }
+    if missing.is_empty() {
+        return Ok(total);
+    }
+    if calibration.missing_digits == MissingDigits::Strict {
+        return Err(SolveError::InvalidInput { file: filename.to_string(), message: missing_digits_report(&missing) });
+    }
+    for (line_number, line) in &missing {
+        warn!("{}:{}: skipping line without a digit: \"{}\"", filename, line_number, line);
+    }
+    return Ok(total);
 }
 
 #[cfg(test)]
 mod sum_calibration_values_tests {
     use super::*;
 
+    fn digits(extraction: Extraction) -> Calibration {
+        return Calibration::new(Vocabulary::digits(), extraction);
+    }
+
+    fn english(extraction: Extraction) -> Calibration {
+        return Calibration::new(Vocabulary::built_in("english").unwrap(), extraction);
+    }
+
+    fn english_multi_digit(extraction: Extraction) -> Calibration {
+        return Calibration::new(Vocabulary::built_in_multi_digit("english").unwrap(), extraction);
+    }
+
     #[test]
     fn test_sum_calibration_values_part1() {
-        assert_eq!(sum_calibration_values("data/day01/part1_test.txt", "digits"), 142);
+        assert_eq!(sum_calibration_values::<i32>("data/day01/part1_test.txt", &digits(Extraction::FirstLast(1))), Ok(142));
     }
 
     #[test]
     fn test_sum_calibration_values_part2() {
-        assert_eq!(sum_calibration_values("data/day01/part2_test.txt", "words"), 281);
+        assert_eq!(sum_calibration_values::<i32>("data/day01/part2_test.txt", &english(Extraction::FirstLast(1))), Ok(281));
+    }
+
+    #[test]
+    fn test_sum_calibration_values_big() {
+        assert_eq!(sum_calibration_values::<BigInt>("data/day01/part2_test.txt", &english(Extraction::FirstLast(1))), Ok(BigInt::from(281)));
+    }
+
+    #[test]
+    fn test_sum_calibration_values_french() {
+        let calibration = Calibration::new(Vocabulary::built_in("french").unwrap(), Extraction::FirstLast(1));
+        assert_eq!(sum_calibration_values::<i32>("data/day01/french_test.txt", &calibration), Ok(281));
+    }
+
+    #[test]
+    fn test_sum_calibration_values_first_last_n() {
+        assert_eq!(sum_calibration_values::<i32>("data/day01/part1_test.txt", &digits(Extraction::FirstLast(2))), Ok(6372));
+        assert_eq!(sum_calibration_values::<i32>("data/day01/part2_test.txt", &english(Extraction::FirstLast(2))), Ok(28181));
+    }
+
+    #[test]
+    fn test_sum_calibration_values_all() {
+        assert_eq!(sum_calibration_values::<i32>("data/day01/part1_test.txt", &digits(Extraction::All)), Ok(12402));
+        assert_eq!(sum_calibration_values::<i32>("data/day01/part2_test.txt", &english(Extraction::All)), Ok(71481));
+    }
+
+    #[test]
+    fn test_sum_calibration_values_strict() {
+        let error = sum_calibration_values::<i32>("data/day01/missing_digits_test.txt", &digits(Extraction::FirstLast(1))).unwrap_err();
+        assert_eq!(error, SolveError::InvalidInput {
+            file: "data/day01/missing_digits_test.txt".to_string(),
+            message: "3 lines without a digit\n  line 2: \"pqrstuvwx\"\n  line 4: \"\"\n  line 6: \"nodigits\"".to_string(),
+        });
+    }
+
+    #[test]
+    fn test_sum_calibration_values_lenient() {
+        let calibration = digits(Extraction::FirstLast(1)).with_missing_digits(MissingDigits::Lenient);
+        assert_eq!(sum_calibration_values::<i32>("data/day01/missing_digits_test.txt", &calibration), Ok(104));
+        let calibration = english(Extraction::All).with_missing_digits(MissingDigits::Lenient);
+        assert_eq!(sum_calibration_values::<i32>("data/day01/missing_digits_test.txt", &calibration), Ok(12 + 12345 + 7));
+    }
+
+    #[test]
+    fn test_sum_calibration_values_multi_digit_words() {
+        assert_eq!(sum_calibration_values::<i32>("data/day01/part1_test.txt", &english_multi_digit(Extraction::FirstLast(1))), Ok(142));
+        assert_eq!(sum_calibration_values::<i32>("data/day01/part2_test.txt", &english_multi_digit(Extraction::FirstLast(1))), Ok(281));
+        assert_eq!(sum_calibration_values::<i32>("data/day01/part2_test.txt", &english_multi_digit(Extraction::All)), Ok(72121));
+    }
+}
+
+fn generate_document(lines: usize, vocabulary: &Vocabulary, seed: u64) -> String {
+    let mut state = seed.max(1);
+    let mut next = |limit: usize| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        return (state % limit as u64) as usize;
+    };
+    let words = vocabulary.words();
+    let mut document = String::new();
+    for _ in 0..lines {
+        let tokens = 4 + next(24);
+        let digit_at = next(tokens);
+        for index in 0..tokens {
+            let roll = next(10);
+            if index == digit_at || roll == 0 {
+                document.push(char::from(b'0' + next(10) as u8));
+            } else if roll <= 2 && !words.is_empty() {
+                document.push_str(&words[next(words.len())].0);
+            } else {
+                document.push(char::from(b'a' + next(26) as u8));
+            }
+        }
+        document.push('\n');
+    }
+    return document;
+}
+
+fn regex_calibration_sum(filename: &str, vocabulary: &Vocabulary, compile_per_line: bool) -> i64 {
+    let alternatives = vocabulary.regex_alternatives();
+    let compile = || (Regex::new(&alternatives).unwrap(), Regex::new(&format!(".*(?<digit>{})", alternatives)).unwrap());
+    let mut patterns = compile();
+    let mut total = 0;
+    for line in line_iterator(filename) {
+        if compile_per_line {
+            patterns = compile();
+        }
+        let first = digit_value(patterns.0.find(&line).unwrap().as_str(), vocabulary);
+        let last = digit_value(&patterns.1.captures(&line).unwrap()["digit"], vocabulary);
+        total += (10 * first + last) as i64;
+    }
+    return total;
+}
+
+#[cfg(test)]
+mod regex_calibration_sum_tests {
+    use super::*;
+
+    #[test]
+    fn test_regex_calibration_sum_matches_automaton() {
+        let vocabulary = Vocabulary::built_in("english").unwrap();
+        let path = std::env::temp_dir().join(format!("aoc_day01_regex_calibration_sum_test_{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(path, generate_document(300, &vocabulary, 42)).unwrap();
+        let calibration = Calibration::new(vocabulary.clone(), Extraction::FirstLast(1));
+        let expected = sum_calibration_values::<i64>(path, &calibration).unwrap();
+        assert_eq!(regex_calibration_sum(path, &vocabulary, false), expected);
+        assert_eq!(regex_calibration_sum(path, &vocabulary, true), expected);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_generate_document() {
+        let vocabulary = Vocabulary::built_in("english").unwrap();
+        let document = generate_document(50, &vocabulary, 7);
+        assert_eq!(document.lines().count(), 50);
+        assert_eq!(document.lines().all(|line| line.chars().any(|c| c.is_ascii_digit())), true);
+        assert_eq!(document, generate_document(50, &vocabulary, 7));
+    }
+}
+
+pub fn benchmark(_context: &Context) -> Result<Vec<Measurement>, SolveError> {
+    let calibration = Calibration::new(Vocabulary::built_in(DEFAULT_VOCABULARY).unwrap(), Extraction::FirstLast(1));
+    let vocabulary = &calibration.vocabulary;
+    let mut measurements = vec![];
+    for lines in BENCHMARK_SIZES {
+        let path = std::env::temp_dir().join(format!("aoc_day01_benchmark_{}_{}.txt", std::process::id(), lines));
+        let path = path.to_str().unwrap();
+        fs::write(path, generate_document(lines, vocabulary, lines as u64)).unwrap();
+        let expected = sum_calibration_values::<i64>(path, &calibration).unwrap();
+        measurements.push(measure("regex compiled once", lines, 3, || regex_calibration_sum(path, vocabulary, false)));
+        if lines <= MAX_PER_LINE_COMPILE_SIZE {
+            measurements.push(measure("regex compiled per line", lines, 1, || regex_calibration_sum(path, vocabulary, true)));
+        }
+        measurements.push(measure("automaton", lines, 3, || sum_calibration_values::<i64>(path, &calibration).unwrap()));
+        if regex_calibration_sum(path, vocabulary, false) != expected {
+            warn!("{}: regex and automaton sums differ", path);
+        }
+        let _ = fs::remove_file(path);
+    }
+    return Ok(measurements);
+}
+
+fn rule_parameter<'a>(context: &'a Context, name: &str, default: &'a str) -> Result<&'a str, SolveError> {
+    let rule = context.parameter(name).unwrap_or(default);
+    if rule != "digits" && rule != "words" {
+        return Err(SolveError::InvalidParameter {
+            name: name.to_string(),
+            message: format!("unknown rule \"{}\" (expected digits or words)", rule),
+        });
+    }
+    return Ok(rule);
+}
+
+#[cfg(test)]
+mod rule_parameter_tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_parameter_default() {
+        assert_eq!(rule_parameter(&Context::default(), "part1_rule", "digits"), Ok("digits"));
+    }
+
+    #[test]
+    fn test_rule_parameter_configured() {
+        let context = Context::default().with_parameter("part1_rule", "words");
+        assert_eq!(rule_parameter(&context, "part1_rule", "digits"), Ok("words"));
+    }
+
+    #[test]
+    fn test_rule_parameter_invalid() {
+        let context = Context::default().with_parameter("part2_rule", "letters");
+        assert_eq!(rule_parameter(&context, "part2_rule", "words"), Err(SolveError::InvalidParameter {
+            name: "part2_rule".to_string(),
+            message: "unknown rule \"letters\" (expected digits or words)".to_string(),
+        }));
+    }
+}
+
+fn flag_parameter(context: &Context, name: &str) -> Result<bool, SolveError> {
+    return match context.parameter(name).unwrap_or("false") {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        other => Err(SolveError::InvalidParameter {
+            name: name.to_string(),
+            message: format!("expected true or false, found \"{}\"", other),
+        }),
+    };
+}
+
+fn vocabulary_for_rule(context: &Context, rule_name: &str, default_rule: &str) -> Result<Vocabulary, SolveError> {
+    if rule_parameter(context, rule_name, default_rule)? == "digits" {
+        return Ok(Vocabulary::digits());
+    }
+    let name = context.parameter("vocabulary").unwrap_or(DEFAULT_VOCABULARY);
+    let multi_digit_words = flag_parameter(context, "multi_digit_words")?;
+    return Vocabulary::lookup(name, multi_digit_words).map_err(|message| SolveError::InvalidParameter { name: "vocabulary".to_string(), message });
+}
+
+#[cfg(test)]
+mod vocabulary_for_rule_tests {
+    use super::*;
+
+    #[test]
+    fn test_vocabulary_for_rule_default() {
+        assert_eq!(vocabulary_for_rule(&Context::default(), "part1_rule", "digits").unwrap().name, "digits");
+        assert_eq!(vocabulary_for_rule(&Context::default(), "part2_rule", "words").unwrap().name, "english");
+    }
+
+    #[test]
+    fn test_vocabulary_for_rule_configured() {
+        let context = Context::default().with_parameter("vocabulary", "german");
+        assert_eq!(vocabulary_for_rule(&context, "part2_rule", "words").unwrap().name, "german");
+        assert_eq!(vocabulary_for_rule(&context, "part1_rule", "digits").unwrap().name, "digits");
+    }
+
+    #[test]
+    fn test_vocabulary_for_rule_unknown() {
+        let context = Context::default().with_parameter("vocabulary", "data/day01/vocabularies/missing.txt");
+        let error = vocabulary_for_rule(&context, "part2_rule", "words").unwrap_err();
+        assert_eq!(matches!(error, SolveError::InvalidParameter { ref name, .. } if name == "vocabulary"), true);
+    }
+
+    #[test]
+    fn test_vocabulary_for_rule_multi_digit_words() {
+        let context = Context::default().with_parameter("multi_digit_words", "true");
+        assert_eq!(vocabulary_for_rule(&context, "part2_rule", "words").unwrap().value("ninety-nine"), Some(99));
+        let context = Context::default().with_parameter("multi_digit_words", "yes");
+        assert_eq!(vocabulary_for_rule(&context, "part2_rule", "words").unwrap_err(), SolveError::InvalidParameter {
+            name: "multi_digit_words".to_string(),
+            message: "expected true or false, found \"yes\"".to_string(),
+        });
+    }
+}
+
+fn calibration_for_rule(context: &Context, rule_name: &str, default_rule: &str) -> Result<Calibration, SolveError> {
+    let vocabulary = vocabulary_for_rule(context, rule_name, default_rule)?;
+    let extract = context.parameter("extract").unwrap_or(DEFAULT_EXTRACTION);
+    let extraction = parse_extraction(extract).ok_or_else(|| SolveError::InvalidParameter {
+        name: "extract".to_string(),
+        message: format!("unknown extraction \"{}\" (expected first-last, first-last:N or all)", extract),
+    })?;
+    let missing_digits = match context.parameter("missing_digits").unwrap_or("strict") {
+        "strict" => MissingDigits::Strict,
+        "lenient" => MissingDigits::Lenient,
+        other => return Err(SolveError::InvalidParameter {
+            name: "missing_digits".to_string(),
+            message: format!("unknown mode \"{}\" (expected strict or lenient)", other),
+        }),
+    };
+    return Ok(Calibration::new(vocabulary, extraction).with_missing_digits(missing_digits));
+}
+
+#[cfg(test)]
+mod calibration_for_rule_tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration_for_rule_default() {
+        let calibration = calibration_for_rule(&Context::default(), "part2_rule", "words").unwrap();
+        assert_eq!(calibration.extraction, Extraction::FirstLast(1));
+        assert_eq!(calibration.vocabulary.name, "english");
+        assert_eq!(calibration.missing_digits, MissingDigits::Strict);
+    }
+
+    #[test]
+    fn test_calibration_for_rule_configured() {
+        let context = Context::default().with_parameter("extract", "first-last:2");
+        assert_eq!(calibration_for_rule(&context, "part1_rule", "digits").unwrap().extraction, Extraction::FirstLast(2));
+        let context = Context::default().with_parameter("missing_digits", "lenient");
+        assert_eq!(calibration_for_rule(&context, "part1_rule", "digits").unwrap().missing_digits, MissingDigits::Lenient);
+    }
+
+    #[test]
+    fn test_calibration_for_rule_invalid() {
+        let context = Context::default().with_parameter("extract", "middle");
+        assert_eq!(calibration_for_rule(&context, "part1_rule", "digits").unwrap_err(), SolveError::InvalidParameter {
+            name: "extract".to_string(),
+            message: "unknown extraction \"middle\" (expected first-last, first-last:N or all)".to_string(),
+        });
+        let context = Context::default().with_parameter("missing_digits", "loose");
+        assert_eq!(calibration_for_rule(&context, "part1_rule", "digits").is_err(), true);
     }
 }
 
-pub fn part1() {
-    println!("Day 1 Part 1 result: {}", sum_calibration_values("data/day01/input.txt", "digits"));
+pub fn part1(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    let calibration = calibration_for_rule(context, "part1_rule", "digits")?;
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(sum_calibration_values::<i32>(filename, &calibration)?),
+        Arithmetic::Big => Answer::from(sum_calibration_values::<BigInt>(filename, &calibration)?),
+    });
 }
 
-pub fn part2() {
-    println!("Day 1 Part 2 result: {}", sum_calibration_values("data/day01/input.txt", "words"));
+pub fn part2(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    let calibration = calibration_for_rule(context, "part2_rule", "words")?;
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(sum_calibration_values::<i32>(filename, &calibration)?),
+        Arithmetic::Big => Answer::from(sum_calibration_values::<BigInt>(filename, &calibration)?),
+    });
 }
@@ -1 +1,2 @@
 pub mod day01;
+pub mod vocabulary;
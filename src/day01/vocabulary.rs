@@ -0,0 +1,321 @@
+use std::fs;
+use crate::aoc_common::automaton::Automaton;
+
+const BUILT_IN: [(&str, [&str;9]);4] = [
+    ("english", ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"]),
+    ("french", ["un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf"]),
+    ("german", ["eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun"]),
+    ("spanish", ["uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve"]),
+];
+
+#[derive(Clone, Debug)]
+pub struct Vocabulary {
+    pub name: String,
+    words: Vec<(String, i32)>,
+    automaton: Automaton,
+    multi_digit_words: bool,
+}
+
+const ENGLISH_TEENS: [&str;10] = ["ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen"];
+const ENGLISH_TENS: [&str;8] = ["twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+
+const DIGITS: [&str;10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+fn alternatives(words: &Vec<(String, i32)>) -> String {
+    let mut sorted: Vec<&str> = words.iter().map(|(word, _)| word.as_str()).collect();
+    sorted.sort_by_key(|word| std::cmp::Reverse(word.len()));
+    let mut pattern = "\\d".to_string();
+    for word in sorted {
+        pattern.push('|');
+        pattern.push_str(&regex::escape(word));
+    }
+    return pattern;
+}
+
+#[cfg(test)]
+mod alternatives_tests {
+    use super::*;
+
+    #[test]
+    fn test_alternatives_longest_first() {
+        let words = vec![("sei".to_string(), 6), ("seis".to_string(), 6), ("a.b".to_string(), 1)];
+        assert_eq!(alternatives(&words), "\\d|seis|sei|a\\.b");
+    }
+
+    #[test]
+    fn test_alternatives_digits_only() {
+        assert_eq!(alternatives(&vec![]), "\\d");
+    }
+}
+
+impl Vocabulary {
+    pub fn new(name: &str, words: Vec<(String, i32)>) -> Result<Vocabulary, String> {
+        for (word, value) in &words {
+            if word.is_empty() || word.chars().any(|c| c.is_whitespace() || c.is_ascii_digit()) {
+                return Err(format!("{}: invalid number word \"{}\"", name, word));
+            }
+            if *value < 0 {
+                return Err(format!("{}: value for \"{}\" must not be negative, found {}", name, word, value));
+            }
+        }
+        let patterns: Vec<&str> = DIGITS.iter().copied().chain(words.iter().map(|(word, _)| word.as_str())).collect();
+        return Ok(Vocabulary { name: name.to_string(), automaton: Automaton::new(&patterns), words, multi_digit_words: false });
+    }
+
+    pub fn digits() -> Vocabulary {
+        return Vocabulary::new("digits", vec![]).unwrap();
+    }
+
+    pub fn built_in(name: &str) -> Option<Vocabulary> {
+        let (name, words) = BUILT_IN.iter().find(|(built_in, _)| built_in.eq_ignore_ascii_case(name))?;
+        let words = words.iter().enumerate().map(|(index, word)| (word.to_string(), index as i32 + 1)).collect();
+        return Some(Vocabulary::new(name, words).unwrap());
+    }
+
+    pub fn built_in_multi_digit(name: &str) -> Option<Vocabulary> {
+        if !name.eq_ignore_ascii_case("english") {
+            return None;
+        }
+        let mut words = Vocabulary::built_in(name)?.words;
+        words.extend(ENGLISH_TEENS.iter().enumerate().map(|(index, word)| (word.to_string(), 10 + index as i32)));
+        for (tens_index, tens) in ENGLISH_TENS.iter().enumerate() {
+            let tens_value = 20 + 10 * tens_index as i32;
+            words.push((tens.to_string(), tens_value));
+            for (unit_index, unit) in BUILT_IN[0].1.iter().enumerate() {
+                let value = tens_value + 1 + unit_index as i32;
+                words.push((format!("{}-{}", tens, unit), value));
+                words.push((format!("{}{}", tens, unit), value));
+            }
+        }
+        return Some(Vocabulary::new("english", words).unwrap().with_multi_digit_words());
+    }
+
+    pub fn with_multi_digit_words(mut self) -> Vocabulary {
+        self.multi_digit_words = true;
+        return self;
+    }
+
+    pub fn built_in_names() -> Vec<&'static str> {
+        return BUILT_IN.iter().map(|(name, _)| *name).collect();
+    }
+
+    pub fn parse(name: &str, text: &str) -> Result<Vocabulary, String> {
+        let mut words: Vec<(String, i32)> = vec![];
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let value = match fields.as_slice() {
+                [_, value] => value.parse::<i32>().ok(),
+                _ => None,
+            };
+            if value.is_none() {
+                return Err(format!("{}:{}: expected \"word value\", found \"{}\"", name, index + 1, line));
+            }
+            words.push((fields[0].to_string(), value.unwrap()));
+        }
+        if words.is_empty() {
+            return Err(format!("{}: no number words defined", name));
+        }
+        return Vocabulary::new(name, words);
+    }
+
+    pub fn load(path: &str) -> Result<Vocabulary, String> {
+        let text = fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+        return Vocabulary::parse(path, &text);
+    }
+
+    pub fn lookup(name_or_path: &str, multi_digit_words: bool) -> Result<Vocabulary, String> {
+        if multi_digit_words && Vocabulary::built_in(name_or_path).is_some() {
+            return Vocabulary::built_in_multi_digit(name_or_path)
+                .ok_or_else(|| format!("multi-digit words are only built in for english; list them in a word file for {}", name_or_path));
+        }
+        if let Some(vocabulary) = Vocabulary::built_in(name_or_path) {
+            return Ok(vocabulary);
+        }
+        if !name_or_path.contains('/') && !name_or_path.contains('.') {
+            return Err(format!(
+                "unknown vocabulary \"{}\" (expected {} or a path to a word file)",
+                name_or_path, Vocabulary::built_in_names().join(", "),
+            ));
+        }
+        let vocabulary = Vocabulary::load(name_or_path)?;
+        return Ok(match multi_digit_words {
+            true => vocabulary.with_multi_digit_words(),
+            false => vocabulary,
+        });
+    }
+
+    pub fn words(&self) -> &Vec<(String, i32)> {
+        return &self.words;
+    }
+
+    pub fn regex_alternatives(&self) -> String {
+        return alternatives(&self.words);
+    }
+
+    pub fn first_and_last<'a>(&self, line: &'a str) -> Option<(&'a str, &'a str)> {
+        let mut first: Option<(usize, usize)> = None;
+        let mut last: Option<(usize, usize)> = None;
+        self.automaton.for_each_match(line, |found| {
+            let span = (found.start, found.end);
+            if first.is_none_or(|(start, end)| span.0 < start || (span.0 == start && span.1 > end)) {
+                first = Some(span);
+            }
+            let is_later = match self.multi_digit_words {
+                true => last.is_none_or(|(start, end)| span.1 > end || span.0 < start),
+                false => last.is_none_or(|(start, end)| span.0 > start || (span.0 == start && span.1 > end)),
+            };
+            if is_later {
+                last = Some(span);
+            }
+        });
+        let (first, last) = (first?, last?);
+        return Some((&line[first.0..first.1], &line[last.0..last.1]));
+    }
+
+    pub fn tokens<'a>(&self, line: &'a str) -> Vec<&'a str> {
+        let mut spans: Vec<(usize, usize)> = vec![];
+        self.automaton.for_each_match(line, |found| {
+            while spans.last().is_some_and(|&(start, _)| start >= found.start) {
+                spans.pop();
+            }
+            if spans.last().is_none_or(|&(_, end)| end < found.end) {
+                spans.push((found.start, found.end));
+            }
+        });
+        return spans.iter().map(|&(start, end)| &line[start..end]).collect();
+    }
+
+    pub fn value(&self, token: &str) -> Option<i32> {
+        if let Some((_, value)) = self.words.iter().find(|(word, _)| word == token) {
+            return Some(*value);
+        }
+        return token.parse::<i32>().ok();
+    }
+}
+
+#[cfg(test)]
+mod vocabulary_tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_english() {
+        let english = Vocabulary::built_in("English").unwrap();
+        assert_eq!(english.name, "english");
+        assert_eq!(english.value("one"), Some(1));
+        assert_eq!(english.value("nine"), Some(9));
+        assert_eq!(english.value("7"), Some(7));
+        assert_eq!(english.value("ten"), None);
+    }
+
+    #[test]
+    fn test_built_in_languages() {
+        assert_eq!(Vocabulary::built_in("french").unwrap().value("quatre"), Some(4));
+        assert_eq!(Vocabulary::built_in("german").unwrap().value("fünf"), Some(5));
+        assert_eq!(Vocabulary::built_in("spanish").unwrap().value("siete"), Some(7));
+        assert_eq!(Vocabulary::built_in("klingon").is_none(), true);
+    }
+
+    #[test]
+    fn test_overlapping_words() {
+        let english = Vocabulary::built_in("english").unwrap();
+        assert_eq!(english.first_and_last("eightwo"), Some(("eight", "two")));
+        assert_eq!(english.first_and_last("xtwone3"), Some(("two", "3")));
+        assert_eq!(english.first_and_last("oneight"), Some(("one", "eight")));
+        let french = Vocabulary::built_in("french").unwrap();
+        assert_eq!(french.first_and_last("huitrois"), Some(("huit", "trois")));
+    }
+
+    #[test]
+    fn test_digits_only() {
+        let digits = Vocabulary::digits();
+        assert_eq!(digits.first_and_last("one2three4five"), Some(("2", "4")));
+        assert_eq!(digits.first_and_last("seven7"), Some(("7", "7")));
+        assert_eq!(digits.first_and_last("none"), None);
+    }
+
+    #[test]
+    fn test_longest_word_at_same_position() {
+        let vocabulary = Vocabulary::parse("custom", "sei 6\nseis 4\n").unwrap();
+        assert_eq!(vocabulary.first_and_last("seis"), Some(("seis", "seis")));
+        assert_eq!(vocabulary.first_and_last("xsei"), Some(("sei", "sei")));
+    }
+
+    #[test]
+    fn test_last_word_starts_latest() {
+        let vocabulary = Vocabulary::parse("custom", "abc 1\nb 2\n").unwrap();
+        assert_eq!(vocabulary.first_and_last("abc"), Some(("abc", "b")));
+        assert_eq!(vocabulary.clone().with_multi_digit_words().first_and_last("abc"), Some(("abc", "abc")));
+    }
+
+    #[test]
+    fn test_parse() {
+        let vocabulary = Vocabulary::parse("custom", "# comment\nalpha 1\n\nbeta 2 # second\n").unwrap();
+        assert_eq!(vocabulary.value("beta"), Some(2));
+        assert_eq!(vocabulary.first_and_last("xbetalpha"), Some(("beta", "alpha")));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Vocabulary::parse("custom", "alpha").unwrap_err(), "custom:1: expected \"word value\", found \"alpha\"");
+        assert_eq!(Vocabulary::parse("custom", "alpha -1").unwrap_err(), "custom: value for \"alpha\" must not be negative, found -1");
+        assert_eq!(Vocabulary::parse("custom", "# empty\n").unwrap_err(), "custom: no number words defined");
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(Vocabulary::lookup("data/day01/vocabularies/italian.txt", false).unwrap().value("otto"), Some(8));
+        assert_eq!(Vocabulary::lookup("spanish", false).unwrap().name, "spanish");
+        assert_eq!(Vocabulary::lookup("italian", false).unwrap_err(), "unknown vocabulary \"italian\" (expected english, french, german, spanish or a path to a word file)");
+    }
+
+    #[test]
+    fn test_lookup_multi_digit_words() {
+        assert_eq!(Vocabulary::lookup("english", true).unwrap().value("ninety-nine"), Some(99));
+        assert_eq!(Vocabulary::lookup("english", false).unwrap().value("eleven"), None);
+        assert_eq!(Vocabulary::lookup("french", true).unwrap_err(), "multi-digit words are only built in for english; list them in a word file for french");
+    }
+
+    #[test]
+    fn test_lookup_multi_digit_words_file() {
+        let path = std::env::temp_dir().join(format!("aoc_day01_multi_digit_vocabulary_{}.txt", std::process::id()));
+        fs::write(&path, "abc 12\nb 3\n").unwrap();
+        let path = path.to_str().unwrap();
+        assert_eq!(Vocabulary::lookup(path, false).unwrap().first_and_last("abc"), Some(("abc", "b")));
+        assert_eq!(Vocabulary::lookup(path, true).unwrap().first_and_last("abc"), Some(("abc", "abc")));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_built_in_multi_digit() {
+        let english = Vocabulary::built_in_multi_digit("english").unwrap();
+        assert_eq!(english.value("eleven"), Some(11));
+        assert_eq!(english.value("twenty"), Some(20));
+        assert_eq!(english.value("twenty-one"), Some(21));
+        assert_eq!(english.value("fortytwo"), Some(42));
+        assert_eq!(english.value("seven"), Some(7));
+        assert_eq!(english.words().len(), 9 + 10 + 8 * 19);
+    }
+
+    #[test]
+    fn test_tokens_drop_contained_words() {
+        let english = Vocabulary::built_in_multi_digit("english").unwrap();
+        assert_eq!(english.tokens("xtwenty-one3"), vec!["twenty-one", "3"]);
+        assert_eq!(english.tokens("oneighteen"), vec!["one", "eighteen"]);
+        assert_eq!(english.tokens("sevenseventeen"), vec!["seven", "seventeen"]);
+        assert_eq!(english.first_and_last("7twenty-one"), Some(("7", "twenty-one")));
+        assert_eq!(english.first_and_last("seventeen"), Some(("seventeen", "seventeen")));
+    }
+
+    #[test]
+    fn test_tokens_keep_overlapping_words() {
+        let english = Vocabulary::built_in("english").unwrap();
+        assert_eq!(english.tokens("eightwothree"), vec!["eight", "two", "three"]);
+        assert_eq!(english.tokens("a1b2c3"), vec!["1", "2", "3"]);
+        assert_eq!(english.tokens("none"), vec!["one"]);
+        assert_eq!(english.tokens("xyz").is_empty(), true);
+    }
+}
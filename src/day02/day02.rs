@@ -1,411 +1,950 @@
-use std::collections::HashMap;
-use crate::aoc_common::lib::{sum, sum_up, sum_up_with_rule};
+use num::BigInt;
+use crate::aoc_common::answer::Answer;
+use crate::aoc_common::checked::{checked_add, checked_product, checked_sum, Accumulator, Arithmetic, OverflowError};
+use crate::aoc_common::context::{Context, SolveError};
+use crate::aoc_common::lib::line_iterator;
+use crate::aoc_common::table::{format_table, TableFormat};
+use crate::trace;
+use super::game::{Colour, Counts, Game, Round};
+use super::inference::{estimate_bag, log_likelihood, Estimate};
+use super::query::{Aggregate, Comparison, Query, Value};
 
-fn get_rgb_for_phrase(round: &str) -> HashMap<&str, i32> {
-    let mut colour_map = HashMap::new();
-    let words: Vec<&str> = round.split(" ").collect();
-    if words.len() == 2 {
-        let number = words[0].parse::<i32>().unwrap();
-        colour_map.insert(words[1], number);
+pub const PARAMETERS: &[&str] = &["bag", "max_bag_size", "max_bag_floor", "format"];
+
+const DEFAULT_BAG: &str = "12 red, 13 green, 14 blue";
+
+const DEFAULT_MAX_BAG_SIZE: u64 = 200;
+const MAX_BAG_SIZE: u64 = 10_000;
+const DEFAULT_MAX_BAG_FLOOR: u64 = 10_000;
+
+#[cfg(test)]
+fn counts(text: &str) -> Counts {
+    return text.parse::<Round>().unwrap().counts().unwrap();
+}
+
+fn is_round_possible(round: &Round, bag: &Counts) -> bool {
+    for (colour, count) in round.counts().unwrap() {
+        if count > *bag.get(&colour).unwrap_or(&0) {
+            return false;
+        }
     }
-    return colour_map;
+    return true;
 }
 
 #[cfg(test)]
-mod get_rgb_for_phrase_tests {
+mod is_round_possible_tests {
     use super::*;
 
+    fn round(text: &str) -> Round {
+        return text.parse().unwrap();
+    }
+
     #[test]
-    fn test_get_rgb_for_phrase_empty() {
-        let colour_map = get_rgb_for_phrase("");
-        assert_eq!(colour_map.is_empty(), true);
+    fn test_is_round_possible_empty_round() {
+        assert_eq!(is_round_possible(&round(""), &counts("12 red, 13 green, 14 blue")), true);
     }
 
     #[test]
-    fn test_get_rgb_for_phrase_1_red() {
-        let colour_map = get_rgb_for_phrase("1 red");
-        assert_eq!(colour_map.is_empty(), false);
-        assert_eq!(colour_map["red"], 1);
+    fn test_is_round_possible_empty_max() {
+        assert_eq!(is_round_possible(&round("12 red, 13 green, 14 blue"), &counts("")), false);
+    }
+
+    #[test]
+    fn test_is_round_possible_some_values_have_no_max() {
+        assert_eq!(is_round_possible(&round("12 red, 13 green, 14 blue"), &counts("12 red, 14 blue")), false);
     }
-}
 
-fn sum_maps<'a>(a: HashMap<&'a str, i32>, b: HashMap<&'a str, i32>) -> HashMap<&'a str, i32> {
-    let mut sum: HashMap<&str, i32> = HashMap::new();
-    for key in a.keys() {
-        sum.insert(key, a[key] + b.get(key).unwrap_or(&0));
+    #[test]
+    fn test_is_round_possible_some_values_are_not_drawn() {
+        assert_eq!(is_round_possible(&round("12 red, 14 blue"), &counts("12 red, 13 green, 14 blue")), true);
     }
-    for key in b.keys() {
-        if !a.contains_key(key) {
-            sum.insert(key, b[key]);
-        }
+
+    #[test]
+    fn test_is_round_possible_some_values_are_too_many() {
+        assert_eq!(is_round_possible(&round("12 red, 14 green, 14 blue"), &counts("12 red, 13 green, 14 blue")), false);
+    }
+
+    #[test]
+    fn test_is_round_possible_some_values_are_fewer() {
+        assert_eq!(is_round_possible(&round("12 red, 12 green, 14 blue"), &counts("12 red, 13 green, 14 blue")), true);
+    }
+
+    #[test]
+    fn test_is_round_possible_all_values_are_equal() {
+        assert_eq!(is_round_possible(&round("12 red, 13 green, 14 blue"), &counts("12 red, 13 green, 14 blue")), true);
+    }
+
+    #[test]
+    fn test_is_round_possible_repeated_colour() {
+        assert_eq!(is_round_possible(&round("7 red, 6 red"), &counts("12 red")), false);
     }
-    return sum;
+}
+
+fn is_game_possible(game: &Game, bag: &Counts) -> bool {
+    return game.rounds.iter().all(|round| is_round_possible(round, bag));
 }
 
 #[cfg(test)]
-mod sum_maps_tests {
+mod is_game_possible_tests {
     use super::*;
 
     #[test]
-    fn test_sum_maps_empty() {
-        let a = HashMap::new();
-        let b = HashMap::new();
-        let result = sum_maps(a, b);
-        assert_eq!(result.is_empty(), true);
+    fn test_is_game_possible_empty_game() {
+        let game = "Game 1: ".parse().unwrap();
+        assert_eq!(is_game_possible(&game, &counts("12 red, 13 green, 14 blue")), true);
+    }
+
+    #[test]
+    fn test_is_game_possible_one_game_fails() {
+        let game = "Game 1: 5 red, 6 green; 12 green; 13 red, 4 blue".parse().unwrap();
+        assert_eq!(is_game_possible(&game, &counts("12 red, 13 green, 14 blue")), false);
     }
 
     #[test]
-    fn test_sum_maps_some() {
-        let a = HashMap::from([
-            ("red", 1),
-            ("green", 2),
-        ]);
-        let b = HashMap::from([
-            ("red", 7),
-            ("blue", 4),
-        ]);
-        let result = sum_maps(a, b);
-        assert_eq!(result.is_empty(), false);
-        assert_eq!(result["red"], 8);
-        assert_eq!(result["green"], 2);
-        assert_eq!(result["blue"], 4);
+    fn test_is_game_possible_all_games_pass() {
+        let game = "Game 1: 5 red, 6 green; 12 green; 12 red, 4 blue".parse().unwrap();
+        assert_eq!(is_game_possible(&game, &counts("12 red, 13 green, 14 blue")), true);
     }
 }
 
-fn get_rgb_for_round(round: &str) -> HashMap<&str, i32> {
-    let phrases: Vec<&str> = round.split(", ").collect();
-    let mut colour_map = HashMap::new();
-    for phrase in phrases {
-        colour_map = sum_maps(colour_map, get_rgb_for_phrase(phrase));
+fn possible_game_id(game: &Game, bag: &Counts) -> u32 {
+    let possible = is_game_possible(game, bag);
+    trace!("game {} possible: {}", game.id, possible);
+    if possible {
+        return game.id;
     }
-    return colour_map;
+    return 0;
 }
 
 #[cfg(test)]
-mod get_rgb_for_round_tests {
+mod possible_game_id_tests {
     use super::*;
 
     #[test]
-    fn test_get_rgb_for_round_empty() {
-        let colour_map = get_rgb_for_round("");
-        assert_eq!(colour_map.is_empty(), true);
+    fn test_possible_game_id_empty_game() {
+        let game = "Game 4: ".parse().unwrap();
+        assert_eq!(possible_game_id(&game, &counts("12 red, 13 green, 14 blue")), 4);
+    }
+
+    #[test]
+    fn test_possible_game_id_one_game_fails() {
+        let game = "Game 4: 5 red, 6 green; 12 green; 13 red, 4 blue".parse().unwrap();
+        assert_eq!(possible_game_id(&game, &counts("12 red, 13 green, 14 blue")), 0);
     }
 
     #[test]
-    fn test_get_rgb_for_round_1_red_2_green() {
-        let colour_map = get_rgb_for_round("1 red, 2 green");
-        assert_eq!(colour_map.is_empty(), false);
-        assert_eq!(colour_map["red"], 1);
-        assert_eq!(colour_map["green"], 2);
+    fn test_possible_game_id_all_games_pass() {
+        let game = "Game 4: 5 red, 6 green; 12 green; 12 red, 4 blue".parse().unwrap();
+        assert_eq!(possible_game_id(&game, &counts("12 red, 13 green, 14 blue")), 4);
     }
 }
 
-fn is_round_possible(round: HashMap<&str, i32>, max: HashMap<&str, i32>) -> bool {
-    for key in round.keys() {
-        if round[key] > *max.get(key).unwrap_or(&0) {
-            return false;
+fn update_min(round: &Round, current_min: Counts) -> Counts {
+    let mut new_min = current_min;
+    for (colour, count) in round.counts().unwrap() {
+        let min_value = new_min.entry(colour).or_insert(0);
+        if *min_value < count {
+            *min_value = count;
         }
     }
-    return true;
+    return new_min;
 }
 
 #[cfg(test)]
-mod is_round_possible_tests {
+mod update_min_tests {
     use super::*;
 
     #[test]
-    fn test_is_round_possible_empty_round() {
-        let round = get_rgb_for_round("");
-        let max = get_rgb_for_round("12 red, 13 green, 14 blue");
-        assert_eq!(is_round_possible(round, max), true);
+    fn test_update_min() {
+        let round = "3 blue, 5 green, 6 yellow".parse().unwrap();
+        let new_min = update_min(&round, counts("4 red, 3 green, 7 yellow"));
+        assert_eq!(new_min, counts("4 red, 5 green, 3 blue, 7 yellow"));
     }
+}
+
+fn get_min_for_game(game: &Game) -> Counts {
+    let mut min = Counts::new();
+    for round in &game.rounds {
+        min = update_min(round, min);
+    }
+    return min;
+}
+
+#[cfg(test)]
+mod get_min_for_game_tests {
+    use super::*;
 
     #[test]
-    fn test_is_round_possible_empty_max() {
-        let round = get_rgb_for_round("12 red, 13 green, 14 blue");
-        let max = get_rgb_for_round("");
-        assert_eq!(is_round_possible(round, max), false);
+    fn test_get_min_for_game() {
+        let game = "Game 1: 3 blue, 5 green, 6 yellow; 4 red, 3 green, 7 yellow".parse().unwrap();
+        assert_eq!(get_min_for_game(&game), counts("4 red, 5 green, 3 blue, 7 yellow"));
     }
+}
+
+fn get_power_for_min<T: Accumulator + From<u32>>(min: &Counts) -> Result<T, OverflowError> {
+    return checked_product(min.values().map(|count| T::from(*count)));
+}
+
+#[cfg(test)]
+mod get_power_for_min_tests {
+    use super::*;
 
     #[test]
-    fn test_is_round_possible_some_values_have_no_max() {
-        let round = get_rgb_for_round("12 red, 13 green, 14 blue");
-        let max = get_rgb_for_round("12 red, 14 blue");
-        assert_eq!(is_round_possible(round, max), false);
+    fn test_get_power_for_min() {
+        assert_eq!(get_power_for_min::<i64>(&counts("4 red, 5 green, 3 blue")), Ok(60));
     }
 
     #[test]
-    fn test_is_round_possible_some_values_are_not_drawn() {
-        let round = get_rgb_for_round("12 red, 14 blue");
-        let max = get_rgb_for_round("12 red, 13 green, 14 blue");
-        assert_eq!(is_round_possible(round, max), true);
+    fn test_get_power_for_min_overflow() {
+        assert_eq!(get_power_for_min::<i64>(&counts("4000000000 red, 4000000000 green")).is_err(), true);
     }
+}
+
+fn game_power<T: Accumulator + From<u32>>(game: &Game) -> Result<T, OverflowError> {
+    return get_power_for_min(&get_min_for_game(game));
+}
+
+#[cfg(test)]
+mod game_power_tests {
+    use super::*;
 
     #[test]
-    fn test_is_round_possible_some_values_are_too_many() {
-        let round = get_rgb_for_round("12 red, 14 green, 14 blue");
-        let max = get_rgb_for_round("12 red, 13 green, 14 blue");
-        assert_eq!(is_round_possible(round, max), false);
+    fn test_game_power() {
+        let game = "Game 1: 3 blue, 5 green, 6 yellow; 4 red, 3 green, 7 yellow".parse().unwrap();
+        assert_eq!(game_power::<i64>(&game), Ok(420));
+    }
+}
+
+fn read_games(filename: &str) -> Result<Vec<Game>, SolveError> {
+    let mut games = vec![];
+    for (index, line) in line_iterator(filename).enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let game = line.parse::<Game>().map_err(|error| SolveError::InvalidInput {
+            file: filename.to_string(),
+            message: format!("line {}: {}", index + 1, error),
+        })?;
+        games.push(game);
     }
+    return Ok(games);
+}
+
+#[cfg(test)]
+mod read_games_tests {
+    use super::*;
 
     #[test]
-    fn test_is_round_possible_some_values_are_fewer() {
-        let round = get_rgb_for_round("12 red, 12 green, 14 blue");
-        let max = get_rgb_for_round("12 red, 13 green, 14 blue");
-        assert_eq!(is_round_possible(round, max), true);
+    fn test_read_games() {
+        let games = read_games("data/day02/test.txt").unwrap();
+        assert_eq!(games.len(), 5);
+        assert_eq!(games[2].id, 3);
+        assert_eq!(games[2].rounds.len(), 3);
     }
 
     #[test]
-    fn test_is_round_possible_all_values_are_equal() {
-        let round = get_rgb_for_round("12 red, 13 green, 14 blue");
-        let max = get_rgb_for_round("12 red, 13 green, 14 blue");
-        assert_eq!(is_round_possible(round, max), true);
+    fn test_read_games_malformed() {
+        assert_eq!(read_games("data/day02/malformed_test.txt"), Err(SolveError::InvalidInput {
+            file: "data/day02/malformed_test.txt".to_string(),
+            message: "line 2: Game 2, round 1: invalid count \"x\" in \"x red\"".to_string(),
+        }));
     }
 }
 
-fn is_game_possible(game: &str, max: &str) -> bool {
-    let max_map = get_rgb_for_round(max);
-    let rounds: Vec<&str> = game.split("; ").collect();
-    for round in rounds {
-        if !is_round_possible(get_rgb_for_round(round), max_map.clone()) {
-            return false;
-        }
+fn sum_possible_game_ids<T: Accumulator + From<u32>>(filename: &str, bag: &Counts) -> Result<T, SolveError> {
+    let mut total = T::zero();
+    for game in read_games(filename)? {
+        total = checked_add(total, T::from(possible_game_id(&game, bag)))?;
     }
-    return true;
+    return Ok(total);
 }
 
 #[cfg(test)]
-mod is_game_possible_tests {
+mod sum_possible_game_ids_tests {
     use super::*;
 
     #[test]
-    fn test_is_game_possible_empty_game() {
-        let game = "";
-        let max = "12 red, 13 green, 14 blue";
-        assert_eq!(is_game_possible(game, max), true);
+    fn test_sum_possible_game_ids_part1() {
+        assert_eq!(sum_possible_game_ids::<i64>("data/day02/test.txt", &counts("12 red, 13 green, 14 blue")), Ok(8));
     }
+}
+
+fn sum_game_powers<T: Accumulator + From<u32>>(filename: &str) -> Result<T, SolveError> {
+    let mut total = T::zero();
+    for game in read_games(filename)? {
+        total = checked_add(total, game_power(&game)?)?;
+    }
+    return Ok(total);
+}
+
+#[cfg(test)]
+mod sum_game_powers_tests {
+    use super::*;
 
     #[test]
-    fn test_is_game_possible_one_game_fails() {
-        let game = "5 red, 6 green; 12 green; 13 red, 4 blue";
-        let max = "12 red, 13 green, 14 blue";
-        assert_eq!(is_game_possible(game, max), false);
+    fn test_sum_game_powers_part2() {
+        assert_eq!(sum_game_powers::<i64>("data/day02/test.txt"), Ok(2286));
     }
 
     #[test]
-    fn test_is_game_possible_all_games_pass() {
-        let game = "5 red, 6 green; 12 green; 12 red, 4 blue";
-        let max = "12 red, 13 green, 14 blue";
-        assert_eq!(is_game_possible(game, max), true);
+    fn test_sum_game_powers_big() {
+        assert_eq!(sum_game_powers::<BigInt>("data/day02/test.txt"), Ok(BigInt::from(2286)));
     }
 }
 
-fn game_number(game_number_str: &str) -> i32 {
-    let game_number_vec: Vec<&str> = game_number_str.split(" ", ).collect();
-    if game_number_vec.len() < 2 || game_number_vec[0] != "Game" {
-        return 0;
+fn aggregate_value(aggregate: Aggregate, colour: &Colour, game: &Game, min: &Counts) -> i64 {
+    let counts = game.rounds.iter().map(|round| *round.counts().unwrap().get(colour).unwrap_or(&0) as i64);
+    return match aggregate {
+        Aggregate::Max => *min.get(colour).unwrap_or(&0) as i64,
+        Aggregate::Min => counts.min().unwrap_or(0),
+        Aggregate::Sum => counts.sum(),
+    };
+}
+
+fn query_value(value: &Value, game: &Game, min: &Counts, round: &Counts) -> i64 {
+    return match value {
+        Value::Number(number) => *number,
+        Value::Count(colour) => *round.get(colour).unwrap_or(&0) as i64,
+        Value::Aggregate(aggregate, colour) => aggregate_value(*aggregate, colour, game, min),
+        Value::Rounds => game.rounds.len() as i64,
+    };
+}
+
+fn upper_bound<'a>(left: &'a Value, comparison: Comparison, right: &'a Value) -> Option<(&'a Colour, i64)> {
+    return match (left, comparison, right) {
+        (Value::Count(colour), Comparison::LessOrEqual, Value::Number(limit)) => Some((colour, *limit)),
+        (Value::Count(colour), Comparison::Less, Value::Number(limit)) => Some((colour, limit.saturating_sub(1))),
+        (Value::Number(limit), Comparison::GreaterOrEqual, Value::Count(colour)) => Some((colour, *limit)),
+        (Value::Number(limit), Comparison::Greater, Value::Count(colour)) => Some((colour, limit.saturating_sub(1))),
+        _ => None,
+    };
+}
+
+#[cfg(test)]
+mod upper_bound_tests {
+    use super::*;
+
+    #[test]
+    fn test_upper_bound() {
+        let red = Value::Count("red".parse().unwrap());
+        assert_eq!(upper_bound(&red, Comparison::LessOrEqual, &Value::Number(12)), Some((&"red".parse().unwrap(), 12)));
+        assert_eq!(upper_bound(&red, Comparison::Less, &Value::Number(12)), Some((&"red".parse().unwrap(), 11)));
+        assert_eq!(upper_bound(&Value::Number(12), Comparison::GreaterOrEqual, &red), Some((&"red".parse().unwrap(), 12)));
+        assert_eq!(upper_bound(&Value::Number(12), Comparison::Greater, &red), Some((&"red".parse().unwrap(), 11)));
+        assert_eq!(upper_bound(&red, Comparison::Greater, &Value::Number(12)), None);
+        assert_eq!(upper_bound(&red, Comparison::LessOrEqual, &Value::Rounds), None);
     }
-    return game_number_vec[1].parse::<i32>().unwrap();
+}
+
+fn game_matches(game: &Game, min: &Counts, query: &Query) -> bool {
+    return match query {
+        Query::Compare(left, comparison, right) => {
+            if let Some((colour, limit)) = upper_bound(left, *comparison, right) {
+                if limit < 0 {
+                    return game.rounds.is_empty();
+                }
+                let mut bag = min.clone();
+                bag.insert(colour.clone(), limit.min(u32::MAX as i64) as u32);
+                return is_game_possible(game, &bag);
+            }
+            let holds = |round: &Counts| comparison.holds(query_value(left, game, min, round), query_value(right, game, min, round));
+            if left.is_per_round() || right.is_per_round() {
+                game.rounds.iter().all(|round| holds(&round.counts().unwrap()))
+            } else {
+                holds(&Counts::new())
+            }
+        }
+        Query::Not(inner) => game_matches(game, min, &inner.negated()),
+        Query::And(left, right) => game_matches(game, min, left) && game_matches(game, min, right),
+        Query::Or(left, right) => game_matches(game, min, left) || game_matches(game, min, right),
+    };
 }
 
 #[cfg(test)]
-mod game_number_tests {
+mod game_matches_tests {
     use super::*;
 
+    fn matching_ids(query: &str) -> Vec<u32> {
+        let query = query.parse().unwrap();
+        return read_games("data/day02/test.txt").unwrap().into_iter()
+            .filter(|game| game_matches(game, &get_min_for_game(game), &query))
+            .map(|game| game.id)
+            .collect();
+    }
+
+    #[test]
+    fn test_game_matches_part1_bag() {
+        assert_eq!(matching_ids("red <= 12 && green <= 13 && blue <= 14"), vec![1, 2, 5]);
+    }
+
+    #[test]
+    fn test_game_matches_colours_compared_per_round() {
+        assert_eq!(matching_ids("red <= 12 && blue > green"), vec![]);
+        assert_eq!(matching_ids("blue >= 1 || red >= 1"), vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_game_matches_aggregates() {
+        assert_eq!(matching_ids("max(green) >= 5"), vec![3]);
+        assert_eq!(matching_ids("max(blue) > max(green)"), vec![1, 2, 4]);
+        assert_eq!(matching_ids("min(green) == 0"), vec![1]);
+        assert_eq!(matching_ids("sum(red) == 25"), vec![3]);
+    }
+
+    #[test]
+    fn test_game_matches_rounds() {
+        assert_eq!(matching_ids("rounds >= 3"), vec![1, 2, 3, 4]);
+        assert_eq!(matching_ids("!(rounds >= 3) || sum(red) == 25"), vec![3, 5]);
+    }
+
+    #[test]
+    fn test_game_matches_upper_bounds() {
+        assert_eq!(matching_ids("red < 13 && 13 >= green && 15 > blue"), vec![1, 2, 5]);
+        assert_eq!(matching_ids("red <= 4"), vec![1, 2]);
+        assert_eq!(matching_ids("red < 0"), vec![]);
+        assert_eq!(matching_ids("purple <= 0 && red <= 99999999999"), vec![1, 2, 3, 4, 5]);
+    }
+
     #[test]
-    fn test_game_number_empty_string() {
-        assert_eq!(game_number(""), 0);
+    fn test_game_matches_negation_applies_to_every_round() {
+        assert_eq!(matching_ids("red > 5"), vec![]);
+        assert_eq!(matching_ids("!(red > 5)"), vec![1, 2]);
+        assert_eq!(matching_ids("!(red > 5)"), matching_ids("red <= 5"));
+        assert_eq!(matching_ids("!(red <= 5 || blue >= 1)"), vec![]);
+        assert_eq!(matching_ids("!!(red <= 5)"), matching_ids("red <= 5"));
     }
 
     #[test]
-    fn test_game_number_not_game() {
-        assert_eq!(game_number("4"), 0);
+    fn test_game_matches_unknown_colour() {
+        assert_eq!(matching_ids("purple == 0 && max(purple) == 0"), vec![1, 2, 3, 4, 5]);
     }
 
     #[test]
-    fn test_game_number_valid_string() {
-        assert_eq!(game_number("Game 4"), 4);
+    fn test_game_matches_no_rounds() {
+        let game = "Game 9: ".parse().unwrap();
+        let query = "red > 100 && rounds == 0 && max(red) == 0".parse().unwrap();
+        assert_eq!(game_matches(&game, &Counts::new(), &query), true);
+        assert_eq!(game_matches(&game, &Counts::new(), &"red < 0 && !(red <= 100)".parse().unwrap()), true);
     }
 }
 
-fn possible_game_id(game: &str, max: &str) -> i32 {
-    let game_vec: Vec<&str> = game.split(": ", ).collect();
-    if game_vec.len() < 2 {
-        return 0;
+pub fn query(filename: &str, expression: &str) -> Result<String, SolveError> {
+    let query = expression.parse::<Query>().map_err(|error| SolveError::InvalidParameter {
+        name: "query".to_string(),
+        message: format!("\"{}\" {}", expression, error),
+    })?;
+    let mut ids = vec![];
+    for game in read_games(filename)? {
+        if game_matches(&game, &get_min_for_game(&game), &query) {
+            ids.push(game.id);
+        }
     }
-    let game_number = game_number(game_vec[0]);
-    let possible = is_game_possible(game_vec[1], max);
-    if possible {
-        return game_number;
+    let sum = checked_sum(ids.iter().map(|id| *id as u64))?;
+    let mut listed = ids.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(", ");
+    if ids.is_empty() {
+        listed = "none".to_string();
     }
-    return 0;
+    return Ok(format!("Matching games: {}\nCount: {}\nSum of ids: {}", listed, ids.len(), sum));
 }
 
 #[cfg(test)]
-mod possible_game_id_tests {
+mod query_tests {
     use super::*;
 
     #[test]
-    fn test_possible_game_id_empty_string() {
-        let game = "";
-        let max = "12 red, 13 green, 14 blue";
-        assert_eq!(possible_game_id(game, max), 0);
+    fn test_query() {
+        assert_eq!(query("data/day02/test.txt", "max(blue) > max(green)"), Ok("Matching games: 1, 2, 4\nCount: 3\nSum of ids: 7".to_string()));
     }
 
     #[test]
-    fn test_possible_game_id_empty_game() {
-        let game = "Game 4: ";
-        let max = "12 red, 13 green, 14 blue";
-        assert_eq!(possible_game_id(game, max), 4);
+    fn test_query_no_matches() {
+        assert_eq!(query("data/day02/test.txt", "rounds > 3"), Ok("Matching games: none\nCount: 0\nSum of ids: 0".to_string()));
     }
 
     #[test]
-    fn test_possible_game_id_one_game_fails() {
-        let game = "Game 4: 5 red, 6 green; 12 green; 13 red, 4 blue";
-        let max = "12 red, 13 green, 14 blue";
-        assert_eq!(possible_game_id(game, max), 0);
+    fn test_query_invalid() {
+        assert_eq!(query("data/day02/test.txt", "red <"), Err(SolveError::InvalidParameter {
+            name: "query".to_string(),
+            message: "\"red <\" column 6: expected a colour, number, rounds or max/min/sum(colour), found end of query".to_string(),
+        }));
     }
+}
+
+fn bag_parameter(context: &Context) -> Result<Counts, SolveError> {
+    let bag = context.parameter("bag").unwrap_or(DEFAULT_BAG);
+    let invalid = |message: String| SolveError::InvalidParameter {
+        name: "bag".to_string(),
+        message: format!("\"{}\" should look like \"12 red, 13 green, 14 blue\": {}", bag, message),
+    };
+    let round = bag.parse::<Round>().map_err(|error| invalid(error.to_string()))?;
+    if round.draws.is_empty() {
+        return Err(invalid("the bag is empty".to_string()));
+    }
+    return round.counts().map_err(|error| invalid(error.to_string()));
+}
+
+#[cfg(test)]
+mod bag_parameter_tests {
+    use super::*;
 
     #[test]
-    fn test_possible_game_id_missing_game_number() {
-        let game = "5 red, 6 green; 12 green; 12 red, 4 blue";
-        let max = "12 red, 13 green, 14 blue";
-        assert_eq!(possible_game_id(game, max), 0);
+    fn test_bag_parameter_default() {
+        assert_eq!(bag_parameter(&Context::default()), Ok(counts(DEFAULT_BAG)));
     }
 
     #[test]
-    fn test_possible_game_id_all_games_pass() {
-        let game = "Game 4: 5 red, 6 green; 12 green; 12 red, 4 blue";
-        let max = "12 red, 13 green, 14 blue";
-        assert_eq!(possible_game_id(game, max), 4);
+    fn test_bag_parameter_configured() {
+        let context = Context::default().with_parameter("bag", "1 red, 2 purple");
+        assert_eq!(bag_parameter(&context), Ok(counts("1 red, 2 purple")));
+    }
+
+    #[test]
+    fn test_bag_parameter_invalid() {
+        for bag in ["", "x red", "12 red,13 green", "12 red, 13", "-1 blue"] {
+            let context = Context::default().with_parameter("bag", bag);
+            assert_eq!(bag_parameter(&context).is_err(), true, "{}", bag);
+        }
+    }
+
+    #[test]
+    fn test_bag_parameter_message() {
+        let context = Context::default().with_parameter("bag", "x red");
+        assert_eq!(bag_parameter(&context), Err(SolveError::InvalidParameter {
+            name: "bag".to_string(),
+            message: "\"x red\" should look like \"12 red, 13 green, 14 blue\": invalid count \"x\" in \"x red\"".to_string(),
+        }));
     }
 }
 
-fn update_min<'a>(round: HashMap<&'a str, i32>, current_min: HashMap<&'a str, i32>) -> HashMap<&'a str, i32> {
-    let mut new_min = current_min.clone();
-    for key in round.keys() {
-        let mut min_value = new_min.get(key).unwrap_or(&0);
-        if min_value < &round[key] {
-            min_value = &round[key];
+fn max_bag_size_parameter(context: &Context) -> Result<u64, SolveError> {
+    let value = context.parameter("max_bag_size").unwrap_or("");
+    if value.is_empty() {
+        return Ok(DEFAULT_MAX_BAG_SIZE);
+    }
+    return match value.parse::<u64>() {
+        Ok(size) if size > 0 && size <= MAX_BAG_SIZE => Ok(size),
+        _ => Err(SolveError::InvalidParameter {
+            name: "max_bag_size".to_string(),
+            message: format!("expected a whole number from 1 to {}, found \"{}\"", MAX_BAG_SIZE, value),
+        }),
+    };
+}
+
+#[cfg(test)]
+mod max_bag_size_parameter_tests {
+    use super::*;
+
+    #[test]
+    fn test_max_bag_size_parameter() {
+        assert_eq!(max_bag_size_parameter(&Context::default()), Ok(DEFAULT_MAX_BAG_SIZE));
+        assert_eq!(max_bag_size_parameter(&Context::default().with_parameter("max_bag_size", "50")), Ok(50));
+        assert_eq!(max_bag_size_parameter(&Context::default().with_parameter("max_bag_size", "10000")), Ok(MAX_BAG_SIZE));
+    }
+
+    #[test]
+    fn test_max_bag_size_parameter_invalid() {
+        for value in ["0", "-3", "big", "10001", "18446744073709551616"] {
+            let context = Context::default().with_parameter("max_bag_size", value);
+            assert_eq!(max_bag_size_parameter(&context).is_err(), true, "{}", value);
         }
-        new_min.insert(*key, *min_value);
     }
-    return new_min;
+}
+
+fn max_bag_floor_parameter(context: &Context) -> Result<u64, SolveError> {
+    let value = context.parameter("max_bag_floor").unwrap_or("");
+    if value.is_empty() {
+        return Ok(DEFAULT_MAX_BAG_FLOOR);
+    }
+    return match value.parse::<u64>() {
+        Ok(floor) if floor > 0 => Ok(floor),
+        _ => Err(SolveError::InvalidParameter {
+            name: "max_bag_floor".to_string(),
+            message: format!("expected a positive whole number, found \"{}\"", value),
+        }),
+    };
 }
 
 #[cfg(test)]
-mod update_min_tests {
+mod max_bag_floor_parameter_tests {
     use super::*;
 
     #[test]
-    fn test_update_min() {
-        let round = get_rgb_for_round("3 blue, 5 green, 6 yellow");
-        let current_min = get_rgb_for_round("4 red, 3 green, 7 yellow");
-        let new_min = update_min(round, current_min);
-        assert_eq!(new_min["red"], 4);
-        assert_eq!(new_min["green"], 5);
-        assert_eq!(new_min["blue"], 3);
-        assert_eq!(new_min["yellow"], 7);
+    fn test_max_bag_floor_parameter() {
+        assert_eq!(max_bag_floor_parameter(&Context::default()), Ok(DEFAULT_MAX_BAG_FLOOR));
+        assert_eq!(max_bag_floor_parameter(&Context::default().with_parameter("max_bag_floor", "50")), Ok(50));
+        assert_eq!(max_bag_floor_parameter(&Context::default().with_parameter("max_bag_floor", "10000000000")), Ok(10_000_000_000));
+    }
+
+    #[test]
+    fn test_max_bag_floor_parameter_invalid() {
+        for value in ["0", "-3", "big", "18446744073709551616"] {
+            let context = Context::default().with_parameter("max_bag_floor", value);
+            assert_eq!(max_bag_floor_parameter(&context).is_err(), true, "{}", value);
+        }
     }
 }
 
-fn get_min_for_game(rounds: &str) -> HashMap<&str, i32> {
-    let mut min = HashMap::new();
-    let round_vec: Vec<&str> = rounds.split("; ", ).collect();
-    for round in round_vec {
-        min = update_min(get_rgb_for_round(round), min);
+fn check_bag_floor(filename: &str, description: &str, minimum: &Counts, max_floor: u64) -> Result<(), SolveError> {
+    let size: u64 = minimum.values().map(|count| *count as u64).sum();
+    if size > max_floor {
+        return Err(SolveError::InvalidInput {
+            file: filename.to_string(),
+            message: format!("{} needs at least {} cubes, more than the max_bag_floor of {}", description, size, max_floor),
+        });
     }
-    return min;
+    return Ok(());
 }
 
 #[cfg(test)]
-mod get_min_for_game_tests {
+mod check_bag_floor_tests {
     use super::*;
 
     #[test]
-    fn test_get_min_for_game() {
-        let min = get_min_for_game("3 blue, 5 green, 6 yellow; 4 red, 3 green, 7 yellow");
-        assert_eq!(min["red"], 4);
-        assert_eq!(min["green"], 5);
-        assert_eq!(min["blue"], 3);
-        assert_eq!(min["yellow"], 7);
+    fn test_check_bag_floor() {
+        assert_eq!(check_bag_floor("games.txt", "game 1", &counts("5000 red, 5000 blue"), DEFAULT_MAX_BAG_FLOOR), Ok(()));
+        assert_eq!(check_bag_floor("games.txt", "game 1", &counts("5000 red, 5001 blue"), DEFAULT_MAX_BAG_FLOOR), Err(SolveError::InvalidInput {
+            file: "games.txt".to_string(),
+            message: "game 1 needs at least 10001 cubes, more than the max_bag_floor of 10000".to_string(),
+        }));
+        assert_eq!(check_bag_floor("games.txt", "all games", &counts("4294967295 red, 4294967295 blue"), DEFAULT_MAX_BAG_FLOOR).is_err(), true);
+    }
+
+    #[test]
+    fn test_check_bag_floor_configured() {
+        assert_eq!(check_bag_floor("games.txt", "game 1", &counts("5000 red, 5001 blue"), 20_000), Ok(()));
+        assert_eq!(check_bag_floor("games.txt", "all games", &counts("4294967295 red, 4294967295 blue"), u64::MAX), Ok(()));
+        assert_eq!(check_bag_floor("games.txt", "game 1", &counts("3 red, 2 blue"), 4).is_err(), true);
+    }
+}
+
+fn describe_estimate(estimate: &Estimate) -> String {
+    let mut description = Round::from_counts(&estimate.bag).to_string();
+    if description.is_empty() {
+        description = "-".to_string();
     }
+    if estimate.at_limit {
+        description.push_str(" (size limit)");
+    }
+    return description;
 }
 
-fn get_power_for_min(min: HashMap<&str, i32>) -> i32 {
-    let mut power = 1;
-    for key in min.keys() {
-        power *= min[key];
+pub fn inference_report(filename: &str, context: &Context) -> Result<String, SolveError> {
+    let bag = bag_parameter(context)?;
+    let max_size = max_bag_size_parameter(context)?;
+    let max_floor = max_bag_floor_parameter(context)?;
+    let format = format_parameter(context)?;
+    let games = read_games(filename)?;
+    let header = ["game", "rounds", "log10 P", "P", "best-fit bag"];
+    let row = |id: String, rounds: usize, log_probability: f64, estimate: &Estimate| vec![
+        id, rounds.to_string(), format!("{:.4}", log_probability / std::f64::consts::LN_10),
+        format!("{:.3e}", log_probability.exp()), describe_estimate(estimate),
+    ];
+    let mut rows = vec![];
+    let mut all_rounds = vec![];
+    let mut all_minimum = Counts::new();
+    let mut total_log_probability = 0.0;
+    let mut possible = 0;
+    for game in &games {
+        let rounds: Vec<Counts> = game.rounds.iter().map(|round| round.counts().unwrap()).collect();
+        let log_probability = log_likelihood(&rounds, &bag);
+        let minimum = get_min_for_game(game);
+        check_bag_floor(filename, &format!("game {}", game.id), &minimum, max_floor)?;
+        let estimate = estimate_bag(&rounds, &minimum, max_size);
+        rows.push(row(game.id.to_string(), rounds.len(), log_probability, &estimate));
+        for round in &game.rounds {
+            all_minimum = update_min(round, all_minimum);
+        }
+        all_rounds.extend(rounds);
+        total_log_probability += log_probability;
+        if log_probability > f64::NEG_INFINITY {
+            possible += 1;
+        }
     }
-    return power;
+    check_bag_floor(filename, "all games", &all_minimum, max_floor)?;
+    let estimate = estimate_bag(&all_rounds, &all_minimum, max_size);
+    if format == TableFormat::Csv {
+        rows.push(row("all".to_string(), all_rounds.len(), total_log_probability, &estimate));
+        return Ok(format_table(&header, &rows, format));
+    }
+    let lines = [
+        format!("Probability of each game under bag {} (draws without replacement)", Round::from_counts(&bag)),
+        format_table(&header, &rows, format),
+        format!(
+            "All games: log10 P = {:.4}, {} of {} possible under the bag",
+            total_log_probability / std::f64::consts::LN_10, possible, games.len(),
+        ),
+        format!(
+            "Best-fit bag for all games: {}, log10 likelihood {:.4}",
+            describe_estimate(&estimate), estimate.log_likelihood / std::f64::consts::LN_10,
+        ),
+    ];
+    return Ok(lines.join("\n"));
 }
 
 #[cfg(test)]
-mod get_power_for_min_tests {
+mod inference_report_tests {
     use super::*;
 
     #[test]
-    fn test_get_power_for_min() {
-        let min = HashMap::from([
-            ("red", 4),
-            ("green", 5),
-            ("blue", 3),
-        ]);
-        assert_eq!(get_power_for_min(min), 60);
+    fn test_inference_report() {
+        let report = inference_report("data/day02/test.txt", &Context::default()).unwrap();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], "Probability of each game under bag 14 blue, 13 green, 12 red (draws without replacement)");
+        assert_eq!(lines[1], "game  rounds  log10 P         P  best-fit bag");
+        assert_eq!(lines[4].starts_with("   3       3     -inf   0.000e0  "), true);
+        assert_eq!(lines[7], "All games: log10 P = -inf, 3 of 5 possible under the bag");
+    }
+
+    #[test]
+    fn test_inference_report_possible_games() {
+        let context = Context::default().with_parameter("bag", "20 red, 13 green, 15 blue");
+        let report = inference_report("data/day02/test.txt", &context).unwrap();
+        assert_eq!(report.lines().nth(7).unwrap().ends_with(", 5 of 5 possible under the bag"), true);
+    }
+
+    #[test]
+    fn test_inference_report_best_fit_bags() {
+        let report = inference_report("data/day02/inference_test.txt", &Context::default()).unwrap();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[2], "   1       3  -2.9563  1.106e-3  86 blue, 114 red (size limit)");
+        assert_eq!(lines[3], "   2       2  -2.2630  5.458e-3  1 blue, 5 red");
+        assert_eq!(lines[5], "Best-fit bag for all games: 66 blue, 132 red, log10 likelihood -2.5424");
+    }
+
+    #[test]
+    fn test_inference_report_size_limit() {
+        let context = Context::default().with_parameter("max_bag_size", "5");
+        let report = inference_report("data/day02/inference_test.txt", &context).unwrap();
+        assert_eq!(report.lines().nth(3).unwrap().ends_with("  1 blue, 4 red (size limit)"), true);
+    }
+
+    #[test]
+    fn test_inference_report_csv() {
+        let context = Context::default().with_parameter("format", "csv");
+        let report = inference_report("data/day02/inference_test.txt", &context).unwrap();
+        assert_eq!(report, "\
+game,rounds,log10 P,P,best-fit bag
+1,3,-2.9563,1.106e-3,\"86 blue, 114 red (size limit)\"
+2,2,-2.2630,5.458e-3,\"1 blue, 5 red\"
+all,5,-5.2193,6.036e-6,\"66 blue, 132 red\"");
+    }
+}
+
+fn format_parameter(context: &Context) -> Result<TableFormat, SolveError> {
+    return match context.parameter("format") {
+        None => Ok(TableFormat::default()),
+        Some(format) => format.parse().map_err(|message| SolveError::InvalidParameter { name: "format".to_string(), message }),
+    };
+}
+
+#[cfg(test)]
+mod format_parameter_tests {
+    use super::*;
+
+    #[test]
+    fn test_format_parameter() {
+        assert_eq!(format_parameter(&Context::default()), Ok(TableFormat::Text));
+        assert_eq!(format_parameter(&Context::default().with_parameter("format", "csv")), Ok(TableFormat::Csv));
+        assert_eq!(format_parameter(&Context::default().with_parameter("format", "xml")).is_err(), true);
     }
 }
 
-fn game_power(game: &str) -> i32 {
-    let game_vec: Vec<&str> = game.split(": ", ).collect();
-    if game_vec.len() < 2 {
-        return 0;
+fn file_colours(games: &[Game]) -> Vec<Colour> {
+    let mut minimum = Counts::new();
+    for round in games.iter().flat_map(|game| &game.rounds) {
+        minimum = update_min(round, minimum);
     }
-    let min = get_min_for_game(game_vec[1]);
-    return get_power_for_min(min);
+    return minimum.into_keys().collect();
+}
+
+fn power_text(game: &Game, arithmetic: Arithmetic) -> Result<String, OverflowError> {
+    return Ok(match arithmetic {
+        Arithmetic::Checked => game_power::<i64>(game)?.to_string(),
+        Arithmetic::Big => game_power::<BigInt>(game)?.to_string(),
+    });
+}
+
+pub fn games_report(filename: &str, context: &Context) -> Result<String, SolveError> {
+    let bag = bag_parameter(context)?;
+    let format = format_parameter(context)?;
+    let games = read_games(filename)?;
+    let colours = file_colours(&games);
+    let mut header = vec!["id".to_string(), "rounds".to_string()];
+    header.extend(colours.iter().map(|colour| format!("max {}", colour)));
+    header.extend(["min bag".to_string(), "power".to_string(), "possible".to_string()]);
+    let mut rows = vec![];
+    for game in &games {
+        let min = get_min_for_game(game);
+        let mut row = vec![game.id.to_string(), game.rounds.len().to_string()];
+        row.extend(colours.iter().map(|colour| min.get(colour).unwrap_or(&0).to_string()));
+        row.push(Round::from_counts(&min).to_string());
+        row.push(power_text(game, context.arithmetic)?);
+        row.push(if is_game_possible(game, &bag) { "yes" } else { "no" }.to_string());
+        rows.push(row);
+    }
+    return Ok(format_table(&header, &rows, format));
 }
 
 #[cfg(test)]
-mod game_power_tests {
+mod games_report_tests {
     use super::*;
 
     #[test]
-    fn test_game_power() {
-        assert_eq!(game_power("Game 1: 3 blue, 5 green, 6 yellow; 4 red, 3 green, 7 yellow"), 420);
+    fn test_games_report_table() {
+        let report = games_report("data/day02/test.txt", &Context::default()).unwrap();
+        assert_eq!(report, "\
+id  rounds  max blue  max green  max red  min bag                   power  possible
+ 1       3         6          2        4  6 blue, 2 green, 4 red       48  yes
+ 2       3         4          3        1  4 blue, 3 green, 1 red       12  yes
+ 3       3         6         13       20  6 blue, 13 green, 20 red   1560  no
+ 4       3        15          3       14  15 blue, 3 green, 14 red    630  no
+ 5       2         2          3        6  2 blue, 3 green, 6 red       36  yes");
+    }
+
+    #[test]
+    fn test_games_report_csv() {
+        let context = Context::default().with_parameter("format", "csv").with_parameter("bag", "20 red, 13 green, 15 blue");
+        let report = games_report("data/day02/test.txt", &context).unwrap();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines[0], "id,rounds,max blue,max green,max red,min bag,power,possible");
+        assert_eq!(lines[3], "3,3,6,13,20,\"6 blue, 13 green, 20 red\",1560,yes");
     }
 }
 
-fn sum_possible_game_ids(filename: &str, max: &str) -> i32 {
-    return sum_up_with_rule(filename, possible_game_id, sum, max);
+fn median(sorted: &[u32]) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        return (sorted[middle - 1] as f64 + sorted[middle] as f64) / 2.0;
+    }
+    return sorted[middle] as f64;
 }
 
 #[cfg(test)]
-mod sum_possible_game_ids_tests {
+mod median_tests {
     use super::*;
 
     #[test]
-    fn test_sum_possible_game_ids_part1() {
-        assert_eq!(sum_possible_game_ids("data/day02/test.txt", "12 red, 13 green, 14 blue"), 8);
+    fn test_median() {
+        assert_eq!(median(&[]), 0.0);
+        assert_eq!(median(&[1, 4, 9]), 4.0);
+        assert_eq!(median(&[1, 4, 9, 10]), 6.5);
     }
 }
 
-fn sum_game_powers(filename: &str) -> i32 {
-    return sum_up(filename, game_power, sum);
+pub fn summary_report(filename: &str, context: &Context) -> Result<String, SolveError> {
+    let bag = bag_parameter(context)?;
+    let format = format_parameter(context)?;
+    let games = read_games(filename)?;
+    let rounds: Vec<Counts> = games.iter().flat_map(|game| &game.rounds).map(|round| round.counts().unwrap()).collect();
+    let header = ["colour", "rounds drawn", "total", "mean", "median", "max", "games needing more than bag"];
+    let mut rows = vec![];
+    for colour in file_colours(&games) {
+        let mut counts: Vec<u32> = rounds.iter().map(|round| *round.get(&colour).unwrap_or(&0)).collect();
+        counts.sort();
+        let total = checked_sum(counts.iter().map(|count| *count as u64))?;
+        let available = *bag.get(&colour).unwrap_or(&0);
+        let over_bag = games.iter().filter(|game| *get_min_for_game(game).get(&colour).unwrap_or(&0) > available).count();
+        rows.push(vec![
+            colour.to_string(),
+            counts.iter().filter(|count| **count > 0).count().to_string(),
+            total.to_string(),
+            format!("{:.2}", total as f64 / counts.len().max(1) as f64),
+            format!("{:.1}", median(&counts)),
+            counts.last().unwrap_or(&0).to_string(),
+            over_bag.to_string(),
+        ]);
+    }
+    let possible: Vec<&Game> = games.iter().filter(|game| is_game_possible(game, &bag)).collect();
+    let id_sum = checked_sum(possible.iter().map(|game| game.id as u64))?;
+    if format == TableFormat::Csv {
+        let totals_header = ["games", "rounds", "possible games", "bag", "possible id sum"];
+        let totals = vec![vec![
+            games.len().to_string(),
+            rounds.len().to_string(),
+            possible.len().to_string(),
+            Round::from_counts(&bag).to_string(),
+            id_sum.to_string(),
+        ]];
+        return Ok(format!("{}\n\n{}", format_table(&header, &rows, format), format_table(&totals_header, &totals, format)));
+    }
+    let footer = format!(
+        "{} games, {} rounds; {} possible under bag {} (id sum {})",
+        games.len(), rounds.len(), possible.len(), Round::from_counts(&bag), id_sum,
+    );
+    return Ok(format!("{}\n{}", format_table(&header, &rows, format), footer));
 }
 
 #[cfg(test)]
-mod sum_game_powers_tests {
+mod summary_report_tests {
     use super::*;
 
     #[test]
-    fn test_sum_game_powers_part2() {
-        assert_eq!(sum_game_powers("data/day02/test.txt"), 2286);
+    fn test_summary_report_table() {
+        let report = summary_report("data/day02/test.txt", &Context::default()).unwrap();
+        assert_eq!(report, "\
+colour  rounds drawn  total  mean  median  max  games needing more than bag
+blue              11     50  3.57     2.5   15                            1
+green             13     48  3.43     2.5   13                            0
+red               11     61  4.36     2.0   20                            2
+5 games, 14 rounds; 3 possible under bag 14 blue, 13 green, 12 red (id sum 8)");
+    }
+
+    fn parse_csv_record(line: &str) -> Vec<String> {
+        let mut fields = vec![String::new()];
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    fields.last_mut().unwrap().push('"');
+                    chars.next();
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => fields.push(String::new()),
+                _ => fields.last_mut().unwrap().push(c),
+            }
+        }
+        return fields;
+    }
+
+    fn parse_csv_sections(text: &str) -> Vec<Vec<Vec<String>>> {
+        return text.split("\n\n").map(|section| section.lines().map(parse_csv_record).collect()).collect();
+    }
+
+    #[test]
+    fn test_summary_report_csv() {
+        let context = Context::default().with_parameter("format", "csv");
+        let report = summary_report("data/day02/test.txt", &context).unwrap();
+        let sections = parse_csv_sections(&report);
+        assert_eq!(sections.len(), 2);
+        for section in &sections {
+            assert_eq!(section.iter().all(|record| record.len() == section[0].len()), true);
+        }
+        assert_eq!(sections[0].len(), 4);
+        assert_eq!(sections[0][0][0], "colour");
+        assert_eq!(sections[0][3], vec!["red", "11", "61", "4.36", "2.0", "20", "2"]);
+        assert_eq!(sections[1], vec![
+            vec!["games", "rounds", "possible games", "bag", "possible id sum"],
+            vec!["5", "14", "3", "14 blue, 13 green, 12 red", "8"],
+        ]);
     }
 }
 
-pub fn part1() {
-    println!("Day 2 Part 1 result: {}", sum_possible_game_ids("data/day02/input.txt", "12 red, 13 green, 14 blue"));
+pub fn part1(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    let bag = bag_parameter(context)?;
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(sum_possible_game_ids::<i64>(filename, &bag)?),
+        Arithmetic::Big => Answer::from(sum_possible_game_ids::<BigInt>(filename, &bag)?),
+    });
 }
 
-pub fn part2() {
-    println!("Day 2 Part 2 result: {}", sum_game_powers("data/day02/input.txt"));
+pub fn part2(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(sum_game_powers::<i64>(filename)?),
+        Arithmetic::Big => Answer::from(sum_game_powers::<BigInt>(filename)?),
+    });
 }
@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+use crate::aoc_common::pattern::Pattern;
+
+static GAME_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern::new("Game {id}: {rounds}").unwrap());
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseGameError {
+    pub message: String,
+}
+
+impl fmt::Display for ParseGameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}", self.message);
+    }
+}
+
+impl std::error::Error for ParseGameError {}
+
+fn parse_error(message: String) -> ParseGameError {
+    return ParseGameError { message };
+}
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Colour(String);
+
+impl fmt::Display for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}", self.0);
+    }
+}
+
+impl FromStr for Colour {
+    type Err = ParseGameError;
+
+    fn from_str(text: &str) -> Result<Colour, ParseGameError> {
+        if text.is_empty() || !text.chars().all(|c| c.is_alphabetic() || c == '-') {
+            return Err(parse_error(format!("invalid colour \"{}\"", text)));
+        }
+        return Ok(Colour(text.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod colour_tests {
+    use super::*;
+
+    #[test]
+    fn test_colour_from_str() {
+        assert_eq!("red".parse::<Colour>(), Ok(Colour("red".to_string())));
+        assert_eq!("sky-blue".parse::<Colour>().unwrap().to_string(), "sky-blue");
+    }
+
+    #[test]
+    fn test_colour_from_str_invalid() {
+        assert_eq!("".parse::<Colour>().is_err(), true);
+        assert_eq!("red,".parse::<Colour>(), Err(ParseGameError { message: "invalid colour \"red,\"".to_string() }));
+    }
+}
+
+pub type Counts = BTreeMap<Colour, u32>;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Draw {
+    pub count: u32,
+    pub colour: Colour,
+}
+
+impl fmt::Display for Draw {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{} {}", self.count, self.colour);
+    }
+}
+
+impl FromStr for Draw {
+    type Err = ParseGameError;
+
+    fn from_str(text: &str) -> Result<Draw, ParseGameError> {
+        let words: Vec<&str> = text.split(' ').collect();
+        if words.len() != 2 {
+            return Err(parse_error(format!("expected \"<count> <colour>\", found \"{}\"", text)));
+        }
+        let count = words[0].parse::<u32>()
+            .map_err(|_| parse_error(format!("invalid count \"{}\" in \"{}\"", words[0], text)))?;
+        return Ok(Draw { count, colour: words[1].parse()? });
+    }
+}
+
+#[cfg(test)]
+mod draw_tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_from_str() {
+        let draw = "3 blue".parse::<Draw>().unwrap();
+        assert_eq!(draw.count, 3);
+        assert_eq!(draw.colour.to_string(), "blue");
+        assert_eq!(draw.to_string(), "3 blue");
+    }
+
+    #[test]
+    fn test_draw_from_str_invalid() {
+        assert_eq!("x red".parse::<Draw>(), Err(ParseGameError { message: "invalid count \"x\" in \"x red\"".to_string() }));
+        assert_eq!("-1 red".parse::<Draw>().is_err(), true);
+        assert_eq!("12".parse::<Draw>(), Err(ParseGameError { message: "expected \"<count> <colour>\", found \"12\"".to_string() }));
+        assert_eq!("1 red 2".parse::<Draw>().is_err(), true);
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Round {
+    pub draws: Vec<Draw>,
+}
+
+impl Round {
+    pub fn from_counts(counts: &Counts) -> Round {
+        let draws = counts.iter().map(|(colour, count)| Draw { count: *count, colour: colour.clone() }).collect();
+        return Round { draws };
+    }
+
+    pub fn counts(&self) -> Result<Counts, ParseGameError> {
+        let mut counts = Counts::new();
+        for draw in &self.draws {
+            let count = counts.entry(draw.colour.clone()).or_insert(0);
+            *count = count.checked_add(draw.count)
+                .ok_or_else(|| parse_error(format!("too many {} cubes in \"{}\"", draw.colour, self)))?;
+        }
+        return Ok(counts);
+    }
+}
+
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let draws: Vec<String> = self.draws.iter().map(|draw| draw.to_string()).collect();
+        return write!(f, "{}", draws.join(", "));
+    }
+}
+
+impl FromStr for Round {
+    type Err = ParseGameError;
+
+    fn from_str(text: &str) -> Result<Round, ParseGameError> {
+        if text.is_empty() {
+            return Ok(Round::default());
+        }
+        let draws = text.split(", ").map(str::parse).collect::<Result<Vec<Draw>, ParseGameError>>()?;
+        let round = Round { draws };
+        round.counts()?;
+        return Ok(round);
+    }
+}
+
+#[cfg(test)]
+mod round_tests {
+    use super::*;
+
+    #[test]
+    fn test_round_from_str() {
+        let round = "1 red, 2 green".parse::<Round>().unwrap();
+        assert_eq!(round.draws.len(), 2);
+        assert_eq!(round.to_string(), "1 red, 2 green");
+    }
+
+    #[test]
+    fn test_round_from_str_empty() {
+        assert_eq!("".parse::<Round>(), Ok(Round::default()));
+    }
+
+    #[test]
+    fn test_round_counts_sum_repeated_colours() {
+        let counts = "1 red, 2 green, 7 red".parse::<Round>().unwrap().counts().unwrap();
+        assert_eq!(counts[&"red".parse().unwrap()], 8);
+        assert_eq!(counts[&"green".parse().unwrap()], 2);
+    }
+
+    #[test]
+    fn test_round_from_counts() {
+        let counts = "4 red, 2 blue, 1 red".parse::<Round>().unwrap().counts().unwrap();
+        assert_eq!(Round::from_counts(&counts).to_string(), "2 blue, 5 red");
+    }
+
+    #[test]
+    fn test_round_from_str_invalid() {
+        assert_eq!("12 red,13 green".parse::<Round>().is_err(), true);
+        assert_eq!("12 red, 13".parse::<Round>().is_err(), true);
+    }
+
+    #[test]
+    fn test_round_counts_overflow() {
+        let round = Round { draws: vec!["4000000000 red".parse().unwrap(), "4000000000 red".parse().unwrap()] };
+        let error = ParseGameError { message: "too many red cubes in \"4000000000 red, 4000000000 red\"".to_string() };
+        assert_eq!(round.counts(), Err(error.clone()));
+        assert_eq!("4000000000 red, 4000000000 red".parse::<Round>(), Err(error));
+        assert_eq!("4000000000 red, 4000000000 blue".parse::<Round>().is_ok(), true);
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Round>,
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rounds: Vec<String> = self.rounds.iter().map(|round| round.to_string()).collect();
+        return write!(f, "Game {}: {}", self.id, rounds.join("; "));
+    }
+}
+
+impl FromStr for Game {
+    type Err = ParseGameError;
+
+    fn from_str(text: &str) -> Result<Game, ParseGameError> {
+        let captures = GAME_PATTERN.parse(text).map_err(|error| parse_error(error.to_string()))?;
+        let id = captures.parse::<u32>("id").map_err(|error| parse_error(error.to_string()))?;
+        let rounds = captures.get("rounds").unwrap();
+        let rounds = if rounds.is_empty() {
+            vec![]
+        } else {
+            rounds.split("; ").enumerate()
+                .map(|(index, round)| round.parse().map_err(|error| parse_error(format!("Game {}, round {}: {}", id, index + 1, error))))
+                .collect::<Result<Vec<Round>, ParseGameError>>()?
+        };
+        return Ok(Game { id, rounds });
+    }
+}
+
+#[cfg(test)]
+mod game_tests {
+    use super::*;
+
+    const GAME: &str = "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red";
+
+    #[test]
+    fn test_game_from_str() {
+        let game = GAME.parse::<Game>().unwrap();
+        assert_eq!(game.id, 4);
+        assert_eq!(game.rounds.len(), 3);
+        assert_eq!(game.rounds[2].draws[1], Draw { count: 15, colour: "blue".parse().unwrap() });
+    }
+
+    #[test]
+    fn test_game_display_round_trip() {
+        assert_eq!(GAME.parse::<Game>().unwrap().to_string(), GAME);
+        assert_eq!("Game 7: ".parse::<Game>().unwrap().to_string(), "Game 7: ");
+    }
+
+    #[test]
+    fn test_game_from_str_no_rounds() {
+        assert_eq!("Game 4: ".parse::<Game>(), Ok(Game { id: 4, rounds: vec![] }));
+    }
+
+    #[test]
+    fn test_game_from_str_invalid() {
+        assert_eq!("5 red, 6 green".parse::<Game>(), Err(ParseGameError { message: "column 1: expected \"Game \"".to_string() }));
+        assert_eq!("Game x: 1 red".parse::<Game>(), Err(ParseGameError { message: "column 6: invalid value \"x\" for {id}".to_string() }));
+        assert_eq!("Game 1: x red".parse::<Game>(), Err(ParseGameError { message: "Game 1, round 1: invalid count \"x\" in \"x red\"".to_string() }));
+    }
+
+    #[test]
+    fn test_game_from_str_count_overflow() {
+        assert_eq!("Game 1: 4000000000 red, 4000000000 red".parse::<Game>(),
+            Err(ParseGameError { message: "Game 1, round 1: too many red cubes in \"4000000000 red, 4000000000 red\"".to_string() }));
+        assert_eq!("Game 3: 1 red; 2 blue, 4294967295 blue".parse::<Game>().unwrap_err().message.starts_with("Game 3, round 2: "), true);
+    }
+}
@@ -0,0 +1,277 @@
+use super::game::Counts;
+
+pub fn ln_choose(n: u64, k: u64) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    let k = k.min(n - k);
+    let mut result = 0.0;
+    for i in 0..k {
+        result += ((n - i) as f64).ln() - ((i + 1) as f64).ln();
+    }
+    return result;
+}
+
+#[cfg(test)]
+mod ln_choose_tests {
+    use super::*;
+
+    #[test]
+    fn test_ln_choose() {
+        assert_eq!(ln_choose(5, 0), 0.0);
+        assert_eq!((ln_choose(5, 2).exp() - 10.0).abs() < 1e-9, true);
+        assert_eq!((ln_choose(52, 5).exp() - 2598960.0).abs() < 1e-3, true);
+    }
+
+    #[test]
+    fn test_ln_choose_more_than_available() {
+        assert_eq!(ln_choose(2, 3), f64::NEG_INFINITY);
+    }
+}
+
+pub fn ln_choose_step(n: u64, k: u64) -> f64 {
+    if k > n {
+        return f64::INFINITY;
+    }
+    return ((n + 1) as f64).ln() - ((n + 1 - k) as f64).ln();
+}
+
+#[cfg(test)]
+mod ln_choose_step_tests {
+    use super::*;
+
+    #[test]
+    fn test_ln_choose_step() {
+        assert_eq!(ln_choose_step(5, 0), 0.0);
+        assert_eq!((ln_choose_step(5, 2) - (ln_choose(6, 2) - ln_choose(5, 2))).abs() < 1e-9, true);
+        assert_eq!((ln_choose_step(40, 7) - (ln_choose(41, 7) - ln_choose(40, 7))).abs() < 1e-9, true);
+    }
+
+    #[test]
+    fn test_ln_choose_step_from_too_few() {
+        assert_eq!(ln_choose_step(2, 3), f64::INFINITY);
+    }
+}
+
+pub fn round_log_probability(round: &Counts, bag: &Counts) -> f64 {
+    let mut log_probability = 0.0;
+    let mut drawn = 0;
+    for (colour, count) in round {
+        log_probability += ln_choose(*bag.get(colour).unwrap_or(&0) as u64, *count as u64);
+        drawn += *count as u64;
+    }
+    let total = bag.values().map(|count| *count as u64).sum();
+    if drawn > total || log_probability == f64::NEG_INFINITY {
+        return f64::NEG_INFINITY;
+    }
+    return log_probability - ln_choose(total, drawn);
+}
+
+#[cfg(test)]
+mod round_log_probability_tests {
+    use super::*;
+    use crate::day02::game::Round;
+
+    fn probability(round: &str, bag: &str) -> f64 {
+        let counts = |text: &str| text.parse::<Round>().unwrap().counts().unwrap();
+        return round_log_probability(&counts(round), &counts(bag)).exp();
+    }
+
+    #[test]
+    fn test_round_log_probability() {
+        assert_eq!((probability("1 red", "1 red, 1 blue") - 0.5).abs() < 1e-9, true);
+        assert_eq!((probability("2 red, 1 blue", "2 red, 2 blue") - 0.5).abs() < 1e-9, true);
+        assert_eq!((probability("1 red, 1 green", "2 red, 1 green, 3 blue") - 2.0 / 15.0).abs() < 1e-9, true);
+    }
+
+    #[test]
+    fn test_round_log_probability_empty_round() {
+        assert_eq!(probability("", "2 red"), 1.0);
+    }
+
+    #[test]
+    fn test_round_log_probability_impossible() {
+        assert_eq!(probability("3 red", "2 red, 5 blue"), 0.0);
+        assert_eq!(probability("1 purple", "2 red"), 0.0);
+        assert_eq!(probability("1 red", ""), 0.0);
+    }
+}
+
+pub fn log_likelihood(rounds: &[Counts], bag: &Counts) -> f64 {
+    return rounds.iter().map(|round| round_log_probability(round, bag)).sum();
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Estimate {
+    pub bag: Counts,
+    pub log_likelihood: f64,
+    pub at_limit: bool,
+}
+
+struct Sample {
+    counts: Vec<u32>,
+    drawn: u64,
+    possible: bool,
+}
+
+fn sample_log_likelihood(samples: &[Sample], bag: &[u32]) -> f64 {
+    let size = bag.iter().map(|count| *count as u64).sum();
+    let mut total = 0.0;
+    for sample in samples {
+        if !sample.possible || sample.drawn > size {
+            return f64::NEG_INFINITY;
+        }
+        for (count, available) in sample.counts.iter().zip(bag) {
+            total += ln_choose(*available as u64, *count as u64);
+        }
+        total -= ln_choose(size, sample.drawn);
+    }
+    return total;
+}
+
+pub fn estimate_bag(rounds: &[Counts], minimum: &Counts, max_size: u64) -> Estimate {
+    let colours: Vec<_> = minimum.keys().cloned().collect();
+    let samples: Vec<Sample> = rounds.iter().map(|round| Sample {
+        counts: colours.iter().map(|colour| *round.get(colour).unwrap_or(&0)).collect(),
+        drawn: round.values().map(|count| *count as u64).sum(),
+        possible: round.keys().all(|colour| minimum.contains_key(colour)),
+    }).collect();
+    let floor: Vec<u32> = minimum.values().enumerate()
+        .map(|(index, count)| samples.iter().map(|sample| sample.counts[index]).fold(*count, u32::max))
+        .collect();
+    let mut bag = floor.clone();
+    let mut best_bag = floor;
+    let mut size: u64 = bag.iter().map(|count| *count as u64).sum();
+    let mut likelihood = sample_log_likelihood(&samples, &bag);
+    let mut best = likelihood;
+    while likelihood > f64::NEG_INFINITY && size < max_size && !bag.is_empty() {
+        let mut chosen = 0;
+        let mut chosen_gain = f64::NEG_INFINITY;
+        for (index, available) in bag.iter().enumerate() {
+            let gain: f64 = samples.iter().map(|sample| ln_choose_step(*available as u64, sample.counts[index] as u64)).sum();
+            if gain > chosen_gain + 1e-12 {
+                chosen = index;
+                chosen_gain = gain;
+            }
+        }
+        let size_cost: f64 = samples.iter().map(|sample| ln_choose_step(size, sample.drawn)).sum();
+        bag[chosen] += 1;
+        size += 1;
+        likelihood += chosen_gain - size_cost;
+        if likelihood > best + 1e-12 {
+            best = likelihood;
+            best_bag = bag.clone();
+        }
+    }
+    let at_limit = best_bag.iter().map(|count| *count as u64).sum::<u64>() >= max_size;
+    let log_likelihood = sample_log_likelihood(&samples, &best_bag);
+    let bag = colours.into_iter().zip(best_bag).collect();
+    return Estimate { bag, log_likelihood, at_limit };
+}
+
+#[cfg(test)]
+mod estimate_bag_tests {
+    use super::*;
+    use crate::day02::game::{Colour, Round};
+
+    fn counts(text: &str) -> Counts {
+        return text.parse::<Round>().unwrap().counts().unwrap();
+    }
+
+    #[test]
+    fn test_estimate_bag_keeps_minimum_when_it_fits_best() {
+        let rounds = vec![counts("1 red"), counts("1 blue")];
+        let estimate = estimate_bag(&rounds, &counts("1 red, 1 blue"), 100);
+        assert_eq!(estimate.bag, counts("1 red, 1 blue"));
+        assert_eq!((estimate.log_likelihood - 0.25f64.ln()).abs() < 1e-9, true);
+        assert_eq!(estimate.at_limit, false);
+    }
+
+    #[test]
+    fn test_estimate_bag_grows_beyond_minimum() {
+        let rounds = vec![counts("2 red"), counts("2 blue"), counts("2 red, 1 blue")];
+        let minimum = counts("2 red, 2 blue");
+        let estimate = estimate_bag(&rounds, &minimum, 100);
+        assert_eq!(estimate.bag, counts("57 red, 43 blue"));
+        assert_eq!(estimate.log_likelihood > log_likelihood(&rounds, &counts("3 red, 2 blue")), true);
+        assert_eq!(estimate.at_limit, true);
+        let estimate = estimate_bag(&[counts("2 red, 2 blue"), counts("3 red, 1 blue"), counts("4 red")], &counts("4 red, 2 blue"), 100);
+        assert_eq!(estimate.bag, counts("15 red, 5 blue"));
+        assert_eq!(estimate.at_limit, false);
+    }
+
+    #[test]
+    fn test_estimate_bag_stops_at_size_limit() {
+        let rounds = vec![counts("3 red"), counts("3 blue"), counts("2 red, 2 blue")];
+        let estimate = estimate_bag(&rounds, &counts("3 red, 3 blue"), 10);
+        assert_eq!(estimate.bag.values().sum::<u32>(), 10);
+        assert_eq!(estimate.at_limit, true);
+    }
+
+    #[test]
+    fn test_estimate_bag_matches_round_probabilities() {
+        let rounds = vec![counts("3 red, 1 blue"), counts("2 blue, 1 red")];
+        let estimate = estimate_bag(&rounds, &counts("3 red, 2 blue"), 100);
+        assert_eq!((estimate.log_likelihood - log_likelihood(&rounds, &estimate.bag)).abs() < 1e-9, true);
+    }
+
+    fn best_bag_by_enumeration(rounds: &[Counts], bag: &mut Counts, colours: &[Colour], spare: u32, best: &mut Option<(Counts, f64)>) {
+        if colours.is_empty() {
+            let likelihood = log_likelihood(rounds, bag);
+            if best.as_ref().is_none_or(|(_, best)| likelihood > best + 1e-12) {
+                *best = Some((bag.clone(), likelihood));
+            }
+            return;
+        }
+        let floor = bag[&colours[0]];
+        for extra in 0..=spare {
+            bag.insert(colours[0].clone(), floor + extra);
+            best_bag_by_enumeration(rounds, bag, &colours[1..], spare - extra, best);
+        }
+        bag.insert(colours[0].clone(), floor);
+    }
+
+    #[test]
+    fn test_estimate_bag_finds_global_optimum() {
+        let rounds = vec![counts("3 blue, 3 red"), counts("3 blue, 1 red"), counts("3 red"), counts("3 red")];
+        let minimum = counts("3 blue, 3 red");
+        let estimate = estimate_bag(&rounds, &minimum, 12);
+        assert_eq!(log_likelihood(&rounds, &counts("3 blue, 4 red")) > log_likelihood(&rounds, &counts("4 blue, 4 red")), true);
+        assert_eq!(log_likelihood(&rounds, &counts("3 blue, 4 red")) > log_likelihood(&rounds, &counts("3 blue, 5 red")), true);
+        assert_eq!(estimate.bag, counts("5 blue, 7 red"));
+        assert_eq!(estimate.log_likelihood > log_likelihood(&rounds, &counts("3 blue, 4 red")), true);
+    }
+
+    #[test]
+    fn test_estimate_bag_matches_enumeration() {
+        let cases = [
+            (vec!["3 blue, 3 red", "3 blue, 1 red", "3 red", "3 red"], 12),
+            (vec!["2 red, 1 green", "1 blue, 2 green", "3 red"], 14),
+            (vec!["1 red, 1 green, 1 blue", "2 green", "4 blue, 1 red"], 15),
+            (vec!["2 red", "2 blue", "2 red, 1 blue"], 9),
+        ];
+        for (rounds, max_size) in cases {
+            let rounds: Vec<Counts> = rounds.into_iter().map(counts).collect();
+            let mut minimum = Counts::new();
+            for round in &rounds {
+                for (colour, count) in round {
+                    let entry = minimum.entry(colour.clone()).or_insert(0);
+                    *entry = (*entry).max(*count);
+                }
+            }
+            let estimate = estimate_bag(&rounds, &minimum, max_size as u64);
+            let colours: Vec<Colour> = minimum.keys().cloned().collect();
+            let mut best = None;
+            best_bag_by_enumeration(&rounds, &mut minimum.clone(), &colours, max_size - minimum.values().sum::<u32>(), &mut best);
+            let (expected, likelihood) = best.unwrap();
+            assert_eq!(estimate.bag, expected, "{:?}", rounds);
+            assert_eq!((estimate.log_likelihood - likelihood).abs() < 1e-9, true, "{:?}", rounds);
+        }
+    }
+
+    #[test]
+    fn test_estimate_bag_no_rounds() {
+        let estimate = estimate_bag(&[], &Counts::new(), 10);
+        assert_eq!(estimate, Estimate { bag: Counts::new(), log_likelihood: 0.0, at_limit: false });
+    }
+}
@@ -1 +1,4 @@
 pub mod day02;
+pub mod game;
+pub mod query;
+pub mod inference;
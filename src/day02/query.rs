@@ -0,0 +1,434 @@
+use std::fmt;
+use std::str::FromStr;
+use super::game::Colour;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueryError {
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "column {}: {}", self.column + 1, self.message);
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    pub fn holds(&self, left: i64, right: i64) -> bool {
+        return match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+        };
+    }
+
+    pub fn negated(&self) -> Comparison {
+        return match self {
+            Comparison::Less => Comparison::GreaterOrEqual,
+            Comparison::LessOrEqual => Comparison::Greater,
+            Comparison::Greater => Comparison::LessOrEqual,
+            Comparison::GreaterOrEqual => Comparison::Less,
+            Comparison::Equal => Comparison::NotEqual,
+            Comparison::NotEqual => Comparison::Equal,
+        };
+    }
+
+    fn symbol(&self) -> &'static str {
+        return match self {
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Equal => "==",
+            Comparison::NotEqual => "!=",
+        };
+    }
+}
+
+#[cfg(test)]
+mod comparison_tests {
+    use super::*;
+
+    #[test]
+    fn test_holds() {
+        assert_eq!(Comparison::Less.holds(1, 2), true);
+        assert_eq!(Comparison::LessOrEqual.holds(2, 2), true);
+        assert_eq!(Comparison::Greater.holds(2, 2), false);
+        assert_eq!(Comparison::GreaterOrEqual.holds(1, 2), false);
+        assert_eq!(Comparison::Equal.holds(3, 3), true);
+        assert_eq!(Comparison::NotEqual.holds(3, 3), false);
+    }
+
+    #[test]
+    fn test_negated() {
+        for comparison in [Comparison::Less, Comparison::LessOrEqual, Comparison::Greater, Comparison::GreaterOrEqual, Comparison::Equal, Comparison::NotEqual] {
+            for (left, right) in [(1, 2), (2, 2), (3, 2)] {
+                assert_eq!(comparison.negated().holds(left, right), !comparison.holds(left, right));
+            }
+            assert_eq!(comparison.negated().negated(), comparison);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Aggregate {
+    Max,
+    Min,
+    Sum,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Value {
+    Number(i64),
+    Count(Colour),
+    Aggregate(Aggregate, Colour),
+    Rounds,
+}
+
+impl Value {
+    pub fn is_per_round(&self) -> bool {
+        return matches!(self, Value::Count(_));
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Query {
+    Compare(Value, Comparison, Value),
+    Not(Box<Query>),
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+}
+
+impl Query {
+    pub fn negated(&self) -> Query {
+        return match self {
+            Query::Compare(left, comparison, right) => Query::Compare(left.clone(), comparison.negated(), right.clone()),
+            Query::Not(inner) => *inner.clone(),
+            Query::And(left, right) => Query::Or(Box::new(left.negated()), Box::new(right.negated())),
+            Query::Or(left, right) => Query::And(Box::new(left.negated()), Box::new(right.negated())),
+        };
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Token {
+    Number(i64),
+    Word(String),
+    Compare(Comparison),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+    End,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            Token::Number(number) => write!(f, "{}", number),
+            Token::Word(word) => write!(f, "\"{}\"", word),
+            Token::Compare(comparison) => write!(f, "\"{}\"", comparison.symbol()),
+            Token::And => write!(f, "\"&&\""),
+            Token::Or => write!(f, "\"||\""),
+            Token::Not => write!(f, "\"!\""),
+            Token::Open => write!(f, "\"(\""),
+            Token::Close => write!(f, "\")\""),
+            Token::End => write!(f, "end of query"),
+        };
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, QueryError> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().enumerate().peekable();
+    while let Some((column, chr)) = chars.next() {
+        let next = chars.peek().map(|(_, next)| *next);
+        let two_chars = matches!((chr, next), ('&', Some('&')) | ('|', Some('|')) | ('<' | '>' | '=' | '!', Some('=')));
+        if two_chars {
+            chars.next();
+        }
+        let token = match (chr, two_chars) {
+            (' ' | '\t', _) => continue,
+            ('(', _) => Token::Open,
+            (')', _) => Token::Close,
+            ('&', true) => Token::And,
+            ('|', true) => Token::Or,
+            ('<', true) => Token::Compare(Comparison::LessOrEqual),
+            ('>', true) => Token::Compare(Comparison::GreaterOrEqual),
+            ('=', true) => Token::Compare(Comparison::Equal),
+            ('!', true) => Token::Compare(Comparison::NotEqual),
+            ('<', false) => Token::Compare(Comparison::Less),
+            ('>', false) => Token::Compare(Comparison::Greater),
+            ('!', false) => Token::Not,
+            _ if chr.is_ascii_digit() => {
+                let mut digits = chr.to_string();
+                while let Some((_, digit)) = chars.next_if(|(_, next)| next.is_ascii_digit()) {
+                    digits.push(digit);
+                }
+                let number = digits.parse()
+                    .map_err(|_| QueryError { column, message: format!("number {} is too large", digits) })?;
+                Token::Number(number)
+            }
+            _ if chr.is_alphabetic() => {
+                let mut word = chr.to_string();
+                while let Some((_, letter)) = chars.next_if(|(_, next)| next.is_alphabetic() || *next == '-') {
+                    word.push(letter);
+                }
+                Token::Word(word)
+            }
+            _ => return Err(QueryError { column, message: format!("unexpected character '{}'", chr) }),
+        };
+        tokens.push((column, token));
+    }
+    tokens.push((text.chars().count(), Token::End));
+    return Ok(tokens);
+}
+
+#[cfg(test)]
+mod tokenize_tests {
+    use super::*;
+
+    fn tokens(text: &str) -> Vec<Token> {
+        return tokenize(text).unwrap().into_iter().map(|(_, token)| token).collect();
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(tokens("!(sky-blue>=12)||red"), vec![
+            Token::Not, Token::Open, Token::Word("sky-blue".to_string()), Token::Compare(Comparison::GreaterOrEqual),
+            Token::Number(12), Token::Close, Token::Or, Token::Word("red".to_string()), Token::End,
+        ]);
+    }
+
+    #[test]
+    fn test_tokenize_columns() {
+        let columns: Vec<usize> = tokenize("red != 3").unwrap().into_iter().map(|(column, _)| column).collect();
+        assert_eq!(columns, vec![0, 4, 7, 8]);
+    }
+
+    #[test]
+    fn test_tokenize_columns_count_characters() {
+        let columns: Vec<usize> = tokenize("grün > 1").unwrap().into_iter().map(|(column, _)| column).collect();
+        assert_eq!(columns, vec![0, 5, 7, 8]);
+        assert_eq!(tokenize("grün ? 1"), Err(QueryError { column: 5, message: "unexpected character '?'".to_string() }));
+    }
+
+    #[test]
+    fn test_tokenize_errors() {
+        assert_eq!(tokenize("red & blue"), Err(QueryError { column: 4, message: "unexpected character '&'".to_string() }));
+        assert_eq!(tokenize("red = 1"), Err(QueryError { column: 4, message: "unexpected character '='".to_string() }));
+        assert_eq!(tokenize("red < 99999999999999999999").is_err(), true);
+    }
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        return &self.tokens[self.position].1;
+    }
+
+    fn column(&self) -> usize {
+        return self.tokens[self.position].0;
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.position].1.clone();
+        if self.position + 1 < self.tokens.len() {
+            self.position += 1;
+        }
+        return token;
+    }
+
+    fn unexpected(&self, expected: &str) -> QueryError {
+        return QueryError { column: self.column(), message: format!("expected {}, found {}", expected, self.peek()) };
+    }
+
+    fn close(&mut self) -> Result<(), QueryError> {
+        if *self.peek() != Token::Close {
+            return Err(self.unexpected("\")\""));
+        }
+        self.advance();
+        return Ok(());
+    }
+
+    fn or(&mut self) -> Result<Query, QueryError> {
+        let mut query = self.and()?;
+        while *self.peek() == Token::Or {
+            self.advance();
+            query = Query::Or(Box::new(query), Box::new(self.and()?));
+        }
+        return Ok(query);
+    }
+
+    fn and(&mut self) -> Result<Query, QueryError> {
+        let mut query = self.unary()?;
+        while *self.peek() == Token::And {
+            self.advance();
+            query = Query::And(Box::new(query), Box::new(self.unary()?));
+        }
+        return Ok(query);
+    }
+
+    fn unary(&mut self) -> Result<Query, QueryError> {
+        return match self.peek() {
+            Token::Not => {
+                self.advance();
+                Ok(Query::Not(Box::new(self.unary()?)))
+            }
+            Token::Open => {
+                self.advance();
+                let query = self.or()?;
+                self.close()?;
+                Ok(query)
+            }
+            _ => self.comparison(),
+        };
+    }
+
+    fn comparison(&mut self) -> Result<Query, QueryError> {
+        let left = self.value()?;
+        let comparison = match self.peek() {
+            Token::Compare(comparison) => *comparison,
+            _ => return Err(self.unexpected("a comparison")),
+        };
+        self.advance();
+        return Ok(Query::Compare(left, comparison, self.value()?));
+    }
+
+    fn colour(&mut self) -> Result<Colour, QueryError> {
+        let column = self.column();
+        return match self.peek().clone() {
+            Token::Word(word) => {
+                self.advance();
+                word.parse().map_err(|error| QueryError { column, message: format!("{}", error) })
+            }
+            _ => Err(self.unexpected("a colour")),
+        };
+    }
+
+    fn value(&mut self) -> Result<Value, QueryError> {
+        let column = self.column();
+        let word = match self.peek().clone() {
+            Token::Number(number) => {
+                self.advance();
+                return Ok(Value::Number(number));
+            }
+            Token::Word(word) => word,
+            _ => return Err(self.unexpected("a colour, number, rounds or max/min/sum(colour)")),
+        };
+        if self.tokens[self.position + 1].1 != Token::Open {
+            if word == "rounds" {
+                self.advance();
+                return Ok(Value::Rounds);
+            }
+            return Ok(Value::Count(self.colour()?));
+        }
+        let aggregate = match word.as_str() {
+            "max" => Aggregate::Max,
+            "min" => Aggregate::Min,
+            "sum" => Aggregate::Sum,
+            _ => return Err(QueryError { column, message: format!("unknown function \"{}\" (expected max, min or sum)", word) }),
+        };
+        self.advance();
+        self.advance();
+        let colour = self.colour()?;
+        self.close()?;
+        return Ok(Value::Aggregate(aggregate, colour));
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(text: &str) -> Result<Query, QueryError> {
+        let mut parser = Parser { tokens: tokenize(text)?, position: 0 };
+        let query = parser.or()?;
+        if *parser.peek() != Token::End {
+            return Err(parser.unexpected("\"&&\", \"||\" or end of query"));
+        }
+        return Ok(query);
+    }
+}
+
+#[cfg(test)]
+mod query_tests {
+    use super::*;
+
+    fn colour(name: &str) -> Colour {
+        return name.parse().unwrap();
+    }
+
+    fn compare(left: Value, comparison: Comparison, right: Value) -> Box<Query> {
+        return Box::new(Query::Compare(left, comparison, right));
+    }
+
+    #[test]
+    fn test_parse_comparisons() {
+        assert_eq!("max(green) >= 5".parse(), Ok(Query::Compare(
+            Value::Aggregate(Aggregate::Max, colour("green")), Comparison::GreaterOrEqual, Value::Number(5),
+        )));
+        assert_eq!("rounds>=3".parse(), Ok(Query::Compare(Value::Rounds, Comparison::GreaterOrEqual, Value::Number(3))));
+        assert_eq!("sum( red ) == min(blue)".parse(), Ok(Query::Compare(
+            Value::Aggregate(Aggregate::Sum, colour("red")), Comparison::Equal, Value::Aggregate(Aggregate::Min, colour("blue")),
+        )));
+    }
+
+    #[test]
+    fn test_parse_and_binds_tighter_than_or() {
+        assert_eq!("red <= 12 && blue > green || rounds < 2".parse(), Ok(Query::Or(
+            Box::new(Query::And(
+                compare(Value::Count(colour("red")), Comparison::LessOrEqual, Value::Number(12)),
+                compare(Value::Count(colour("blue")), Comparison::Greater, Value::Count(colour("green"))),
+            )),
+            compare(Value::Rounds, Comparison::Less, Value::Number(2)),
+        )));
+    }
+
+    #[test]
+    fn test_parse_not_and_parentheses() {
+        assert_eq!("!(red < 1 || blue < 1)".parse(), Ok(Query::Not(Box::new(Query::Or(
+            compare(Value::Count(colour("red")), Comparison::Less, Value::Number(1)),
+            compare(Value::Count(colour("blue")), Comparison::Less, Value::Number(1)),
+        )))));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |text: &str| text.parse::<Query>().unwrap_err().to_string();
+        assert_eq!(error(""), "column 1: expected a colour, number, rounds or max/min/sum(colour), found end of query");
+        assert_eq!(error("red"), "column 4: expected a comparison, found end of query");
+        assert_eq!(error("red <= 12 blue"), "column 11: expected \"&&\", \"||\" or end of query, found \"blue\"");
+        assert_eq!(error("avg(red) > 1"), "column 1: unknown function \"avg\" (expected max, min or sum)");
+        assert_eq!(error("max(3) > 1"), "column 5: expected a colour, found 3");
+        assert_eq!(error("(red > 1"), "column 9: expected \")\", found end of query");
+        assert_eq!(error("größe <"), "column 8: expected a colour, number, rounds or max/min/sum(colour), found end of query");
+    }
+
+    #[test]
+    fn test_negated() {
+        assert_eq!("!(red > 5 && rounds < 2)".parse::<Query>().unwrap().negated(), "red > 5 && rounds < 2".parse().unwrap());
+        assert_eq!("red > 5 && rounds < 2".parse::<Query>().unwrap().negated(), "red <= 5 || rounds >= 2".parse().unwrap());
+        assert_eq!("red == blue || !(sum(red) != 3)".parse::<Query>().unwrap().negated(), "red != blue && sum(red) != 3".parse().unwrap());
+    }
+}
@@ -1,7 +1,22 @@
+use std::collections::HashMap;
+use std::{fs, io};
+use num::BigInt;
+use crate::aoc_common::answer::Answer;
+use crate::aoc_common::bench::{measure, Measurement};
+use crate::aoc_common::checked::{checked_add, checked_append_digit, checked_product, checked_sum, Accumulator, Arithmetic, OverflowError};
+use crate::aoc_common::context::{Context, SolveError};
 use crate::aoc_common::lib::line_iterator;
+use crate::aoc_common::render::{write_image, Colour};
+use crate::{debug, warn};
 
-struct Number {
-    value: i32,
+pub const PARAMETERS: &[&str] = &["gear_symbols", "gear_numbers", "gear_score", "adjacency"];
+
+const BENCHMARK_WIDTH: usize = 140;
+const BENCHMARK_SIZES: [usize;3] = [140, 1_400, 14_000];
+const MAX_SCAN_SIZE: usize = 1_400;
+
+struct Number<T> {
+    value: T,
     line_number: i32,
     column_number_start: i32,
     column_number_end: i32,
@@ -63,22 +78,22 @@ mod find_symbols_in_line_tests {
     }
 }
 
-fn find_numbers_in_line(source: &str, line: i32) -> Vec<Number> {
-    let mut numbers: Vec<Number> = vec![];
-    let mut number = 0;
+fn find_numbers_in_line<T: Accumulator + From<u8>>(source: &str, line: i32) -> Result<Vec<Number<T>>, OverflowError> {
+    let mut numbers: Vec<Number<T>> = vec![];
+    let mut number = T::zero();
     let mut column_number = 0;
     let mut column_number_start = -1;
     for value in source.chars() {
         if value >= '0' && value <= '9' {
-            let digit = value as i32 - 48;
+            let digit = value as u8 - b'0';
             if column_number_start < 0 {
                 column_number_start = column_number;
-                number = digit;
+                number = T::from(digit);
             } else {
-                number = number * 10 + digit;
+                number = checked_append_digit(number, digit)?;
             }
         } else if column_number_start >= 0 {
-            numbers.push(Number { value: number, line_number: line, column_number_start, column_number_end: column_number - 1 });
+            numbers.push(Number { value: number.clone(), line_number: line, column_number_start, column_number_end: column_number - 1 });
             column_number_start = -1;
         }
         column_number = column_number + 1;
@@ -86,7 +101,7 @@ fn find_numbers_in_line(source: &str, line: i32) -> Vec<Number> {
     if column_number_start >= 0 {
         numbers.push(Number { value: number, line_number: line, column_number_start, column_number_end: column_number - 1 });
     }
-    return numbers;
+    return Ok(numbers);
 }
 
 #[cfg(test)]
@@ -95,19 +110,19 @@ mod find_numbers_in_line_tests {
 
     #[test]
     fn test_find_numbers_in_line_empty() {
-        let numbers = find_numbers_in_line("", 4);
+        let numbers = find_numbers_in_line::<i32>("", 4).unwrap();
         assert_eq!(numbers.is_empty(), true);
     }
 
     #[test]
     fn test_find_numbers_in_line_none_match() {
-        let numbers = find_numbers_in_line(".#...", 4);
+        let numbers = find_numbers_in_line::<i32>(".#...", 4).unwrap();
         assert_eq!(numbers.is_empty(), true);
     }
 
     #[test]
     fn test_find_numbers_in_line_one_match() {
-        let numbers = find_numbers_in_line(".#.234..", 4);
+        let numbers = find_numbers_in_line::<i32>(".#.234..", 4).unwrap();
         assert_eq!(numbers.len(), 1);
         assert_eq!(numbers[0].value, 234);
         assert_eq!(numbers[0].line_number, 4);
@@ -117,7 +132,7 @@ mod find_numbers_in_line_tests {
 
     #[test]
     fn test_find_numbers_in_line_many_match() {
-        let numbers = find_numbers_in_line(".#.234..567", 4);
+        let numbers = find_numbers_in_line::<i32>(".#.234..567", 4).unwrap();
         assert_eq!(numbers.len(), 2);
         assert_eq!(numbers[0].value, 234);
         assert_eq!(numbers[0].line_number, 4);
@@ -128,9 +143,21 @@ mod find_numbers_in_line_tests {
         assert_eq!(numbers[1].column_number_start, 8);
         assert_eq!(numbers[1].column_number_end, 10);
     }
+
+    #[test]
+    fn test_find_numbers_in_line_overflow() {
+        assert_eq!(find_numbers_in_line::<i32>("..12345678901..", 4).is_err(), true);
+    }
+
+    #[test]
+    fn test_find_numbers_in_line_big() {
+        let numbers = find_numbers_in_line::<BigInt>("..12345678901234567890123..", 4).unwrap();
+        assert_eq!(numbers[0].value, "12345678901234567890123".parse::<BigInt>().unwrap());
+        assert_eq!(numbers[0].column_number_end, 24);
+    }
 }
 
-fn is_adjacent(number: &Number, symbol: &Symbol) -> bool {
+fn is_adjacent<T>(number: &Number<T>, symbol: &Symbol) -> bool {
     return symbol.column_number >= number.column_number_start - 1
         && symbol.column_number <= number.column_number_end + 1
         && symbol.line_number >= number.line_number - 1
@@ -238,9 +265,228 @@ mod is_adjacent_tests {
     }
 }
 
-fn is_part_number(number: &Number, symbols: &Vec<Symbol>) -> bool {
-    for symbol in symbols {
-        if is_adjacent(number, symbol) {
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Adjacency {
+    Diagonal,
+    Orthogonal,
+}
+
+fn parse_adjacency(text: &str) -> Option<Adjacency> {
+    return match text {
+        "diagonal" => Some(Adjacency::Diagonal),
+        "orthogonal" => Some(Adjacency::Orthogonal),
+        _ => None,
+    };
+}
+
+fn neighbour_cells(line: i32, first_column: i32, last_column: i32, adjacency: Adjacency) -> Vec<(i32, i32)> {
+    let mut cells = vec![(first_column - 1, line), (last_column + 1, line)];
+    let (first, last) = match adjacency {
+        Adjacency::Diagonal => (first_column - 1, last_column + 1),
+        Adjacency::Orthogonal => (first_column, last_column),
+    };
+    for column in first..=last {
+        cells.push((column, line - 1));
+        cells.push((column, line + 1));
+    }
+    return cells;
+}
+
+#[cfg(test)]
+mod neighbour_cells_tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbour_cells_diagonal() {
+        let mut cells = neighbour_cells(4, 2, 2, Adjacency::Diagonal);
+        cells.sort();
+        assert_eq!(cells, vec![(1, 3), (1, 4), (1, 5), (2, 3), (2, 5), (3, 3), (3, 4), (3, 5)]);
+    }
+
+    #[test]
+    fn test_neighbour_cells_orthogonal() {
+        let mut cells = neighbour_cells(4, 1, 3, Adjacency::Orthogonal);
+        cells.sort();
+        assert_eq!(cells, vec![(0, 4), (1, 3), (1, 5), (2, 3), (2, 5), (3, 3), (3, 5), (4, 4)]);
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum AdjacentNumbers {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+fn parse_adjacent_numbers(text: &str) -> Option<AdjacentNumbers> {
+    let (rule, count) = text.split_once(':')?;
+    let count = count.parse::<usize>().ok().filter(|count| *count > 0)?;
+    return match rule {
+        "exactly" => Some(AdjacentNumbers::Exactly(count)),
+        "at-least" => Some(AdjacentNumbers::AtLeast(count)),
+        _ => None,
+    };
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Scoring {
+    Product,
+    Sum,
+    Max,
+}
+
+fn parse_scoring(text: &str) -> Option<Scoring> {
+    return match text {
+        "product" => Some(Scoring::Product),
+        "sum" => Some(Scoring::Sum),
+        "max" => Some(Scoring::Max),
+        _ => None,
+    };
+}
+
+#[cfg(test)]
+mod parse_rule_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_adjacency() {
+        assert_eq!(parse_adjacency("diagonal"), Some(Adjacency::Diagonal));
+        assert_eq!(parse_adjacency("orthogonal"), Some(Adjacency::Orthogonal));
+        assert_eq!(parse_adjacency("knight"), None);
+    }
+
+    #[test]
+    fn test_parse_adjacent_numbers() {
+        assert_eq!(parse_adjacent_numbers("exactly:2"), Some(AdjacentNumbers::Exactly(2)));
+        assert_eq!(parse_adjacent_numbers("at-least:3"), Some(AdjacentNumbers::AtLeast(3)));
+        assert_eq!(parse_adjacent_numbers("exactly:0"), None);
+        assert_eq!(parse_adjacent_numbers("at-most:2"), None);
+        assert_eq!(parse_adjacent_numbers("2"), None);
+    }
+
+    #[test]
+    fn test_parse_scoring() {
+        assert_eq!(parse_scoring("product"), Some(Scoring::Product));
+        assert_eq!(parse_scoring("sum"), Some(Scoring::Sum));
+        assert_eq!(parse_scoring("max"), Some(Scoring::Max));
+        assert_eq!(parse_scoring("min"), None);
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct GearRule {
+    symbols: Vec<char>,
+    numbers: AdjacentNumbers,
+    scoring: Scoring,
+    adjacency: Adjacency,
+}
+
+impl Default for GearRule {
+    fn default() -> GearRule {
+        return GearRule {
+            symbols: vec!['*'],
+            numbers: AdjacentNumbers::Exactly(2),
+            scoring: Scoring::Product,
+            adjacency: Adjacency::Diagonal,
+        };
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Cell {
+    Symbol(usize),
+    Number(usize),
+}
+
+struct Schematic<T> {
+    symbols: Vec<Symbol>,
+    numbers: Vec<Number<T>>,
+    width: i32,
+    height: i32,
+    cells: Vec<Vec<Cell>>,
+}
+
+impl<T> Schematic<T> {
+    fn new(symbols: Vec<Symbol>, numbers: Vec<Number<T>>) -> Schematic<T> {
+        let width = symbols.iter().map(|symbol| symbol.column_number)
+            .chain(numbers.iter().map(|number| number.column_number_end))
+            .max().map_or(0, |column| column + 1);
+        let height = symbols.iter().map(|symbol| symbol.line_number)
+            .chain(numbers.iter().map(|number| number.line_number))
+            .max().map_or(0, |line| line + 1);
+        let mut cells = vec![vec![]; (width * height) as usize];
+        for (index, symbol) in symbols.iter().enumerate() {
+            cells[(symbol.line_number * width + symbol.column_number) as usize].push(Cell::Symbol(index));
+        }
+        for (index, number) in numbers.iter().enumerate() {
+            for column in number.column_number_start..=number.column_number_end {
+                cells[(number.line_number * width + column) as usize].push(Cell::Number(index));
+            }
+        }
+        return Schematic { symbols, numbers, width, height, cells };
+    }
+
+    fn cell(&self, column: i32, line: i32) -> &[Cell] {
+        if column < 0 || line < 0 || column >= self.width || line >= self.height {
+            return &[];
+        }
+        return &self.cells[(line * self.width + column) as usize];
+    }
+}
+
+#[cfg(test)]
+mod schematic_tests {
+    use super::*;
+
+    #[test]
+    fn test_schematic_cells() {
+        let number = Number { value: 35, line_number: 2, column_number_start: 2, column_number_end: 3 };
+        let symbol = Symbol { value: '*', line_number: 1, column_number: 3 };
+        let schematic = Schematic::new(vec![symbol], vec![number]);
+        assert_eq!((schematic.width, schematic.height), (4, 3));
+        assert_eq!(schematic.cell(3, 1), [Cell::Symbol(0)]);
+        assert_eq!(schematic.cell(2, 2), [Cell::Number(0)]);
+        assert_eq!(schematic.cell(3, 2), [Cell::Number(0)]);
+        assert_eq!(schematic.cell(0, 0).is_empty(), true);
+    }
+
+    #[test]
+    fn test_schematic_cells_shared_neighbours() {
+        let schematic = read_schematic::<i32>("data/day03/shared_neighbours_test.txt").unwrap();
+        assert_eq!(schematic.cell(3, 1), [Cell::Symbol(0)]);
+        assert_eq!(schematic.cell(4, 1), [Cell::Symbol(1)]);
+        assert_eq!(schematic.cell(4, 0), [Cell::Number(1)]);
+        assert_eq!(schematic.cell(5, 0), [Cell::Number(1)]);
+        let star = &schematic.symbols[0];
+        let hash = &schematic.symbols[1];
+        assert_eq!(part_numbers_in_range(star, &schematic, Adjacency::Diagonal), vec![123, 45, 67]);
+        assert_eq!(part_numbers_in_range(hash, &schematic, Adjacency::Diagonal), vec![45]);
+        assert_eq!(part_numbers_in_range(hash, &schematic, Adjacency::Orthogonal), vec![45]);
+        assert_eq!(part_number_sum::<i32>(&schematic, Adjacency::Diagonal), Ok(123 + 45 + 67));
+        let rule = GearRule { numbers: AdjacentNumbers::AtLeast(2), ..GearRule::default() };
+        assert_eq!(gear_ratio::<i32>(star, &schematic, &rule), Ok(123 * 45 * 67));
+        assert_eq!(gear_ratio::<i32>(star, &schematic, &GearRule::default()), Ok(0));
+    }
+
+    #[test]
+    fn test_schematic_cells_out_of_bounds() {
+        let schematic: Schematic<i32> = Schematic::new(vec![Symbol { value: '#', line_number: 0, column_number: 0 }], vec![]);
+        assert_eq!(schematic.cell(-1, 0).is_empty(), true);
+        assert_eq!(schematic.cell(0, -1).is_empty(), true);
+        assert_eq!(schematic.cell(1, 0).is_empty(), true);
+        assert_eq!(schematic.cell(0, 1).is_empty(), true);
+    }
+
+    #[test]
+    fn test_schematic_empty() {
+        let schematic: Schematic<i32> = Schematic::new(vec![], vec![]);
+        assert_eq!(schematic.cells.is_empty(), true);
+        assert_eq!(schematic.cell(0, 0).is_empty(), true);
+    }
+}
+
+fn is_part_number<T>(number: &Number<T>, schematic: &Schematic<T>, adjacency: Adjacency) -> bool {
+    for (column, line) in neighbour_cells(number.line_number, number.column_number_start, number.column_number_end, adjacency) {
+        if schematic.cell(column, line).iter().any(|cell| matches!(cell, Cell::Symbol(_))) {
             return true;
         }
     }
@@ -260,7 +506,7 @@ mod is_part_number_tests {
             column_number_end: 3,
         };
         let symbols: Vec<Symbol> = vec![];
-        assert_eq!(is_part_number(&number, &symbols), false);
+        assert_eq!(is_part_number(&number, &Schematic::new(symbols, vec![]), Adjacency::Diagonal), false);
     }
 
     #[test]
@@ -277,7 +523,7 @@ mod is_part_number_tests {
             column_number: 2,
         };
         let symbols: Vec<Symbol> = vec![symbol];
-        assert_eq!(is_part_number(&number, &symbols), false);
+        assert_eq!(is_part_number(&number, &Schematic::new(symbols, vec![]), Adjacency::Diagonal), false);
     }
 
     #[test]
@@ -294,18 +540,37 @@ mod is_part_number_tests {
             column_number: 4,
         };
         let symbols: Vec<Symbol> = vec![symbol];
-        assert_eq!(is_part_number(&number, &symbols), true);
+        assert_eq!(is_part_number(&number, &Schematic::new(symbols, vec![]), Adjacency::Diagonal), true);
+    }
+
+    #[test]
+    fn test_is_part_number_orthogonal() {
+        let number = Number {
+            value: 123,
+            line_number: 4,
+            column_number_start: 1,
+            column_number_end: 3,
+        };
+        let diagonal = Schematic::new(vec![Symbol { value: '*', line_number: 5, column_number: 4 }], vec![]);
+        let below = Schematic::new(vec![Symbol { value: '*', line_number: 5, column_number: 3 }], vec![]);
+        assert_eq!(is_part_number(&number, &diagonal, Adjacency::Orthogonal), false);
+        assert_eq!(is_part_number(&number, &below, Adjacency::Orthogonal), true);
     }
 }
 
-fn part_numbers_in_range(symbol: &Symbol, numbers: &Vec<Number>) -> Vec<i32> {
-    let mut matches: Vec<i32> = vec![];
-    for number in numbers {
-        if is_adjacent(number, symbol) {
-            matches.push(number.value);
+fn part_numbers_in_range<T: Clone>(symbol: &Symbol, schematic: &Schematic<T>, adjacency: Adjacency) -> Vec<T> {
+    let mut indices: Vec<usize> = vec![];
+    for (column, line) in neighbour_cells(symbol.line_number, symbol.column_number, symbol.column_number, adjacency) {
+        for cell in schematic.cell(column, line) {
+            if let Cell::Number(index) = cell {
+                if !indices.contains(index) {
+                    indices.push(*index);
+                }
+            }
         }
     }
-    return matches;
+    indices.sort();
+    return indices.iter().map(|index| schematic.numbers[*index].value.clone()).collect();
 }
 
 #[cfg(test)]
@@ -319,8 +584,8 @@ mod part_numbers_in_range_tests {
             line_number: 4,
             column_number: 5,
         };
-        let numbers: Vec<Number> = vec![];
-        assert_eq!(part_numbers_in_range(&symbol, &numbers).is_empty(), true);
+        let numbers: Vec<Number<i32>> = vec![];
+        assert_eq!(part_numbers_in_range(&symbol, &Schematic::new(vec![], numbers), Adjacency::Diagonal).is_empty(), true);
     }
 
     #[test]
@@ -336,8 +601,8 @@ mod part_numbers_in_range_tests {
             column_number_start: 1,
             column_number_end: 3,
         };
-        let numbers: Vec<Number> = vec![number];
-        assert_eq!(part_numbers_in_range(&symbol, &numbers).is_empty(), true);
+        let numbers: Vec<Number<i32>> = vec![number];
+        assert_eq!(part_numbers_in_range(&symbol, &Schematic::new(vec![], numbers), Adjacency::Diagonal).is_empty(), true);
     }
 
     #[test]
@@ -353,8 +618,8 @@ mod part_numbers_in_range_tests {
             column_number_start: 1,
             column_number_end: 3,
         };
-        let numbers: Vec<Number> = vec![number];
-        let matches = part_numbers_in_range(&symbol, &numbers);
+        let numbers: Vec<Number<i32>> = vec![number];
+        let matches = part_numbers_in_range(&symbol, &Schematic::new(vec![], numbers), Adjacency::Diagonal);
         assert_eq!(matches.len(), 1);
     }
 
@@ -377,21 +642,34 @@ mod part_numbers_in_range_tests {
             column_number_start: 5,
             column_number_end: 7,
         };
-        let numbers: Vec<Number> = vec![number_one, number_two];
-        let matches = part_numbers_in_range(&symbol, &numbers);
+        let numbers: Vec<Number<i32>> = vec![number_one, number_two];
+        let matches = part_numbers_in_range(&symbol, &Schematic::new(vec![], numbers), Adjacency::Diagonal);
         assert_eq!(matches.len(), 2);
     }
 }
 
-fn gear_ratio(symbol: &Symbol, numbers: &Vec<Number>) -> i32 {
-    if symbol.value != '*' {
-        return 0;
+fn gear_numbers<T: Clone>(symbol: &Symbol, schematic: &Schematic<T>, rule: &GearRule) -> Option<Vec<T>> {
+    if !rule.symbols.contains(&symbol.value) {
+        return None;
     }
-    let matches = part_numbers_in_range(symbol, numbers);
-    if matches.len() != 2 {
-        return 0;
-    }
-    return matches[0] * matches[1];
+    let matches = part_numbers_in_range(symbol, schematic, rule.adjacency);
+    let is_gear = match rule.numbers {
+        AdjacentNumbers::Exactly(count) => matches.len() == count,
+        AdjacentNumbers::AtLeast(count) => matches.len() >= count,
+    };
+    return if is_gear { Some(matches) } else { None };
+}
+
+fn gear_ratio<T: Accumulator + Ord>(symbol: &Symbol, schematic: &Schematic<T>, rule: &GearRule) -> Result<T, OverflowError> {
+    let matches = match gear_numbers(symbol, schematic, rule) {
+        Some(matches) => matches,
+        None => return Ok(T::zero()),
+    };
+    return match rule.scoring {
+        Scoring::Product => checked_product(matches),
+        Scoring::Sum => checked_sum(matches),
+        Scoring::Max => Ok(matches.into_iter().max().unwrap_or_else(T::zero)),
+    };
 }
 
 #[cfg(test)]
@@ -405,8 +683,8 @@ mod gear_ratio_tests {
             line_number: 4,
             column_number: 5,
         };
-        let numbers: Vec<Number> = vec![];
-        assert_eq!(gear_ratio(&symbol, &numbers), 0);
+        let numbers: Vec<Number<i32>> = vec![];
+        assert_eq!(gear_ratio::<i32>(&symbol, &Schematic::new(vec![], numbers), &GearRule::default()), Ok(0));
     }
 
     #[test]
@@ -422,8 +700,8 @@ mod gear_ratio_tests {
             column_number_start: 1,
             column_number_end: 3,
         };
-        let numbers: Vec<Number> = vec![number];
-        assert_eq!(gear_ratio(&symbol, &numbers), 0);
+        let numbers: Vec<Number<i32>> = vec![number];
+        assert_eq!(gear_ratio::<i32>(&symbol, &Schematic::new(vec![], numbers), &GearRule::default()), Ok(0));
     }
 
     #[test]
@@ -445,8 +723,8 @@ mod gear_ratio_tests {
             column_number_start: 5,
             column_number_end: 7,
         };
-        let numbers: Vec<Number> = vec![number_one, number_two];
-        assert_eq!(gear_ratio(&symbol, &numbers), 0);
+        let numbers: Vec<Number<i32>> = vec![number_one, number_two];
+        assert_eq!(gear_ratio::<i32>(&symbol, &Schematic::new(vec![], numbers), &GearRule::default()), Ok(0));
     }
 
     #[test]
@@ -462,8 +740,8 @@ mod gear_ratio_tests {
             column_number_start: 1,
             column_number_end: 3,
         };
-        let numbers: Vec<Number> = vec![number];
-        assert_eq!(gear_ratio(&symbol, &numbers), 0);
+        let numbers: Vec<Number<i32>> = vec![number];
+        assert_eq!(gear_ratio::<i32>(&symbol, &Schematic::new(vec![], numbers), &GearRule::default()), Ok(0));
     }
 
     #[test]
@@ -485,8 +763,8 @@ mod gear_ratio_tests {
             column_number_start: 5,
             column_number_end: 7,
         };
-        let numbers: Vec<Number> = vec![number_one, number_two];
-        assert_eq!(gear_ratio(&symbol, &numbers), 56088);
+        let numbers: Vec<Number<i32>> = vec![number_one, number_two];
+        assert_eq!(gear_ratio::<i32>(&symbol, &Schematic::new(vec![], numbers), &GearRule::default()), Ok(56088));
     }
 
     #[test]
@@ -514,22 +792,80 @@ mod gear_ratio_tests {
             column_number_start: 5,
             column_number_end: 7,
         };
-        let numbers: Vec<Number> = vec![number_one, number_two, number_three];
-        assert_eq!(gear_ratio(&symbol, &numbers), 0);
+        let numbers: Vec<Number<i32>> = vec![number_one, number_two, number_three];
+        let schematic = Schematic::new(vec![], numbers);
+        assert_eq!(gear_ratio::<i32>(&symbol, &schematic, &GearRule::default()), Ok(0));
+        let sum_all = GearRule { numbers: AdjacentNumbers::AtLeast(1), scoring: Scoring::Sum, ..GearRule::default() };
+        assert_eq!(gear_ratio::<i32>(&symbol, &schematic, &sum_all), Ok(1368));
+    }
+
+    fn three_numbers_around_hash() -> (Symbol, Schematic<i32>) {
+        let symbol = Symbol { value: '#', line_number: 4, column_number: 4 };
+        let numbers = vec![
+            Number { value: 12, line_number: 4, column_number_start: 2, column_number_end: 3 },
+            Number { value: 5, line_number: 3, column_number_start: 5, column_number_end: 5 },
+            Number { value: 40, line_number: 5, column_number_start: 4, column_number_end: 5 },
+        ];
+        return (symbol, Schematic::new(vec![], numbers));
+    }
+
+    #[test]
+    fn test_gear_ratio_configured_symbols_and_count() {
+        let (symbol, schematic) = three_numbers_around_hash();
+        let exactly_three = GearRule { symbols: vec!['*', '#'], numbers: AdjacentNumbers::Exactly(3), ..GearRule::default() };
+        assert_eq!(gear_ratio::<i32>(&symbol, &schematic, &GearRule::default()), Ok(0));
+        assert_eq!(gear_ratio::<i32>(&symbol, &schematic, &exactly_three), Ok(2400));
+        let at_least_two = GearRule { numbers: AdjacentNumbers::AtLeast(2), ..exactly_three.clone() };
+        assert_eq!(gear_ratio::<i32>(&symbol, &schematic, &at_least_two), Ok(2400));
+        let at_least_four = GearRule { numbers: AdjacentNumbers::AtLeast(4), ..exactly_three };
+        assert_eq!(gear_ratio::<i32>(&symbol, &schematic, &at_least_four), Ok(0));
+    }
+
+    #[test]
+    fn test_gear_ratio_scoring() {
+        let (symbol, schematic) = three_numbers_around_hash();
+        let rule = GearRule { symbols: vec!['#'], numbers: AdjacentNumbers::AtLeast(1), ..GearRule::default() };
+        assert_eq!(gear_ratio::<i32>(&symbol, &schematic, &GearRule { scoring: Scoring::Sum, ..rule.clone() }), Ok(57));
+        assert_eq!(gear_ratio::<i32>(&symbol, &schematic, &GearRule { scoring: Scoring::Max, ..rule }), Ok(40));
+    }
+
+    #[test]
+    fn test_gear_ratio_orthogonal() {
+        let (symbol, schematic) = three_numbers_around_hash();
+        let rule = GearRule { symbols: vec!['#'], adjacency: Adjacency::Orthogonal, ..GearRule::default() };
+        assert_eq!(gear_ratio::<i32>(&symbol, &schematic, &rule), Ok(480));
+    }
+
+    #[test]
+    fn test_gear_ratio_product_overflow() {
+        let symbol = Symbol { value: '*', line_number: 1, column_number: 5 };
+        let numbers = vec![
+            Number { value: 100000, line_number: 0, column_number_start: 0, column_number_end: 5 },
+            Number { value: 100000, line_number: 2, column_number_start: 0, column_number_end: 5 },
+        ];
+        let schematic = Schematic::new(vec![], numbers);
+        assert_eq!(gear_ratio::<i32>(&symbol, &schematic, &GearRule::default()).is_err(), true);
+        let numbers = vec![
+            Number { value: BigInt::from(100000), line_number: 0, column_number_start: 0, column_number_end: 5 },
+            Number { value: BigInt::from(100000), line_number: 2, column_number_start: 0, column_number_end: 5 },
+        ];
+        let schematic = Schematic::new(vec![], numbers);
+        assert_eq!(gear_ratio::<BigInt>(&symbol, &schematic, &GearRule::default()), Ok(BigInt::from(10000000000i64)));
     }
 }
 
-fn read_schematic(filename: &str) -> (Vec<Symbol>, Vec<Number>) {
+fn read_schematic<T: Accumulator + From<u8>>(filename: &str) -> Result<Schematic<T>, OverflowError> {
     let mut symbols: Vec<Symbol> = vec![];
-    let mut numbers: Vec<Number> = vec![];
+    let mut numbers: Vec<Number<T>> = vec![];
     let lines = line_iterator(filename);
     let mut line_number = 0;
     for line in lines {
         symbols.append(&mut find_symbols_in_line(line.as_str(), line_number));
-        numbers.append(&mut find_numbers_in_line(line.as_str(), line_number));
+        numbers.append(&mut find_numbers_in_line(line.as_str(), line_number)?);
         line_number = line_number + 1;
     }
-    return (symbols, numbers);
+    debug!("{}: {} lines, {} symbols, {} numbers", filename, line_number, symbols.len(), numbers.len());
+    return Ok(Schematic::new(symbols, numbers));
 }
 
 #[cfg(test)]
@@ -538,21 +874,27 @@ mod read_schematic_tests {
 
     #[test]
     fn test_read_schematic() {
-        let (symbols, numbers) = read_schematic("data/day03/test.txt");
-        assert_eq!(numbers.len(), 10);
-        assert_eq!(symbols.len(), 6);
+        let schematic = read_schematic::<i32>("data/day03/test.txt").unwrap();
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(schematic.symbols.len(), 6);
+        assert_eq!((schematic.width, schematic.height), (9, 10));
+        assert_eq!(schematic.cell(3, 1), [Cell::Symbol(0)]);
+        assert_eq!(schematic.cell(2, 0), [Cell::Number(0)]);
     }
 }
 
-fn read_schematic_part1(filename: &str) -> i32 {
-    let (symbols, numbers) = read_schematic(filename);
-    let mut sum = 0;
-    for number in numbers {
-        if is_part_number(&number, &symbols) {
-            sum += number.value;
+fn part_number_sum<T: Accumulator>(schematic: &Schematic<T>, adjacency: Adjacency) -> Result<T, OverflowError> {
+    let mut sum = T::zero();
+    for number in &schematic.numbers {
+        if is_part_number(number, schematic, adjacency) {
+            sum = checked_add(sum, number.value.clone())?;
         }
     }
-    return sum;
+    return Ok(sum);
+}
+
+fn read_schematic_part1<T: Accumulator + From<u8>>(filename: &str, adjacency: Adjacency) -> Result<T, OverflowError> {
+    return part_number_sum(&read_schematic(filename)?, adjacency);
 }
 
 #[cfg(test)]
@@ -561,17 +903,32 @@ mod read_schematic_part1_tests {
 
     #[test]
     fn test_read_schematic_part1() {
-        assert_eq!(read_schematic_part1("data/day03/test.txt"), 4361);
+        assert_eq!(read_schematic_part1::<i32>("data/day03/test.txt", Adjacency::Diagonal), Ok(4361));
+    }
+
+    #[test]
+    fn test_read_schematic_part1_big() {
+        assert_eq!(read_schematic_part1::<i64>("data/day03/big_test.txt", Adjacency::Diagonal).is_err(), true);
+        assert_eq!(read_schematic_part1::<BigInt>("data/day03/big_test.txt", Adjacency::Diagonal), Ok("100000000000000000003".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_read_schematic_part1_orthogonal() {
+        assert_eq!(read_schematic_part1::<i32>("data/day03/test.txt", Adjacency::Orthogonal), Ok(2547));
     }
 }
 
-fn read_schematic_part2(filename: &str) -> i32 {
-    let (symbols, numbers) = read_schematic(filename);
-    let mut sum = 0;
-    for symbol in symbols {
-        sum += gear_ratio(&symbol, &numbers);
+fn gear_ratio_sum<T: Accumulator + Ord>(schematic: &Schematic<T>, rule: &GearRule, context: &Context) -> Result<T, SolveError> {
+    let mut sum = T::zero();
+    for (index, symbol) in schematic.symbols.iter().enumerate() {
+        context.checkpoint("gear_ratio_sum", index as u64, Some(schematic.symbols.len() as u64))?;
+        sum = checked_add(sum, gear_ratio(symbol, schematic, rule)?)?;
     }
-    return sum;
+    return Ok(sum);
+}
+
+fn read_schematic_part2<T: Accumulator + Ord + From<u8>>(filename: &str, rule: &GearRule, context: &Context) -> Result<T, SolveError> {
+    return gear_ratio_sum(&read_schematic(filename)?, rule, context);
 }
 
 #[cfg(test)]
@@ -580,14 +937,307 @@ mod read_schematic_part2_tests {
 
     #[test]
     fn test_read_schematic_part2() {
-        assert_eq!(read_schematic_part2("data/day03/test.txt"), 467835);
+        assert_eq!(read_schematic_part2::<i32>("data/day03/test.txt", &GearRule::default(), &Context::default()), Ok(467835));
+    }
+
+    #[test]
+    fn test_read_schematic_part2_big() {
+        assert_eq!(read_schematic_part2::<BigInt>("data/day03/test.txt", &GearRule::default(), &Context::default()), Ok(BigInt::from(467835)));
+    }
+
+    #[test]
+    fn test_read_schematic_part2_big_input() {
+        assert_eq!(read_schematic_part2::<i64>("data/day03/big_test.txt", &GearRule::default(), &Context::default()).is_err(), true);
+        assert_eq!(read_schematic_part2::<BigInt>("data/day03/big_test.txt", &GearRule::default(), &Context::default()), Ok("300000000000000000000".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_read_schematic_part2_cancelled() {
+        let context = Context::default();
+        context.cancel();
+        assert_eq!(read_schematic_part2::<i32>("data/day03/test.txt", &GearRule::default(), &context), Err(SolveError::Cancelled));
+    }
+
+    #[test]
+    fn test_read_schematic_part2_any_symbol_at_least_one_sum() {
+        let rule = GearRule {
+            symbols: "*#+$".chars().collect(),
+            numbers: AdjacentNumbers::AtLeast(1),
+            scoring: Scoring::Sum,
+            adjacency: Adjacency::Diagonal,
+        };
+        assert_eq!(read_schematic_part2::<i32>("data/day03/test.txt", &rule, &Context::default()), Ok(4361));
+    }
+
+    #[test]
+    fn test_read_schematic_part2_max() {
+        let rule = GearRule { scoring: Scoring::Max, ..GearRule::default() };
+        assert_eq!(read_schematic_part2::<i32>("data/day03/test.txt", &rule, &Context::default()), Ok(755 + 467));
+    }
+}
+
+fn scan_sums(schematic: &Schematic<i64>) -> (i64, i64) {
+    let mut part_numbers = 0;
+    for number in &schematic.numbers {
+        if schematic.symbols.iter().any(|symbol| is_adjacent(number, symbol)) {
+            part_numbers += number.value;
+        }
+    }
+    let mut gear_ratios = 0;
+    for symbol in schematic.symbols.iter().filter(|symbol| symbol.value == '*') {
+        let matches: Vec<i64> = schematic.numbers.iter()
+            .filter(|number| is_adjacent(number, symbol))
+            .map(|number| number.value)
+            .collect();
+        if matches.len() == 2 {
+            gear_ratios += matches[0] * matches[1];
+        }
+    }
+    return (part_numbers, gear_ratios);
+}
+
+#[cfg(test)]
+mod scan_sums_tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_sums() {
+        assert_eq!(scan_sums(&read_schematic::<i64>("data/day03/test.txt").unwrap()), (4361, 467835));
+    }
+}
+
+fn indexed_sums(schematic: &Schematic<i64>, rule: &GearRule) -> (i64, i64) {
+    return (part_number_sum(schematic, rule.adjacency).unwrap(), gear_ratio_sum(schematic, rule, &Context::default()).unwrap());
+}
+
+fn generate_schematic(lines: usize, width: usize, seed: u64) -> String {
+    let mut state = seed.max(1);
+    let mut next = |limit: usize| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        return (state % limit as u64) as usize;
+    };
+    let symbols = b"*#+$/@=%&-";
+    let mut schematic = String::new();
+    for _ in 0..lines {
+        let mut column = 0;
+        while column < width {
+            let roll = next(20);
+            if roll < 2 && column + 4 <= width {
+                let digits = 1 + next(3);
+                for _ in 0..digits {
+                    schematic.push(char::from(b'0' + next(10) as u8));
+                }
+                schematic.push('.');
+                column += digits + 1;
+                continue;
+            }
+            if roll == 2 {
+                schematic.push(char::from(symbols[next(symbols.len())]));
+            } else {
+                schematic.push('.');
+            }
+            column += 1;
+        }
+        schematic.push('\n');
+    }
+    return schematic;
+}
+
+#[cfg(test)]
+mod generate_schematic_tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_schematic() {
+        let schematic = generate_schematic(20, 30, 7);
+        assert_eq!(schematic.lines().count(), 20);
+        assert_eq!(schematic.lines().all(|line| line.len() == 30), true);
+        assert_eq!(schematic, generate_schematic(20, 30, 7));
+    }
+
+    #[test]
+    fn test_generated_schematic_sums_agree() {
+        let path = std::env::temp_dir().join(format!("aoc_day03_generate_schematic_test_{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(path, generate_schematic(60, 40, 3)).unwrap();
+        let schematic = read_schematic::<i64>(path).unwrap();
+        let _ = fs::remove_file(path);
+        assert_eq!(schematic.numbers.is_empty(), false);
+        assert_eq!(indexed_sums(&schematic, &GearRule::default()), scan_sums(&schematic));
+    }
+}
+
+pub fn benchmark(context: &Context) -> Result<Vec<Measurement>, SolveError> {
+    let rule = gear_rule(context)?;
+    let mut measurements = vec![];
+    for lines in BENCHMARK_SIZES {
+        let path = std::env::temp_dir().join(format!("aoc_day03_benchmark_{}_{}.txt", std::process::id(), lines));
+        let path = path.to_str().unwrap();
+        fs::write(path, generate_schematic(lines, BENCHMARK_WIDTH, lines as u64)).unwrap();
+        let expected = indexed_sums(&read_schematic::<i64>(path).unwrap(), &rule);
+        if lines <= MAX_SCAN_SIZE {
+            measurements.push(measure("scan every pair", lines, 1, || scan_sums(&read_schematic::<i64>(path).unwrap())));
+            if rule == GearRule::default() && scan_sums(&read_schematic::<i64>(path).unwrap()) != expected {
+                warn!("{}: scanned and indexed sums differ", path);
+            }
+        }
+        measurements.push(measure("spatial index", lines, 3, || indexed_sums(&read_schematic::<i64>(path).unwrap(), &rule)));
+        let _ = fs::remove_file(path);
+    }
+    return Ok(measurements);
+}
+
+fn schematic_colours<T: Clone>(schematic: &Schematic<T>, rule: &GearRule) -> HashMap<(i32, i32), Colour> {
+    let mut colours: HashMap<(i32, i32), Colour> = HashMap::new();
+    for number in &schematic.numbers {
+        let colour = if is_part_number(number, schematic, rule.adjacency) { Colour::GREEN } else { Colour::RED };
+        for column in number.column_number_start..=number.column_number_end {
+            colours.insert((column, number.line_number), colour);
+        }
+    }
+    for symbol in &schematic.symbols {
+        let is_gear = gear_numbers(symbol, schematic, rule).is_some();
+        let colour = if is_gear { Colour::YELLOW } else { Colour::BLUE };
+        colours.insert((symbol.column_number, symbol.line_number), colour);
+    }
+    return colours;
+}
+
+#[cfg(test)]
+mod schematic_colours_tests {
+    use super::*;
+
+    #[test]
+    fn test_schematic_colours() {
+        let colours = schematic_colours(&read_schematic::<i32>("data/day03/test.txt").unwrap(), &GearRule::default());
+        assert_eq!(colours.get(&(0, 0)), Some(&Colour::GREEN));
+        assert_eq!(colours.get(&(5, 0)), Some(&Colour::RED));
+        assert_eq!(colours.get(&(3, 1)), Some(&Colour::YELLOW));
+        assert_eq!(colours.get(&(6, 3)), Some(&Colour::BLUE));
+        assert_eq!(colours.get(&(3, 4)), Some(&Colour::BLUE));
+        assert_eq!(colours.get(&(1, 1)), None);
+    }
+
+    #[test]
+    fn test_schematic_colours_configured_rule() {
+        let context = Context::default().with_parameter("gear_symbols", "*#").with_parameter("gear_numbers", "at-least:1")
+            .with_parameter("adjacency", "orthogonal");
+        let colours = schematic_colours(&read_schematic::<i32>("data/day03/test.txt").unwrap(), &gear_rule(&context).unwrap());
+        assert_eq!(colours.get(&(0, 0)), Some(&Colour::RED));
+        assert_eq!(colours.get(&(6, 3)), Some(&Colour::YELLOW));
+    }
+}
+
+pub fn render(filename: &str, path: &str, context: &Context) -> io::Result<()> {
+    let rule = gear_rule(context).map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+    let schematic = read_schematic::<BigInt>(filename).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    let lines: Vec<String> = line_iterator(filename).collect();
+    let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+    let colours = schematic_colours(&schematic, &rule);
+    return write_image(path, width, lines.len(), 8, |x, y| {
+        *colours.get(&(x as i32, y as i32)).unwrap_or(&Colour::NAVY)
+    });
+}
+
+#[cfg(test)]
+mod render_tests {
+    use super::*;
+
+    #[test]
+    fn test_render_invalid_rule() {
+        let context = Context::default().with_parameter("gear_numbers", "none");
+        let path = std::env::temp_dir().join(format!("aoc_day03_render_test_{}.ppm", std::process::id()));
+        let error = render("data/day03/test.txt", path.to_str().unwrap(), &context).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(path.exists(), false);
+    }
+}
+
+fn adjacency_parameter(context: &Context) -> Result<Adjacency, SolveError> {
+    let adjacency = context.parameter("adjacency").unwrap_or("diagonal");
+    return parse_adjacency(adjacency).ok_or_else(|| SolveError::InvalidParameter {
+        name: "adjacency".to_string(),
+        message: format!("unknown adjacency \"{}\" (expected diagonal or orthogonal)", adjacency),
+    });
+}
+
+fn gear_rule(context: &Context) -> Result<GearRule, SolveError> {
+    let symbols = context.parameter("gear_symbols").unwrap_or("*");
+    if symbols.is_empty() || symbols.chars().any(|c| c == '.' || c.is_ascii_digit() || c.is_whitespace()) {
+        return Err(SolveError::InvalidParameter {
+            name: "gear_symbols".to_string(),
+            message: format!("\"{}\" should list one or more symbols, without dots, digits or spaces", symbols),
+        });
+    }
+    let numbers = context.parameter("gear_numbers").unwrap_or("exactly:2");
+    let numbers = parse_adjacent_numbers(numbers).ok_or_else(|| SolveError::InvalidParameter {
+        name: "gear_numbers".to_string(),
+        message: format!("unknown rule \"{}\" (expected exactly:N or at-least:N with N > 0)", numbers),
+    })?;
+    let scoring = context.parameter("gear_score").unwrap_or("product");
+    let scoring = parse_scoring(scoring).ok_or_else(|| SolveError::InvalidParameter {
+        name: "gear_score".to_string(),
+        message: format!("unknown score \"{}\" (expected product, sum or max)", scoring),
+    })?;
+    return Ok(GearRule { symbols: symbols.chars().collect(), numbers, scoring, adjacency: adjacency_parameter(context)? });
+}
+
+#[cfg(test)]
+mod gear_rule_tests {
+    use super::*;
+
+    #[test]
+    fn test_gear_rule_default() {
+        assert_eq!(gear_rule(&Context::default()), Ok(GearRule::default()));
+    }
+
+    #[test]
+    fn test_gear_rule_configured() {
+        let context = Context::default()
+            .with_parameter("gear_symbols", "*#")
+            .with_parameter("gear_numbers", "at-least:3")
+            .with_parameter("gear_score", "max")
+            .with_parameter("adjacency", "orthogonal");
+        assert_eq!(gear_rule(&context), Ok(GearRule {
+            symbols: vec!['*', '#'],
+            numbers: AdjacentNumbers::AtLeast(3),
+            scoring: Scoring::Max,
+            adjacency: Adjacency::Orthogonal,
+        }));
+    }
+
+    #[test]
+    fn test_gear_rule_invalid() {
+        for (name, value) in [("gear_symbols", ""), ("gear_symbols", "*."), ("gear_symbols", "* #"), ("gear_numbers", "two"), ("gear_score", "min"), ("adjacency", "all")] {
+            let context = Context::default().with_parameter(name, value);
+            assert_eq!(gear_rule(&context).is_err(), true, "{}={}", name, value);
+        }
+    }
+
+    #[test]
+    fn test_gear_rule_message() {
+        let context = Context::default().with_parameter("gear_numbers", "exactly:0");
+        assert_eq!(gear_rule(&context), Err(SolveError::InvalidParameter {
+            name: "gear_numbers".to_string(),
+            message: "unknown rule \"exactly:0\" (expected exactly:N or at-least:N with N > 0)".to_string(),
+        }));
     }
 }
 
-pub fn part1() {
-    println!("Day 3 Part 1 result: {}", read_schematic_part1("data/day03/input.txt"));
+pub fn part1(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    let adjacency = adjacency_parameter(context)?;
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(read_schematic_part1::<i32>(filename, adjacency)?),
+        Arithmetic::Big => Answer::from(read_schematic_part1::<BigInt>(filename, adjacency)?),
+    });
 }
 
-pub fn part2() {
-    println!("Day 3 Part 2 result: {}", read_schematic_part2("data/day03/input.txt"));
+pub fn part2(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    let rule = gear_rule(context)?;
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(read_schematic_part2::<i32>(filename, &rule, context)?),
+        Arithmetic::Big => Answer::from(read_schematic_part2::<BigInt>(filename, &rule, context)?),
+    });
 }
@@ -1,20 +1,19 @@
-use crate::aoc_common::lib::{get_series_of_ints, line_iterator, sum, sum_up};
+use std::sync::LazyLock;
+use num::BigInt;
+use crate::aoc_common::answer::Answer;
+use crate::aoc_common::checked::{checked_add, checked_sum, Accumulator, Arithmetic, OverflowError};
+use crate::aoc_common::context::{Context, SolveError};
+use crate::aoc_common::lib::{get_series_of_ints, line_iterator, sum_up};
+use crate::aoc_common::pattern::Pattern;
+
+static SCRATCHCARD_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern::new("Card {id}: {winners} | {picks}").unwrap());
 
 fn get_scratchcard_numbers(line: &str) -> Option<(i32, Vec<i32>, Vec<i32>)> {
-    let colon = line.find(':');
-    let pipe = line.find('|');
-    if colon.is_none() || pipe.is_none() {
-        return None;
-    }
-    let first = colon.unwrap();
-    let second = pipe.unwrap();
-    let card_numbers = get_series_of_ints(&line[..first]);
-    if card_numbers.len() != 1 {
-        return None;
-    }
-    let winners = get_series_of_ints(&line[first..second]);
-    let selected = get_series_of_ints(&line[second..]);
-    return Some((card_numbers[0], winners, selected));
+    let captures = SCRATCHCARD_PATTERN.parse(line).ok()?;
+    let card_number = captures.parse::<i32>("id").ok()?;
+    let winners = get_series_of_ints(captures.get("winners")?);
+    let selected = get_series_of_ints(captures.get("picks")?);
+    return Some((card_number, winners, selected));
 }
 
 #[cfg(test)]
@@ -36,6 +35,11 @@ mod get_scratchcard_numbers_tests {
         assert_eq!(winners.len(), 5);
         assert_eq!(selected.len(), 8);
     }
+
+    #[test]
+    fn test_get_scratchcard_numbers_bad_card_number() {
+        assert_eq!(get_scratchcard_numbers("Card x: 41 48 | 83 86"), None);
+    }
 }
 
 fn find(n: &i32, v: &Vec<i32>) -> Option<usize> {
@@ -131,12 +135,13 @@ mod matches_for_line_tests {
     }
 }
 
-fn score_for_line(line: &str) -> i32 {
+fn score_for_line(line: &str) -> Result<i32, OverflowError> {
     let matches = matches_for_line(line);
     if matches == 0 {
-        return 0;
+        return Ok(0);
     }
-    return 1 << (matches - 1);
+    return num::checked_pow(2i32, (matches - 1) as usize)
+        .ok_or_else(|| OverflowError { operation: format!("2 ^ {}", matches - 1) });
 }
 
 #[cfg(test)]
@@ -145,27 +150,34 @@ mod score_for_line_tests {
 
     #[test]
     fn test_score_for_line_empty() {
-        assert_eq!(score_for_line(""), 0);
+        assert_eq!(score_for_line(""), Ok(0));
     }
 
     #[test]
     fn test_score_for_line_none_match() {
-        assert_eq!(score_for_line("Card 1: 1 2 3 4 5 | 6 7 8 9 10 11 12"), 0);
+        assert_eq!(score_for_line("Card 1: 1 2 3 4 5 | 6 7 8 9 10 11 12"), Ok(0));
     }
 
     #[test]
     fn test_score_for_line_one_match() {
-        assert_eq!(score_for_line("Card 1: 1 2 3 4 5 | 5 6 7 8 9 10 11 12"), 1);
+        assert_eq!(score_for_line("Card 1: 1 2 3 4 5 | 5 6 7 8 9 10 11 12"), Ok(1));
     }
 
     #[test]
     fn test_score_for_line_four_match() {
-        assert_eq!(score_for_line("Card 1: 1 2 3 4 5 6 7 8 | 5 6 7 8 9 10 11 12"), 8);
+        assert_eq!(score_for_line("Card 1: 1 2 3 4 5 6 7 8 | 5 6 7 8 9 10 11 12"), Ok(8));
+    }
+
+    #[test]
+    fn test_score_for_line_overflow() {
+        let numbers = (1..=32).map(|n| n.to_string()).collect::<Vec<String>>().join(" ");
+        let line = format!("Card 1: {} | {}", numbers, numbers);
+        assert_eq!(score_for_line(&line), Err(OverflowError { operation: "2 ^ 31".to_string() }));
     }
 }
 
-fn score_for_file(filename: &str) -> i32 {
-    return sum_up(filename, score_for_line, sum);
+fn score_for_file<T: Accumulator + From<i32>>(filename: &str) -> Result<T, OverflowError> {
+    return sum_up(filename, score_for_line);
 }
 
 #[cfg(test)]
@@ -174,22 +186,22 @@ mod score_for_file_tests {
 
     #[test]
     fn test_score_for_file() {
-        assert_eq!(score_for_file("data/day04/test.txt"), 13);
+        assert_eq!(score_for_file::<i32>("data/day04/test.txt"), Ok(13));
     }
 }
 
-fn count_cards_for_file(filename: &str) -> i32 {
+fn count_cards_for_file<T: Accumulator>(filename: &str) -> Result<T, OverflowError> {
     let lines = line_iterator(filename);
     let matches: Vec<i32> = lines.map(|line| matches_for_line(line.as_str())).collect();
     let size = matches.len();
-    let mut totals: Vec<i32> = vec![0; size];
+    let mut totals: Vec<T> = vec![T::zero(); size];
     for i in 0..size {
-        totals[i] += 1;
+        totals[i] = checked_add(totals[i].clone(), T::one())?;
         for j in 0..matches[i] {
-            totals[i+j as usize+1] += totals[i];
+            totals[i+j as usize+1] = checked_add(totals[i+j as usize+1].clone(), totals[i].clone())?;
         }
     }
-    return totals.iter().fold(0, |a, b| a + b);
+    return checked_sum(totals);
 }
 
 #[cfg(test)]
@@ -198,14 +210,25 @@ mod count_cards_for_file_tests {
 
     #[test]
     fn test_count_cards_for_file() {
-        assert_eq!(count_cards_for_file("data/day04/test.txt"), 30);
+        assert_eq!(count_cards_for_file::<i32>("data/day04/test.txt"), Ok(30));
+    }
+
+    #[test]
+    fn test_count_cards_for_file_big() {
+        assert_eq!(count_cards_for_file::<BigInt>("data/day04/test.txt"), Ok(BigInt::from(30)));
     }
 }
 
-pub fn part1() {
-    println!("Day 4 Part 1 result: {}", score_for_file("data/day04/input.txt"));
+pub fn part1(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(score_for_file::<i32>(filename)?),
+        Arithmetic::Big => Answer::from(score_for_file::<BigInt>(filename)?),
+    });
 }
 
-pub fn part2() {
-    println!("Day 4 Part 2 result: {}", count_cards_for_file("data/day04/input.txt"));
+pub fn part2(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(count_cards_for_file::<i32>(filename)?),
+        Arithmetic::Big => Answer::from(count_cards_for_file::<BigInt>(filename)?),
+    });
 }
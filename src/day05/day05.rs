@@ -1,17 +1,73 @@
-use crate::aoc_common::lib::{get_series_of_ints, line_iterator};
+use std::str::FromStr;
+use num::BigInt;
+use crate::aoc_common::answer::Answer;
+use crate::aoc_common::checked::{checked_add, checked_sub, Accumulator, Arithmetic, OverflowError};
+use crate::aoc_common::context::{Context, SolveError};
+use crate::aoc_common::interval_set::IntervalSet;
+use crate::aoc_common::sections::{is_colon_header, read_sections};
+use crate::{debug, trace};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Mapping<T> {
+    target: T,
+    source: T,
+    len: T,
+}
+
+type Almanac<T> = (Vec<T>, Vec<Vec<Mapping<T>>>);
+
+fn number_error(field: &str) -> String {
+    let digits = field.strip_prefix('-').unwrap_or(field);
+    if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) {
+        return format!("number \"{}\" is out of range", field);
+    }
+    return format!("invalid number \"{}\"", field);
+}
+
+fn parse_numbers<T: FromStr>(text: &str, first_column: usize) -> Result<Vec<T>, (usize, String)> {
+    let mut numbers = vec![];
+    let mut column = first_column;
+    for field in text.split(' ') {
+        if !field.is_empty() {
+            numbers.push(field.parse().map_err(|_| (column, number_error(field)))?);
+        }
+        column += field.chars().count() + 1;
+    }
+    return Ok(numbers);
+}
+
+#[cfg(test)]
+mod parse_numbers_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_numbers() {
+        assert_eq!(parse_numbers::<i64>("50  98 2", 1), Ok(vec![50, 98, 2]));
+        assert_eq!(parse_numbers::<i64>("", 1), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_numbers_invalid() {
+        assert_eq!(parse_numbers::<i64>("50 9x8 2", 1), Err((4, "invalid number \"9x8\"".to_string())));
+        assert_eq!(parse_numbers::<i64>("79 1a", 8), Err((11, "invalid number \"1a\"".to_string())));
+    }
+
+    #[test]
+    fn test_parse_numbers_out_of_range() {
+        assert_eq!(parse_numbers::<i64>("1 100000000000000000000", 1), Err((3, "number \"100000000000000000000\" is out of range".to_string())));
+    }
 
-#[derive(Copy, Clone)]
-struct Mapping {
-    target: i64,
-    source: i64,
-    len: i64,
+    #[test]
+    fn test_parse_numbers_big() {
+        assert_eq!(parse_numbers::<BigInt>("1 100000000000000000000", 1), Ok(vec![BigInt::from(1), BigInt::from(10).pow(20)]));
+    }
 }
 
-fn get_seed_numbers(line: &str) -> Vec<i64> {
-    if line.starts_with("seeds: ") {
-        return get_series_of_ints(line);
+fn get_seed_numbers<T: Accumulator + Ord + FromStr>(line: &str) -> Result<Vec<T>, (usize, String)> {
+    if let Some(numbers) = line.strip_prefix("seeds: ") {
+        return parse_numbers(numbers, "seeds: ".len() + 1);
     }
-    return vec![];
+    return Ok(vec![]);
 }
 
 #[cfg(test)]
@@ -20,26 +76,29 @@ mod get_seed_numbers_tests {
 
     #[test]
     fn test_get_seed_numbers_empty() {
-        assert_eq!(get_seed_numbers("").is_empty(), true);
+        assert_eq!(get_seed_numbers::<i64>("").unwrap().is_empty(), true);
     }
 
     #[test]
     fn test_get_seed_numbers_not_seed_line() {
-        assert_eq!(get_seed_numbers("1 2 3 4 5").is_empty(), true);
+        assert_eq!(get_seed_numbers::<i64>("1 2 3 4 5").unwrap().is_empty(), true);
     }
 
     #[test]
     fn test_get_seed_numbers_correct() {
-        assert_eq!(get_seed_numbers("seeds: 79 14 55 13").len(), 4);
+        assert_eq!(get_seed_numbers::<i64>("seeds: 79 14 55 13").unwrap().len(), 4);
     }
-}
 
-fn get_mapping(line: &str) -> Option<Mapping> {
-    let ints = get_series_of_ints(line);
-    if ints.len() != 3 {
-        return None;
+    #[test]
+    fn test_get_seed_numbers_invalid() {
+        assert_eq!(get_seed_numbers::<i64>("seeds: 79 x"), Err((11, "invalid number \"x\"".to_string())));
     }
-    return Some(Mapping { target: ints[0], source: ints[1], len: ints[2] });
+}
+
+fn get_mapping<T: Accumulator + Ord + FromStr>(line: &str) -> Result<Mapping<T>, (usize, String)> {
+    let ints: Vec<T> = parse_numbers(line, 1)?;
+    let [target, source, len]: [T; 3] = ints.try_into().map_err(|_| (1, format!("expected \"<target> <source> <length>\", found \"{}\"", line)))?;
+    return Ok(Mapping { target, source, len });
 }
 
 #[cfg(test)]
@@ -48,23 +107,29 @@ mod get_mapping_tests {
 
     #[test]
     fn test_get_mapping_empty_line() {
-        assert_eq!(get_mapping("").is_none(), true);
+        assert_eq!(get_mapping::<i64>(""), Err((1, "expected \"<target> <source> <length>\", found \"\"".to_string())));
     }
 
     #[test]
     fn test_get_mapping_seed_line() {
-        assert_eq!(get_mapping("seeds: 79 14 55 13").is_none(), true);
+        assert_eq!(get_mapping::<i64>("seeds: 79 14 55 13"), Err((1, "invalid number \"seeds:\"".to_string())));
     }
 
     #[test]
     fn test_get_mapping_map_header_line() {
-        assert_eq!(get_mapping("seed-to-soil map:").is_none(), true);
+        assert_eq!(get_mapping::<i64>("seed-to-soil map:").is_err(), true);
+    }
+
+    #[test]
+    fn test_get_mapping_bad_number() {
+        assert_eq!(get_mapping::<i64>("50 98 two"), Err((7, "invalid number \"two\"".to_string())));
+        assert_eq!(get_mapping::<i64>("50 98 2 1").is_err(), true);
     }
 
     #[test]
     fn test_get_mapping_map_correct_line() {
-        let result = get_mapping("50 98 2");
-        assert_eq!(result.is_some(), true);
+        let result = get_mapping::<i64>("50 98 2");
+        assert_eq!(result.is_ok(), true);
         let mapping = result.unwrap();
         assert_eq!(mapping.target, 50);
         assert_eq!(mapping.source, 98);
@@ -72,24 +137,27 @@ mod get_mapping_tests {
     }
 }
 
-fn get_target(source: i64, mappings: &Vec<Mapping>) -> i64 {
+fn get_target<T: Accumulator + Ord>(source: T, mappings: &Vec<Mapping<T>>) -> Result<T, OverflowError> {
     for mapping in mappings {
-        if source >= mapping.source && source < mapping.source + mapping.len {
-            return source + mapping.target - mapping.source;
+        if source >= mapping.source {
+            let offset = checked_sub(source.clone(), mapping.source.clone())?;
+            if offset < mapping.len {
+                return checked_add(mapping.target.clone(), offset);
+            }
         }
     }
-    return source;
+    return Ok(source);
 }
 
 #[cfg(test)]
-const FIRST_MAPPING: Mapping = Mapping {
+const FIRST_MAPPING: Mapping<i64> = Mapping {
     target: 50,
     source: 98,
     len: 2,
 };
 
 #[cfg(test)]
-const SECOND_MAPPING: Mapping = Mapping {
+const SECOND_MAPPING: Mapping<i64> = Mapping {
     target: 52,
     source: 50,
     len: 48,
@@ -102,30 +170,43 @@ mod get_target_tests {
     #[test]
     fn test_get_target_98() {
         let test_mappings = vec![FIRST_MAPPING, SECOND_MAPPING];
-        assert_eq!(get_target(98, &test_mappings), 50);
+        assert_eq!(get_target(98, &test_mappings), Ok(50));
     }
 
     #[test]
     fn test_get_target_99() {
         let test_mappings = vec![FIRST_MAPPING, SECOND_MAPPING];
-        assert_eq!(get_target(99, &test_mappings), 51);
+        assert_eq!(get_target(99, &test_mappings), Ok(51));
     }
 
     #[test]
     fn test_get_target_53() {
         let test_mappings = vec![FIRST_MAPPING, SECOND_MAPPING];
-        assert_eq!(get_target(53, &test_mappings), 55);
+        assert_eq!(get_target(53, &test_mappings), Ok(55));
     }
 
     #[test]
     fn test_get_target_10() {
         let test_mappings = vec![FIRST_MAPPING, SECOND_MAPPING];
-        assert_eq!(get_target(10, &test_mappings), 10);
+        assert_eq!(get_target(10, &test_mappings), Ok(10));
+    }
+
+    #[test]
+    fn test_get_target_overflow() {
+        let test_mappings = vec![Mapping { target: i64::MAX - 1, source: 0, len: 10 }];
+        assert_eq!(get_target(1, &test_mappings), Ok(i64::MAX));
+        assert_eq!(get_target(2, &test_mappings).is_err(), true);
+    }
+
+    #[test]
+    fn test_get_target_big() {
+        let test_mappings = vec![Mapping { target: BigInt::from(i64::MAX), source: BigInt::from(0), len: BigInt::from(10) }];
+        assert_eq!(get_target(BigInt::from(2), &test_mappings), Ok(BigInt::from(i64::MAX) + 2));
     }
 }
 
-fn get_targets(sources: Vec<i64>, mappings: &Vec<Mapping>) -> Vec<i64> {
-    return sources.iter().map(|source| get_target(*source, mappings)).collect();
+fn get_targets<T: Accumulator + Ord>(sources: Vec<T>, mappings: &Vec<Mapping<T>>) -> Result<Vec<T>, OverflowError> {
+    return sources.into_iter().map(|source| get_target(source, mappings)).collect();
 }
 
 #[cfg(test)]
@@ -136,7 +217,7 @@ mod get_targets_tests {
     fn test_get_targets() {
         let test_sources = vec![79, 14, 55, 13];
         let test_mappings = vec![FIRST_MAPPING, SECOND_MAPPING];
-        let targets = get_targets(test_sources, &test_mappings);
+        let targets = get_targets(test_sources, &test_mappings).unwrap();
         assert_eq!(targets.len(), 4);
         assert_eq!(targets[0], 81);
         assert_eq!(targets[1], 14);
@@ -145,303 +226,124 @@ mod get_targets_tests {
     }
 }
 
-struct TargetRange {
-    start: i64,
-    len: i64,
-    prev_start: i64,
-    prev_len: i64,
-    next_start: i64,
-    next_len: i64,
-}
-
-fn get_target_range_for_single_mapping(source: i64, source_len: i64, mapping: Mapping) -> TargetRange {
-    let mut mapping_start = source - mapping.source;
-    let mut mapping_end = mapping_start + source_len;
-    let mut items_before = 0;
-    let mut items_after = 0;
-    if mapping_start <= mapping.len && mapping_end >= 0 {
-        if mapping_start < 0 {
-            items_before = -mapping_start;
-            mapping_start = 0;
-        }
-        if mapping_end > mapping.len {
-            items_after = mapping_end - mapping.len;
-            mapping_end = mapping.len;
-        }
-    } else {
-        mapping_start = 0;
-        mapping_end = 0;
-        items_after = source_len;
+fn seeds_to_intervals<T: Accumulator + Ord>(filename: &str, seeds: &[T]) -> Result<IntervalSet<T>, SolveError> {
+    if !seeds.len().is_multiple_of(2) {
+        return Err(SolveError::InvalidInput {
+            file: filename.to_string(),
+            message: format!("seed ranges come in start/length pairs, but {} has no length", seeds[seeds.len() - 1]),
+        });
     }
-    return TargetRange {
-        start: mapping_start + mapping.target,
-        len: mapping_end - mapping_start,
-        prev_start: source - mapping_start,
-        prev_len: items_before,
-        next_start: source + items_before + mapping_end - mapping_start,
-        next_len: items_after,
+    let mut ranges = vec![];
+    for pair in seeds.chunks_exact(2) {
+        ranges.push(pair[0].clone()..checked_add(pair[0].clone(), pair[1].clone())?);
     }
+    return Ok(IntervalSet::from_ranges(ranges));
 }
 
 #[cfg(test)]
-mod get_target_range_for_single_mapping_tests {
+mod seeds_to_intervals_tests {
     use super::*;
 
     #[test]
-    fn test_get_target_range_for_single_mapping_no_match() {
-        let result = get_target_range_for_single_mapping(20, 10, Mapping { target: 40, source: 40, len: 10 });
-        assert_eq!(result.len, 0);
-        assert_eq!(result.prev_len, 0);
-        assert_eq!(result.next_start, 20);
-        assert_eq!(result.next_len, 10);
-    }
-
-    #[test]
-    fn test_get_target_range_for_single_mapping_exact_match() {
-        let result = get_target_range_for_single_mapping(20, 10, Mapping { target: 40, source: 20, len: 10 });
-        assert_eq!(result.start, 40);
-        assert_eq!(result.len, 10);
-        assert_eq!(result.prev_len, 0);
-        assert_eq!(result.next_len, 0);
-    }
-
-    #[test]
-    fn test_get_target_range_for_single_mapping_source_enclosed() {
-        let result = get_target_range_for_single_mapping(22, 8, Mapping { target: 40, source: 20, len: 10 });
-        assert_eq!(result.start, 42);
-        assert_eq!(result.len, 8);
-        assert_eq!(result.prev_len, 0);
-        assert_eq!(result.next_len, 0);
-    }
-
-    #[test]
-    fn test_get_target_range_for_single_mapping_some_preceding_numbers() {
-        let result = get_target_range_for_single_mapping(18, 8, Mapping { target: 40, source: 20, len: 10 });
-        assert_eq!(result.start, 40);
-        assert_eq!(result.len, 6);
-        assert_eq!(result.prev_start, 18);
-        assert_eq!(result.prev_len, 2);
-        assert_eq!(result.next_len, 0);
+    fn test_seeds_to_intervals() {
+        let result = seeds_to_intervals("seeds.txt", &[79, 14, 55, 13]).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.iter().cloned().collect::<Vec<_>>(), vec![55..68, 79..93]);
     }
 
     #[test]
-    fn test_get_target_range_for_single_mapping_some_following_numbers() {
-        let result = get_target_range_for_single_mapping(24, 8, Mapping { target: 40, source: 20, len: 10 });
-        assert_eq!(result.start, 44);
-        assert_eq!(result.len, 6);
-        assert_eq!(result.prev_len, 0);
-        assert_eq!(result.next_start, 30);
-        assert_eq!(result.next_len, 2);
+    fn test_seeds_to_intervals_odd_seed() {
+        assert_eq!(seeds_to_intervals("seeds.txt", &[79, 14, 55]), Err(SolveError::InvalidInput {
+            file: "seeds.txt".to_string(),
+            message: "seed ranges come in start/length pairs, but 55 has no length".to_string(),
+        }));
     }
 
     #[test]
-    fn test_get_target_range_for_single_mapping_some_preceding_and_following_numbers() {
-        let result = get_target_range_for_single_mapping(18, 14, Mapping { target: 40, source: 20, len: 10 });
-        assert_eq!(result.start, 40);
-        assert_eq!(result.len, 10);
-        assert_eq!(result.prev_start, 18);
-        assert_eq!(result.prev_len, 2);
-        assert_eq!(result.next_start, 30);
-        assert_eq!(result.next_len, 2);
+    fn test_seeds_to_intervals_overflow() {
+        assert_eq!(seeds_to_intervals("seeds.txt", &[i64::MAX - 5, 10]).is_err(), true);
     }
 }
 
-fn partial_vector_copy(mappings: &Vec<Mapping>, start: usize) -> Vec<Mapping> {
-    let mut mappings_copy: Vec<Mapping> = vec![];
-    let mut index = start;
-    let len = mappings.len();
-    while index < len {
-        mappings_copy.push(mappings[index]);
-        index = index + 1;
-    }
-    return mappings_copy;
-}
-
-#[cfg(test)]
-mod partial_vector_copy_tests {
-    use super::*;
-
-    #[test]
-    fn test_partial_vector_copy() {
-        let source = vec![FIRST_MAPPING, SECOND_MAPPING];
-        let result = partial_vector_copy(&source, 1);
-        assert_eq!(result.len(), 1);
-        assert_eq!(result[0].source, source[1].source);
-    }
-}
-
-fn get_targets_for_map(source: i64, source_len: i64, mappings: &Vec<Mapping>) -> Vec<(i64, i64)> {
-    if mappings.len() == 0 {
-        return vec![(source, source_len)];
-    }
-    let target_range = get_target_range_for_single_mapping(source, source_len, mappings[0]);
-    let mappings_copy = partial_vector_copy(&mappings, 1);
-    let mut targets: Vec<(i64, i64)> = vec![];
-    if target_range.prev_len != 0 {
-        targets.append(&mut get_targets_for_map(target_range.prev_start, target_range.prev_len, &mappings_copy));
-    }
-    if target_range.len != 0 {
-        targets.push((target_range.start, target_range.len));
-    }
-    if target_range.next_len != 0 {
-        targets.append(&mut get_targets_for_map(target_range.next_start, target_range.next_len, &mappings_copy));
+fn get_targets_for_intervals<T: Accumulator + Ord>(sources: &IntervalSet<T>, mappings: &Vec<Mapping<T>>) -> Result<IntervalSet<T>, OverflowError> {
+    let mut targets: IntervalSet<T> = IntervalSet::new();
+    let mut unmapped = sources.clone();
+    for mapping in mappings {
+        let mapping_sources = IntervalSet::from_start_and_len(mapping.source.clone(), mapping.len.clone())?;
+        let offset = checked_sub(mapping.target.clone(), mapping.source.clone())?;
+        targets = targets.union(&sources.intersection(&mapping_sources).shift(offset)?);
+        unmapped = unmapped.difference(&mapping_sources);
     }
-    return targets;
+    return Ok(targets.union(&unmapped));
 }
 
 #[cfg(test)]
-mod get_targets_for_map_tests {
+mod get_targets_for_intervals_tests {
     use super::*;
 
     #[test]
-    fn test_get_targets_for_map() {
+    fn test_get_targets_for_intervals_single_mapping() {
         let test_mappings = vec![FIRST_MAPPING, SECOND_MAPPING];
-        let targets = get_targets_for_map(79, 14, &test_mappings);
-        assert_eq!(targets.len(), 1);
-        assert_eq!(targets[0], (81, 14));
+        let result = get_targets_for_intervals(&IntervalSet::from_start_and_len(79, 14).unwrap(), &test_mappings).unwrap();
+        assert_eq!(result.iter().cloned().collect::<Vec<_>>(), vec![81..95]);
     }
 
     #[test]
-    fn test_get_targets_for_map_two_mappings() {
+    fn test_get_targets_for_intervals_two_mappings_and_unmatched() {
         let test_mappings = vec![FIRST_MAPPING, SECOND_MAPPING];
-        let targets = get_targets_for_map(79, 20, &test_mappings);
-        assert_eq!(targets.len(), 2);
-        assert_eq!(targets[0], (81, 19));
-        assert_eq!(targets[1], (50, 1));
+        let result = get_targets_for_intervals(&IntervalSet::from_start_and_len(79, 22).unwrap(), &test_mappings).unwrap();
+        assert_eq!(result.iter().cloned().collect::<Vec<_>>(), vec![50..52, 81..101]);
     }
 
     #[test]
-    fn test_get_targets_for_map_two_mappings_and_unmatched() {
+    fn test_get_targets_for_intervals_many_sources() {
         let test_mappings = vec![FIRST_MAPPING, SECOND_MAPPING];
-        let targets = get_targets_for_map(79, 22, &test_mappings);
-        assert_eq!(targets.len(), 3);
-        assert_eq!(targets[0], (81, 19));
-        assert_eq!(targets[1], (50, 2));
-        assert_eq!(targets[2], (100, 1));
-    }
-}
-
-fn condense_ranges(ranges: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
-    let mut condensed: Vec<(i64, i64)> = vec![];
-    let mut sorted_ranges: Vec<(i64, i64)> = ranges.iter().copied().collect();
-    sorted_ranges.sort();
-    let mut index = 0;
-    let mut start = 0;
-    let mut len = 0;
-    while index < sorted_ranges.len() {
-        let (range_start, range_len) = sorted_ranges[index];
-        if range_start == start + len {
-            len = len + range_len;
-        } else {
-            if len != 0 {
-                condensed.push((start, len));
-            }
-            start = range_start;
-            len = range_len;
-        }
-        index = index + 1;
-    }
-    if len != 0 {
-        condensed.push((start, len));
-    }
-    return condensed;
-}
-
-#[cfg(test)]
-mod condense_ranges_tests {
-    use super::*;
-
-    #[test]
-    fn test_condense_ranges() {
-        let sources = vec![(81, 19), (50, 2), (100, 1)];
-        let result = condense_ranges(sources);
-        assert_eq!(result.len(), 2);
-        assert_eq!(result[0], (50, 2));
-        assert_eq!(result[1], (81, 20));
-    }
-}
-
-fn sources_to_ranges(sources: Vec<i64>) -> Vec<(i64, i64)> {
-    let mut ranges: Vec<(i64, i64)> = vec![];
-    let mut index = 0;
-    while index < sources.len() {
-        let source_start = sources[index];
-        let source_len = sources[index + 1];
-        ranges.push((source_start, source_len));
-        index += 2;
-    }
-    return ranges;
-}
-
-#[cfg(test)]
-mod sources_to_ranges_tests {
-    use super::*;
-
-    #[test]
-    fn test_sources_to_ranges() {
-        let sources = vec![79, 14, 55, 13];
-        let result = sources_to_ranges(sources);
-        assert_eq!(result.len(), 2);
-        assert_eq!(result[0], (79, 14));
-        assert_eq!(result[1], (55, 13));
-    }
-}
-
-fn get_targets_for_sources(sources: Vec<(i64, i64)>, mappings: &Vec<Mapping>) -> Vec<(i64, i64)> {
-    let mut target_ranges: Vec<(i64, i64)> = vec![];
-    for source in sources {
-        let (source_start, source_len) = source;
-        target_ranges.append(&mut get_targets_for_map(source_start, source_len, mappings));
+        let sources = seeds_to_intervals("seeds.txt", &[79, 14, 55, 13]).unwrap();
+        let result = get_targets_for_intervals(&sources, &test_mappings).unwrap();
+        assert_eq!(result.iter().cloned().collect::<Vec<_>>(), vec![57..70, 81..95]);
     }
-    return condense_ranges(target_ranges);
-}
-
-#[cfg(test)]
-mod get_targets_for_sources_tests {
-    use super::*;
 
     #[test]
-    fn test_get_targets_for_sources() {
-        let test_mappings = vec![FIRST_MAPPING, SECOND_MAPPING];
-        let sources = vec![(79, 14), (55, 13)];
-        let result = get_targets_for_sources(sources, &test_mappings);
-        assert_eq!(result.len(), 2);
-        assert_eq!(result[0], (57, 13));
-        assert_eq!(result[1], (81, 14));
-    }
-}
-
-fn get_seeds_and_maps(filename: &str) -> (Vec<i64>, Vec<Vec<Mapping>>) {
-    let mut seeds: Vec<i64> = vec![];
-    let mut maps: Vec<Vec<Mapping>> = vec![];
-    let mut current_map: Vec<Mapping> = vec![];
-    let lines = line_iterator(filename);
-    for line_string in lines {
-        let line = line_string.as_str();
-        if line.starts_with("seeds: ") {
-            seeds.append(&mut get_seed_numbers(line));
-        } else if line.ends_with("map:") {
-            if !current_map.is_empty() {
-                maps.push(current_map);
+    fn test_get_targets_for_intervals_overflow() {
+        let test_mappings = vec![Mapping { target: i64::MAX - 5, source: 0, len: 10 }];
+        assert_eq!(get_targets_for_intervals(&IntervalSet::from_start_and_len(0, 5).unwrap(), &test_mappings).unwrap().max(), Some(i64::MAX - 1));
+        assert_eq!(get_targets_for_intervals(&IntervalSet::from_start_and_len(0, 7).unwrap(), &test_mappings).is_err(), true);
+    }
+}
+
+fn get_seeds_and_maps<T: Accumulator + Ord + FromStr>(filename: &str) -> Result<Almanac<T>, SolveError> {
+    let invalid = |line_number: usize, (column, message): (usize, String)| SolveError::InvalidInput {
+        file: filename.to_string(),
+        message: format!("line {}, column {}: {}", line_number, column, message),
+    };
+    let mut seeds: Vec<T> = vec![];
+    let mut maps: Vec<Vec<Mapping<T>>> = vec![];
+    for section in read_sections(filename, is_colon_header) {
+        if section.header.is_none() {
+            for (line_number, line) in section.numbered_lines() {
+                seeds.append(&mut get_seed_numbers(line).map_err(|error| invalid(line_number, error))?);
             }
-            current_map = vec![];
-        } else if line != "" {
-            current_map.push(get_mapping(line).unwrap());
+            continue;
         }
+        let mut current_map: Vec<Mapping<T>> = vec![];
+        for (line_number, line) in section.numbered_lines() {
+            current_map.push(get_mapping(line).map_err(|error| invalid(line_number, error))?);
+        }
+        maps.push(current_map);
     }
-    maps.push(current_map);
-    return (seeds, maps);
+    debug!("{}: {} seeds, {} maps", filename, seeds.len(), maps.len());
+    return Ok((seeds, maps));
 }
 
 #[cfg(test)]
 mod get_seeds_and_maps_tests {
     use super::*;
 
-    fn mapping_eq(a: &Mapping, b: &Mapping) -> bool {
+    fn mapping_eq(a: &Mapping<i64>, b: &Mapping<i64>) -> bool {
         a.target == b.target && a.source == b.source && a.len == b.len
     }
 
-    fn vec_mapping_eq(a: &Vec<Mapping>, b: &Vec<Mapping>) -> bool {
+    fn vec_mapping_eq(a: &Vec<Mapping<i64>>, b: &Vec<Mapping<i64>>) -> bool {
         if a.len() != b.len() {
             return false;
         }
@@ -455,9 +357,25 @@ mod get_seeds_and_maps_tests {
         return true;
     }
 
+    #[test]
+    fn test_get_seeds_and_maps_malformed() {
+        assert_eq!(get_seeds_and_maps::<i64>("data/day05/malformed_test.txt"), Err(SolveError::InvalidInput {
+            file: "data/day05/malformed_test.txt".to_string(),
+            message: "line 5, column 4: invalid number \"5O\"".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_get_seeds_and_maps_out_of_range() {
+        assert_eq!(get_seeds_and_maps::<i64>("data/day05/big_test.txt"), Err(SolveError::InvalidInput {
+            file: "data/day05/big_test.txt".to_string(),
+            message: "line 1, column 8: number \"100000000000000000001\" is out of range".to_string(),
+        }));
+    }
+
     #[test]
     fn test_get_seeds_and_maps() {
-        let (seeds, maps) = get_seeds_and_maps("data/day05/test.txt");
+        let (seeds, maps) = get_seeds_and_maps::<i64>("data/day05/test.txt").unwrap();
         assert_eq!(seeds.len(), 4);
         assert_eq!(seeds[0], 79);
         assert_eq!(seeds[1], 14);
@@ -499,12 +417,14 @@ mod get_seeds_and_maps_tests {
     }
 }
 
-fn get_locations(seeds: Vec<i64>, maps: Vec<Vec<Mapping>>) -> Vec<i64> {
-    let mut targets: Vec<i64> = seeds;
-    for map in maps {
-        targets = get_targets(targets, &map);
+fn get_locations<T: Accumulator + Ord>(seeds: Vec<T>, maps: Vec<Vec<Mapping<T>>>, context: &Context) -> Result<Vec<T>, SolveError> {
+    let mut targets: Vec<T> = seeds;
+    let total = maps.len() as u64;
+    for (index, map) in maps.into_iter().enumerate() {
+        context.checkpoint("get_locations", index as u64, Some(total))?;
+        targets = get_targets(targets, &map)?;
     }
-    return targets;
+    return Ok(targets);
 }
 
 #[cfg(test)]
@@ -513,26 +433,31 @@ mod get_locations_tests {
 
     #[test]
     fn test_get_locations() {
-        let (seeds, maps) = get_seeds_and_maps("data/day05/test.txt");
-        let locations = get_locations(seeds, maps);
+        let (seeds, maps) = get_seeds_and_maps::<i64>("data/day05/test.txt").unwrap();
+        let locations = get_locations(seeds, maps, &Context::default()).unwrap();
         assert_eq!(locations[0], 82);
         assert_eq!(locations[1], 43);
         assert_eq!(locations[2], 86);
         assert_eq!(locations[3], 35);
     }
-}
 
-fn get_locations_part2(seeds: Vec<i64>, maps: Vec<Vec<Mapping>>) -> Vec<i64> {
-    let mut targets: Vec<(i64, i64)> = sources_to_ranges(seeds);
-    for map in maps {
-        targets = get_targets_for_sources(targets, &map);
+    #[test]
+    fn test_get_locations_cancelled() {
+        let (seeds, maps) = get_seeds_and_maps::<i64>("data/day05/test.txt").unwrap();
+        let context = Context::default();
+        context.cancel();
+        assert_eq!(get_locations(seeds, maps, &context), Err(SolveError::Cancelled));
     }
-    let mut result: Vec<i64> = vec![];
-    for target in targets {
-        let (target_start, _) = target;
-        result.push(target_start);
+}
+
+fn get_locations_part2<T: Accumulator + Ord>(filename: &str, seeds: Vec<T>, maps: Vec<Vec<Mapping<T>>>, context: &Context) -> Result<IntervalSet<T>, SolveError> {
+    let mut targets = seeds_to_intervals(filename, &seeds)?;
+    for (index, map) in maps.iter().enumerate() {
+        context.checkpoint("get_locations_part2", index as u64, Some(maps.len() as u64))?;
+        targets = get_targets_for_intervals(&targets, map)?;
+        trace!("after map {}: {} intervals", index + 1, targets.len());
     }
-    return result;
+    return Ok(targets);
 }
 
 #[cfg(test)]
@@ -541,20 +466,25 @@ mod get_locations_part2_tests {
 
     #[test]
     fn test_get_locations_part2() {
-        let (seeds, maps) = get_seeds_and_maps("data/day05/test.txt");
-        let locations = get_locations_part2(seeds, maps);
+        let (seeds, maps) = get_seeds_and_maps::<i64>("data/day05/test.txt").unwrap();
+        let locations = get_locations_part2("data/day05/test.txt", seeds, maps, &Context::default()).unwrap();
         assert_eq!(locations.len(), 4);
-        assert_eq!(locations[0], 46);
-        assert_eq!(locations[1], 82);
-        assert_eq!(locations[2], 86);
-        assert_eq!(locations[3], 94);
+        assert_eq!(locations.min(), Some(46));
+        let starts: Vec<i64> = locations.iter().map(|range| range.start).collect();
+        assert_eq!(starts, vec![46, 82, 86, 94]);
+    }
+
+    #[test]
+    fn test_get_locations_part2_cancelled() {
+        let (seeds, maps) = get_seeds_and_maps::<i64>("data/day05/test.txt").unwrap();
+        let context = Context::default();
+        context.cancel();
+        assert_eq!(get_locations_part2("data/day05/test.txt", seeds, maps, &context), Err(SolveError::Cancelled));
     }
 }
 
-fn min_value(values: Vec<i64>) -> i64 {
-    let mut sorted_values = values;
-    sorted_values.sort();
-    return sorted_values[0];
+fn min_value<T: Ord>(values: Vec<T>) -> Option<T> {
+    return values.into_iter().min();
 }
 
 #[cfg(test)]
@@ -563,16 +493,107 @@ mod min_value_tests {
 
     #[test]
     fn test_min_value() {
-        assert_eq!(min_value(vec![82, 43, 86, 35]), 35);
+        assert_eq!(min_value(vec![82, 43, 86, 35]), Some(35));
     }
+
+    #[test]
+    fn test_min_value_empty() {
+        assert_eq!(min_value::<i64>(vec![]), None);
+    }
+}
+
+fn no_seeds(filename: &str) -> SolveError {
+    return SolveError::InvalidInput { file: filename.to_string(), message: "no seeds to map".to_string() };
 }
 
-pub fn part1() {
-    let (seeds, maps) = get_seeds_and_maps("data/day05/input.txt");
-    println!("Day 5 Part 1 result: {}", min_value(get_locations(seeds, maps)));
+fn lowest_location<T: Accumulator + Ord + FromStr>(filename: &str, context: &Context) -> Result<T, SolveError> {
+    let (seeds, maps) = get_seeds_and_maps(filename)?;
+    return min_value(get_locations(seeds, maps, context)?).ok_or_else(|| no_seeds(filename));
 }
 
-pub fn part2() {
-    let (seeds, maps) = get_seeds_and_maps("data/day05/input.txt");
-    println!("Day 5 Part 2 result: {}", min_value(get_locations_part2(seeds, maps)));
+#[cfg(test)]
+mod lowest_location_tests {
+    use super::*;
+
+    #[test]
+    fn test_lowest_location() {
+        assert_eq!(lowest_location::<i64>("data/day05/test.txt", &Context::default()), Ok(35));
+    }
+
+    #[test]
+    fn test_lowest_location_big() {
+        assert_eq!(lowest_location::<BigInt>("data/day05/big_test.txt", &Context::default()), Ok(BigInt::from(5)));
+    }
+
+    #[test]
+    fn test_lowest_location_overflow() {
+        assert_eq!(lowest_location::<i64>("data/day05/overflow_test.txt", &Context::default()).is_err(), true);
+        assert_eq!(lowest_location::<BigInt>("data/day05/overflow_test.txt", &Context::default()), Ok(BigInt::from(i64::MAX) + 1));
+    }
+}
+
+pub fn part1(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(lowest_location::<i64>(filename, context)?),
+        Arithmetic::Big => Answer::from(lowest_location::<BigInt>(filename, context)?),
+    });
+}
+
+#[cfg(test)]
+mod part1_tests {
+    use super::*;
+
+    #[test]
+    fn test_part1_no_seeds() {
+        assert_eq!(part1("data/day05/no_seeds_test.txt", &Context::default()), Err(SolveError::InvalidInput {
+            file: "data/day05/no_seeds_test.txt".to_string(),
+            message: "no seeds to map".to_string(),
+        }));
+    }
+}
+
+fn lowest_location_part2<T: Accumulator + Ord + FromStr>(filename: &str, context: &Context) -> Result<T, SolveError> {
+    let (seeds, maps) = get_seeds_and_maps(filename)?;
+    return get_locations_part2(filename, seeds, maps, context)?.min().ok_or_else(|| no_seeds(filename));
+}
+
+#[cfg(test)]
+mod lowest_location_part2_tests {
+    use super::*;
+
+    #[test]
+    fn test_lowest_location_part2() {
+        assert_eq!(lowest_location_part2::<i64>("data/day05/test.txt", &Context::default()), Ok(46));
+    }
+
+    #[test]
+    fn test_lowest_location_part2_big() {
+        assert_eq!(lowest_location_part2::<BigInt>("data/day05/big_test.txt", &Context::default()), Ok(BigInt::from(5)));
+    }
+
+    #[test]
+    fn test_lowest_location_part2_overflow() {
+        assert_eq!(lowest_location_part2::<i64>("data/day05/overflow_test.txt", &Context::default()).is_err(), true);
+        assert_eq!(lowest_location_part2::<BigInt>("data/day05/overflow_test.txt", &Context::default()), Ok(BigInt::from(i64::MAX) + 1));
+    }
+}
+
+pub fn part2(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(lowest_location_part2::<i64>(filename, context)?),
+        Arithmetic::Big => Answer::from(lowest_location_part2::<BigInt>(filename, context)?),
+    });
+}
+
+#[cfg(test)]
+mod part2_tests {
+    use super::*;
+
+    #[test]
+    fn test_part2_no_seeds() {
+        assert_eq!(part2("data/day05/no_seeds_test.txt", &Context::default()), Err(SolveError::InvalidInput {
+            file: "data/day05/no_seeds_test.txt".to_string(),
+            message: "no seeds to map".to_string(),
+        }));
+    }
 }
@@ -1,7 +1,11 @@
-use crate::aoc_common::lib::{get_series_of_ints, line_iterator};
+use num::BigInt;
+use crate::aoc_common::answer::Answer;
+use crate::aoc_common::checked::{checked_add, checked_append_digit, checked_mul, checked_product, checked_sub, Accumulator, Arithmetic, OverflowError};
+use crate::aoc_common::context::{Context, SolveError};
+use crate::aoc_common::lib::line_iterator;
 
-fn calculate_distance(len: i64, hold: i64) -> i64 {
-    return hold * (len - hold);
+fn calculate_distance<T: Accumulator>(len: T, hold: T) -> Result<T, OverflowError> {
+    return checked_mul(hold.clone(), checked_sub(len, hold)?);
 }
 
 #[cfg(test)]
@@ -10,43 +14,81 @@ mod calculate_distance_tests {
 
     #[test]
     fn test_calculate_distance() {
-        assert_eq!(calculate_distance(7, 0), 0);
-        assert_eq!(calculate_distance(7, 1), 6);
-        assert_eq!(calculate_distance(7, 2), 10);
-        assert_eq!(calculate_distance(7, 3), 12);
-        assert_eq!(calculate_distance(7, 4), 12);
-        assert_eq!(calculate_distance(7, 5), 10);
-        assert_eq!(calculate_distance(7, 6), 6);
-        assert_eq!(calculate_distance(7, 7), 0);
-    }
-}
-
-fn smallest_time(time: i64, distance: i64) -> i64 {
-    let mut index = 1;
-    while index <= time / 2 {
-        if calculate_distance(time, index) > distance {
-            return index;
+        assert_eq!(calculate_distance(7, 0), Ok(0));
+        assert_eq!(calculate_distance(7, 1), Ok(6));
+        assert_eq!(calculate_distance(7, 2), Ok(10));
+        assert_eq!(calculate_distance(7, 3), Ok(12));
+        assert_eq!(calculate_distance(7, 4), Ok(12));
+        assert_eq!(calculate_distance(7, 5), Ok(10));
+        assert_eq!(calculate_distance(7, 6), Ok(6));
+        assert_eq!(calculate_distance(7, 7), Ok(0));
+    }
+
+    #[test]
+    fn test_calculate_distance_overflow() {
+        assert_eq!(calculate_distance(i64::MAX, i64::MAX / 2).is_err(), true);
+    }
+
+    #[test]
+    fn test_calculate_distance_big() {
+        assert_eq!(calculate_distance(BigInt::from(i64::MAX), BigInt::from(i64::MAX / 2)), Ok(BigInt::from(i64::MAX / 2) * (i64::MAX / 2 + 1)));
+    }
+}
+
+fn smallest_time<T: Accumulator + Ord + From<u8>>(time: T, distance: T, context: &Context) -> Result<T, SolveError> {
+    let half = time.clone() / T::from(2);
+    let total = half.to_string().parse::<u64>().ok();
+    let mut index = T::one();
+    let mut step: u64 = 1;
+    while index <= half {
+        context.checkpoint("smallest_time", step, total)?;
+        if calculate_distance(time.clone(), index.clone())? > distance {
+            return Ok(index);
         }
-        index = index + 1;
+        index = index + T::one();
+        step = step + 1;
     }
-    return 0;
+    return Ok(T::zero());
 }
 
 #[cfg(test)]
 mod smallest_time_tests {
+    use std::time::Duration;
     use super::*;
 
     #[test]
     fn test_smallest_time() {
-        assert_eq!(smallest_time(7, 9), 2);
-        assert_eq!(smallest_time(15, 40), 4);
-        assert_eq!(smallest_time(30, 200), 11);
+        assert_eq!(smallest_time(7, 9, &Context::default()), Ok(2));
+        assert_eq!(smallest_time(15, 40, &Context::default()), Ok(4));
+        assert_eq!(smallest_time(30, 200, &Context::default()), Ok(11));
+    }
+
+    #[test]
+    fn test_smallest_time_big() {
+        let time = BigInt::from(10).pow(20);
+        assert_eq!(smallest_time(time.clone(), time, &Context::default()), Ok(BigInt::from(2)));
+    }
+
+    #[test]
+    fn test_smallest_time_timed_out() {
+        let context = Context::default().with_timeout(Duration::ZERO);
+        assert_eq!(matches!(smallest_time(100_000, i64::MAX, &context), Err(SolveError::TimedOut(_))), true);
+    }
+
+    #[test]
+    fn test_smallest_time_cancelled() {
+        let context = Context::default();
+        context.cancel();
+        assert_eq!(smallest_time(100_000, i64::MAX, &context), Err(SolveError::Cancelled));
     }
 }
 
-fn winning_permutations(time: i64, distance: i64) -> i64 {
-    let lowest = smallest_time(time, distance);
-    return 1 + time - lowest * 2;
+fn winning_permutations<T: Accumulator + Ord + From<u8>>(time: T, distance: T, context: &Context) -> Result<T, SolveError> {
+    let lowest = smallest_time(time.clone(), distance, context)?;
+    if lowest.is_zero() {
+        return Ok(T::zero());
+    }
+    return Ok(checked_add(checked_sub(time, checked_mul(lowest, T::from(2))?)?, T::one())?);
 }
 
 #[cfg(test)]
@@ -55,18 +97,55 @@ mod winning_permutations_tests {
 
     #[test]
     fn test_winning_permutations() {
-        assert_eq!(winning_permutations(7, 9), 4);
-        assert_eq!(winning_permutations(15, 40), 8);
-        assert_eq!(winning_permutations(30, 200), 9);
+        assert_eq!(winning_permutations(7, 9, &Context::default()), Ok(4));
+        assert_eq!(winning_permutations(15, 40, &Context::default()), Ok(8));
+        assert_eq!(winning_permutations(30, 200, &Context::default()), Ok(9));
+    }
+
+    #[test]
+    fn test_winning_permutations_no_winning_hold() {
+        assert_eq!(winning_permutations(7, 12, &Context::default()), Ok(0));
+        assert_eq!(winning_permutations(0, 0, &Context::default()), Ok(0));
+    }
+
+    #[test]
+    fn test_winning_permutations_largest_time() {
+        assert_eq!(winning_permutations(i64::MAX, 0, &Context::default()), Ok(i64::MAX - 1));
+    }
+
+    #[test]
+    fn test_winning_permutations_big() {
+        let time = BigInt::from(i64::MAX) * BigInt::from(4);
+        assert_eq!(winning_permutations(time.clone(), BigInt::from(0), &Context::default()), Ok(time - 1));
     }
 }
 
-fn get_part1_data(filename: &str) -> (Vec<i64>, Vec<i64>) {
+fn get_part1_numbers<T: Accumulator + Ord + From<u8>>(line: &str) -> Result<Vec<T>, OverflowError> {
+    return line.split_whitespace().skip(1).map(get_part2_number).collect();
+}
+
+#[cfg(test)]
+mod get_part1_numbers_tests {
+    use super::*;
+
+    #[test]
+    fn test_get_part1_numbers() {
+        assert_eq!(get_part1_numbers::<i64>("Time:      7  15   30"), Ok(vec![7, 15, 30]));
+    }
+
+    #[test]
+    fn test_get_part1_numbers_overflow() {
+        assert_eq!(get_part1_numbers::<i64>("Time:  7  10000000000000000000").is_err(), true);
+        assert_eq!(get_part1_numbers::<BigInt>("Time:  7  10000000000000000000"), Ok(vec![BigInt::from(7), BigInt::from(10).pow(19)]));
+    }
+}
+
+fn get_part1_data<T: Accumulator + Ord + From<u8>>(filename: &str) -> Result<(Vec<T>, Vec<T>), OverflowError> {
     let lines = line_iterator(filename);
     let strings: Vec<String> = lines.collect();
-    let times = get_series_of_ints::<i64>(strings[0].as_str());
-    let distances = get_series_of_ints::<i64>(strings[1].as_str());
-    return (times, distances);
+    let times = get_part1_numbers(strings[0].as_str())?;
+    let distances = get_part1_numbers(strings[1].as_str())?;
+    return Ok((times, distances));
 }
 
 #[cfg(test)]
@@ -75,21 +154,21 @@ mod get_part1_data_tests {
 
     #[test]
     fn test_get_part1_data() {
-        let (times, distances) = get_part1_data("data/day06/test.txt");
+        let (times, distances) = get_part1_data::<i64>("data/day06/test.txt").unwrap();
         assert_eq!(times.len(), 3);
         assert_eq!(distances.len(), 3);
     }
 }
 
-fn get_part1_result(filename: &str) -> i64 {
-    let (times, distances) = get_part1_data(filename);
+fn get_part1_result<T: Accumulator + Ord + From<u8>>(filename: &str, context: &Context) -> Result<T, SolveError> {
+    let (times, distances) = get_part1_data::<T>(filename)?;
+    let mut permutations: Vec<T> = vec![];
     let mut index = 0;
-    let mut result = 1;
     while index < times.len() {
-        result = result * winning_permutations(times[index], distances[index]);
+        permutations.push(winning_permutations(times[index].clone(), distances[index].clone(), context)?);
         index = index + 1;
     }
-    return result;
+    return Ok(checked_product(permutations)?);
 }
 
 #[cfg(test)]
@@ -98,18 +177,29 @@ mod get_part1_result_tests {
 
     #[test]
     fn test_get_part1_result() {
-        assert_eq!(get_part1_result("data/day06/test.txt"), 288);
+        assert_eq!(get_part1_result::<i64>("data/day06/test.txt", &Context::default()), Ok(288));
+    }
+
+    #[test]
+    fn test_get_part1_result_big() {
+        assert_eq!(get_part1_result::<BigInt>("data/day06/test.txt", &Context::default()), Ok(BigInt::from(288)));
+    }
+
+    #[test]
+    fn test_get_part1_result_big_input() {
+        assert_eq!(get_part1_result::<i64>("data/day06/big_test.txt", &Context::default()).is_err(), true);
+        assert_eq!(get_part1_result::<BigInt>("data/day06/big_test.txt", &Context::default()), Ok(BigInt::from(3) * (BigInt::from(10).pow(19) - 1)));
     }
 }
 
-fn get_part2_number(line: &str) -> i64 {
-    let mut number = 0;
+fn get_part2_number<T: Accumulator + Ord + From<u8>>(line: &str) -> Result<T, OverflowError> {
+    let mut number = T::zero();
     for chr in line.chars() {
         if chr >= '0' && chr <= '9' {
-            number = number * 10 + chr as i64 - 48;
+            number = checked_append_digit(number, chr as u8 - b'0')?;
         }
     }
-    return number;
+    return Ok(number);
 }
 
 #[cfg(test)]
@@ -118,17 +208,27 @@ mod get_part2_number_tests {
 
     #[test]
     fn test_get_part2_number() {
-        assert_eq!(get_part2_number("Time:      7  15   30"), 71530);
-        assert_eq!(get_part2_number("Distance:  9  40  200"), 940200);
+        assert_eq!(get_part2_number::<i64>("Time:      7  15   30"), Ok(71530));
+        assert_eq!(get_part2_number::<i64>("Distance:  9  40  200"), Ok(940200));
+    }
+
+    #[test]
+    fn test_get_part2_number_overflow() {
+        assert_eq!(get_part2_number::<i64>("Distance:  9223372036  854775808").is_err(), true);
+    }
+
+    #[test]
+    fn test_get_part2_number_big() {
+        assert_eq!(get_part2_number::<BigInt>("Distance:  9223372036  854775808"), Ok(BigInt::from(i64::MAX) + 1));
     }
 }
 
-fn get_part2_data(filename: &str) -> (i64, i64) {
+fn get_part2_data<T: Accumulator + Ord + From<u8>>(filename: &str) -> Result<(T, T), OverflowError> {
     let lines = line_iterator(filename);
     let strings: Vec<String> = lines.collect();
-    let time = get_part2_number(strings[0].as_str());
-    let distance = get_part2_number(strings[1].as_str());
-    return (time, distance);
+    let time = get_part2_number(strings[0].as_str())?;
+    let distance = get_part2_number(strings[1].as_str())?;
+    return Ok((time, distance));
 }
 
 #[cfg(test)]
@@ -137,15 +237,15 @@ mod get_part2_data_tests {
 
     #[test]
     fn test_get_part2_data() {
-        let (time, distance) = get_part2_data("data/day06/test.txt");
+        let (time, distance) = get_part2_data::<i64>("data/day06/test.txt").unwrap();
         assert_eq!(time, 71530);
         assert_eq!(distance, 940200);
     }
 }
 
-fn get_part2_result(filename: &str) -> i64 {
-    let (time, distance) = get_part2_data(filename);
-    return winning_permutations(time, distance);
+fn get_part2_result<T: Accumulator + Ord + From<u8>>(filename: &str, context: &Context) -> Result<T, SolveError> {
+    let (time, distance) = get_part2_data::<T>(filename)?;
+    return winning_permutations(time, distance, context);
 }
 
 #[cfg(test)]
@@ -154,14 +254,31 @@ mod get_part2_result_tests {
 
     #[test]
     fn test_get_part2_result() {
-        assert_eq!(get_part2_result("data/day06/test.txt"), 71503);
+        assert_eq!(get_part2_result::<i64>("data/day06/test.txt", &Context::default()), Ok(71503));
+    }
+
+    #[test]
+    fn test_get_part2_result_big() {
+        assert_eq!(get_part2_result::<BigInt>("data/day06/test.txt", &Context::default()), Ok(BigInt::from(71503)));
+    }
+
+    #[test]
+    fn test_get_part2_result_big_input() {
+        assert_eq!(get_part2_result::<i64>("data/day06/big_test.txt", &Context::default()).is_err(), true);
+        assert_eq!(get_part2_result::<BigInt>("data/day06/big_test.txt", &Context::default()), Ok(BigInt::from(10).pow(20) + 3));
     }
 }
 
-pub fn part1() {
-    println!("Day 6 Part 1 result: {}", get_part1_result("data/day06/input.txt"));
+pub fn part1(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(get_part1_result::<i64>(filename, context)?),
+        Arithmetic::Big => Answer::from(get_part1_result::<BigInt>(filename, context)?),
+    });
 }
 
-pub fn part2() {
-    println!("Day 6 Part 2 result: {}", get_part2_result("data/day06/input.txt"));
+pub fn part2(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(get_part2_result::<i64>(filename, context)?),
+        Arithmetic::Big => Answer::from(get_part2_result::<BigInt>(filename, context)?),
+    });
 }
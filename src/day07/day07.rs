@@ -1,11 +1,18 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::LazyLock;
+use num::BigInt;
+use crate::aoc_common::answer::Answer;
+use crate::aoc_common::checked::{checked_add, checked_mul, Accumulator, Arithmetic, OverflowError};
+use crate::aoc_common::context::{Context, SolveError};
 use crate::aoc_common::lib::line_iterator;
+use crate::aoc_common::pattern::Pattern;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-struct Hand {
+struct Hand<T> {
     cards: [i8; 5],
-    bid: i64,
+    bid: T,
 }
 
 const JOKER: i8 = 0;
@@ -15,34 +22,30 @@ const QUEEN: i8 = 12;
 const KING: i8 = 13;
 const ACE: i8 = 14;
 
-fn get_hand(line: &str, jokers: bool) -> Option<Hand> {
-    let parts: Vec<&str> = line.split(' ').collect();
-    if parts.len() != 2 {
-        return None;
-    }
-    let cards: Vec<char> = parts[0].chars().collect();
+static HAND_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern::new("{cards} {bid}").unwrap());
+
+fn get_hand<T: FromStr>(line: &str, jokers: bool) -> Option<Hand<T>> {
+    let captures = HAND_PATTERN.parse(line).ok()?;
+    let cards: Vec<char> = captures.get("cards")?.chars().collect();
     if cards.len() != 5 {
         return None;
     }
-    let bid = parts[1].parse::<i64>();
+    let bid = captures.parse::<T>("bid");
     if bid.is_err() {
         return None;
     }
-    let mut hand: Hand = Hand { cards: [0; 5], bid: bid.unwrap() };
-    let mut index = 0;
-    for card in cards {
-        let value;
-        match card {
-            '2'..='9' => value = card as i8 - '0' as i8,
-            'T' => value = TEN,
-            'J' => value = match jokers { true => JOKER, false => JACK },
-            'Q' => value = QUEEN,
-            'K' => value = KING,
-            'A' => value = ACE,
+    let mut hand: Hand<T> = Hand { cards: [0; 5], bid: bid.unwrap() };
+    for (index, card) in cards.into_iter().enumerate() {
+        let value = match card {
+            '2'..='9' => card as i8 - '0' as i8,
+            'T' => TEN,
+            'J' => match jokers { true => JOKER, false => JACK },
+            'Q' => QUEEN,
+            'K' => KING,
+            'A' => ACE,
             _ => return None
-        }
+        };
         hand.cards[index] = value;
-        index += 1;
     }
     return Some(hand);
 }
@@ -53,42 +56,48 @@ mod get_hand_tests {
 
     #[test]
     fn test_get_hand_empty() {
-        assert_eq!(get_hand("", false).is_none(), true);
+        assert_eq!(get_hand::<i64>("", false).is_none(), true);
     }
 
     #[test]
     fn test_get_hand_malformed_line_missing_bid() {
-        assert_eq!(get_hand("AAAAA", false).is_none(), true);
+        assert_eq!(get_hand::<i64>("AAAAA", false).is_none(), true);
     }
 
     #[test]
     fn test_get_hand_malformed_line_too_many_args() {
-        assert_eq!(get_hand("AAAAA 123 x", false).is_none(), true);
+        assert_eq!(get_hand::<i64>("AAAAA 123 x", false).is_none(), true);
     }
 
     #[test]
     fn test_get_hand_malformed_line_too_few_cards() {
-        assert_eq!(get_hand("AAAA 123", false).is_none(), true);
+        assert_eq!(get_hand::<i64>("AAAA 123", false).is_none(), true);
     }
 
     #[test]
     fn test_get_hand_malformed_line_too_many_cards() {
-        assert_eq!(get_hand("AAAAAA 123", false).is_none(), true);
+        assert_eq!(get_hand::<i64>("AAAAAA 123", false).is_none(), true);
     }
 
     #[test]
     fn test_get_hand_malformed_line_bid_not_a_number() {
-        assert_eq!(get_hand("AAAAA 123x", false).is_none(), true);
+        assert_eq!(get_hand::<i64>("AAAAA 123x", false).is_none(), true);
+    }
+
+    #[test]
+    fn test_get_hand_bid_out_of_range() {
+        assert_eq!(get_hand::<i64>("AAAAA 10000000000000000000", false).is_none(), true);
+        assert_eq!(get_hand::<BigInt>("AAAAA 10000000000000000000", false).unwrap().bid, BigInt::from(10).pow(19));
     }
 
     #[test]
     fn test_get_hand_malformed_line_invalid_card() {
-        assert_eq!(get_hand("12345 123", false).is_none(), true);
+        assert_eq!(get_hand::<i64>("12345 123", false).is_none(), true);
     }
 
     #[test]
     fn test_get_hand_valid_bid_number_cards() {
-        assert_eq!(get_hand("24689 123", false).unwrap(), Hand {
+        assert_eq!(get_hand::<i64>("24689 123", false).unwrap(), Hand {
             cards: [ 2, 4, 6, 8, 9 ],
             bid: 123,
         });
@@ -96,7 +105,7 @@ mod get_hand_tests {
 
     #[test]
     fn test_get_hand_valid_bid_picture_cards() {
-        assert_eq!(get_hand("TJQKA 123", false).unwrap(), Hand {
+        assert_eq!(get_hand::<i64>("TJQKA 123", false).unwrap(), Hand {
             cards: [ TEN, JACK, QUEEN, KING, ACE ],
             bid: 123,
         });
@@ -104,7 +113,7 @@ mod get_hand_tests {
 
     #[test]
     fn test_get_hand_valid_bid_picture_cards_with_joker() {
-        assert_eq!(get_hand("TJQKA 123", true).unwrap(), Hand {
+        assert_eq!(get_hand::<i64>("TJQKA 123", true).unwrap(), Hand {
             cards: [ TEN, JOKER, QUEEN, KING, ACE ],
             bid: 123,
         });
@@ -365,14 +374,17 @@ mod compare_hands_tests {
     }
 }
 
-fn get_cards_by_rank(filename: &str, jokers: bool) -> Vec<Hand> {
+fn get_cards_by_rank<T: FromStr>(filename: &str, jokers: bool) -> Result<Vec<Hand<T>>, SolveError> {
     let lines = line_iterator(filename);
-    let mut hands: Vec<Hand> = vec![];
-    for line in lines {
-        hands.push(get_hand(line.as_str(), jokers).unwrap());
+    let mut hands: Vec<Hand<T>> = vec![];
+    for (index, line) in lines.enumerate() {
+        hands.push(get_hand(line.as_str(), jokers).ok_or_else(|| SolveError::InvalidInput {
+            file: filename.to_string(),
+            message: format!("line {}: invalid hand \"{}\"", index + 1, line),
+        })?);
     }
     hands.sort_by(|a, b| compare_hands(a.cards, b.cards));
-    return hands;
+    return Ok(hands);
 }
 
 #[cfg(test)]
@@ -381,7 +393,7 @@ mod get_cards_by_rank_tests {
 
     #[test]
     fn test_get_cards_by_rank() {
-        let hands = get_cards_by_rank("data/day07/test.txt", false);
+        let hands = get_cards_by_rank::<i64>("data/day07/test.txt", false).unwrap();
         assert_eq!(hands[0], Hand { cards: [3, 2, TEN, 3, KING], bid: 765 });
         assert_eq!(hands[1], Hand { cards: [KING, TEN, JACK, JACK, TEN], bid: 220 });
         assert_eq!(hands[2], Hand { cards: [KING, KING, 6, 7, 7], bid: 28 });
@@ -391,23 +403,31 @@ mod get_cards_by_rank_tests {
 
     #[test]
     fn test_get_cards_by_rank_with_jokers() {
-        let hands = get_cards_by_rank("data/day07/test.txt", true);
+        let hands = get_cards_by_rank::<i64>("data/day07/test.txt", true).unwrap();
         assert_eq!(hands[0], Hand { cards: [3, 2, TEN, 3, KING], bid: 765 });
         assert_eq!(hands[1], Hand { cards: [KING, KING, 6, 7, 7], bid: 28 });
         assert_eq!(hands[2], Hand { cards: [TEN, 5, 5, JOKER, 5], bid: 684 });
         assert_eq!(hands[3], Hand { cards: [QUEEN, QUEEN, QUEEN, JOKER, ACE], bid: 483 });
         assert_eq!(hands[4], Hand { cards: [KING, TEN, JOKER, JOKER, TEN], bid: 220 });
     }
+
+    #[test]
+    fn test_get_cards_by_rank_bid_out_of_range() {
+        assert_eq!(get_cards_by_rank::<i64>("data/day07/big_test.txt", false), Err(SolveError::InvalidInput {
+            file: "data/day07/big_test.txt".to_string(),
+            message: "line 1: invalid hand \"32T3K 10000000000000000000\"".to_string(),
+        }));
+    }
 }
 
-fn get_total_winnings(ranked_hands: Vec<Hand>) -> i64 {
-    let mut index = 1;
-    let mut total = 0;
+fn get_total_winnings<T: Accumulator>(ranked_hands: Vec<Hand<T>>) -> Result<T, OverflowError> {
+    let mut index = T::one();
+    let mut total = T::zero();
     for hand in ranked_hands {
-        total += index * hand.bid;
-        index += 1;
+        total = checked_add(total, checked_mul(index.clone(), hand.bid)?)?;
+        index = checked_add(index, T::one())?;
     }
-    return total;
+    return Ok(total);
 }
 
 #[cfg(test)]
@@ -423,7 +443,7 @@ mod get_total_winnings_tests {
             Hand { cards: [TEN, 5, 5, JACK, 5], bid: 684 },
             Hand { cards: [QUEEN, QUEEN, QUEEN, JACK, ACE], bid: 483 },
         ];
-        assert_eq!(get_total_winnings(hands), 6440);
+        assert_eq!(get_total_winnings::<i64>(hands), Ok(6440));
     }
 
     #[test]
@@ -435,14 +455,54 @@ mod get_total_winnings_tests {
             Hand { cards: [QUEEN, QUEEN, QUEEN, JOKER, ACE], bid: 483 },
             Hand { cards: [KING, TEN, JOKER, JOKER, TEN], bid: 220 },
         ];
-        assert_eq!(get_total_winnings(hands), 5905);
+        assert_eq!(get_total_winnings::<i64>(hands), Ok(5905));
+    }
+
+    #[test]
+    fn test_get_total_winnings_overflow() {
+        let hands = vec![
+            Hand { cards: [3, 2, TEN, 3, KING], bid: i64::MAX / 2 },
+            Hand { cards: [KING, KING, 6, 7, 7], bid: i64::MAX / 2 },
+        ];
+        assert_eq!(get_total_winnings::<i64>(hands).is_err(), true);
+        let hands = vec![
+            Hand { cards: [3, 2, TEN, 3, KING], bid: BigInt::from(i64::MAX / 2) },
+            Hand { cards: [KING, KING, 6, 7, 7], bid: BigInt::from(i64::MAX / 2) },
+        ];
+        assert_eq!(get_total_winnings::<BigInt>(hands), Ok(BigInt::from(i64::MAX / 2) * 3));
+    }
+}
+
+fn total_winnings<T: Accumulator + FromStr>(filename: &str, jokers: bool) -> Result<T, SolveError> {
+    return Ok(get_total_winnings(get_cards_by_rank(filename, jokers)?)?);
+}
+
+#[cfg(test)]
+mod total_winnings_tests {
+    use super::*;
+
+    #[test]
+    fn test_total_winnings() {
+        assert_eq!(total_winnings::<i64>("data/day07/test.txt", false), Ok(6440));
+        assert_eq!(total_winnings::<i64>("data/day07/test.txt", true), Ok(5905));
+    }
+
+    #[test]
+    fn test_total_winnings_big() {
+        assert_eq!(total_winnings::<BigInt>("data/day07/big_test.txt", false), Ok(BigInt::from(10).pow(19) + 2));
     }
 }
 
-pub fn part1() {
-    println!("Day 7 Part 1 result: {}", get_total_winnings(get_cards_by_rank("data/day07/input.txt", false)));
+pub fn part1(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(total_winnings::<i64>(filename, false)?),
+        Arithmetic::Big => Answer::from(total_winnings::<BigInt>(filename, false)?),
+    });
 }
 
-pub fn part2() {
-    println!("Day 7 Part 2 result: {}", get_total_winnings(get_cards_by_rank("data/day07/input.txt", true)));
+pub fn part2(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(total_winnings::<i64>(filename, true)?),
+        Arithmetic::Big => Answer::from(total_winnings::<BigInt>(filename, true)?),
+    });
 }
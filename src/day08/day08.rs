@@ -1,5 +1,15 @@
 use std::collections::HashMap;
-use crate::aoc_common::lib::line_iterator;
+use std::sync::LazyLock;
+use num::{BigInt, CheckedMul, CheckedSub, Integer, Signed};
+use crate::aoc_common::answer::Answer;
+use crate::aoc_common::checked::{checked_add, Arithmetic, OverflowError};
+use crate::aoc_common::context::{Context, SolveError};
+use crate::aoc_common::math::lcm_all;
+use crate::aoc_common::pattern::Pattern;
+use crate::aoc_common::render::Colour;
+use crate::aoc_common::sections::read_sections;
+use crate::aoc_common::terminal::paint;
+use crate::debug;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 struct Node {
@@ -51,14 +61,31 @@ mod node_id_tests {
     }
 }
 
-fn parse_node(line: &str) -> Option<Node> {
-    if line.len() != 16 {
-        return None;
+fn node_name(id: i16) -> String {
+    let letters = [id / (26 * 26), (id / 26) % 26, id % 26];
+    return letters.iter().map(|letter| (b'A' + *letter as u8) as char).collect();
+}
+
+#[cfg(test)]
+mod node_name_tests {
+    use super::*;
+
+    #[test]
+    fn test_node_name() {
+        assert_eq!(node_name(AAA_NODE), "AAA");
+        assert_eq!(node_name(ZZZ_NODE), "ZZZ");
+        assert_eq!(node_name(node_id("KTZ").unwrap()), "KTZ");
     }
-    let id = node_id(&line[0..3]);
-    let left = node_id(&line[7..10]);
-    let right = node_id(&line[12..15]);
-    return Some(Node { id: id.unwrap(), left: left.unwrap(), right: right.unwrap() })
+}
+
+static NODE_PATTERN: LazyLock<Pattern> = LazyLock::new(|| Pattern::new("{node} = ({left}, {right})").unwrap());
+
+fn parse_node(line: &str) -> Option<Node> {
+    let captures = NODE_PATTERN.parse(line).ok()?;
+    let id = node_id(captures.get("node")?)?;
+    let left = node_id(captures.get("left")?)?;
+    let right = node_id(captures.get("right")?)?;
+    return Some(Node { id, left, right })
 }
 
 #[cfg(test)]
@@ -73,6 +100,13 @@ mod parse_node_tests {
             right: node_id("CCC").unwrap(),
         }))
     }
+
+    #[test]
+    fn parse_malformed_node() {
+        assert_eq!(parse_node(""), None);
+        assert_eq!(parse_node("AAA = BBB, CCC"), None);
+        assert_eq!(parse_node("AAA = (BBB, cCC)"), None);
+    }
 }
 
 const Z: i16 = 25;
@@ -103,29 +137,61 @@ mod is_unfinished_tests {
     }
 }
 
-fn count_steps(path: &str, nodes: &HashMap<i16, Node>, path_start: usize, start_node: i16, any_z: bool) -> Option<i64> {
+fn draw_step(path_chars: &Vec<char>, path_index: usize, node: i16, step_count: i64, ansi: bool) -> String {
+    if !ansi {
+        return format!("step {}: {} (instruction {})", step_count, node_name(node), path_index);
+    }
+    let instructions: String = path_chars.iter().enumerate().map(|(index, chr)| {
+        paint(&chr.to_string(), if index == path_index { Colour::RED } else { Colour::GREY }, true)
+    }).collect();
+    return format!("{}\n{}  step {}", instructions, paint(&node_name(node), Colour::YELLOW, true), step_count);
+}
+
+#[cfg(test)]
+mod draw_step_tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_step_plain() {
+        assert_eq!(draw_step(&vec!['L', 'R'], 1, node_id("BBB").unwrap(), 3, false), "step 3: BBB (instruction 1)");
+    }
+
+    #[test]
+    fn test_draw_step_ansi() {
+        let frame = draw_step(&vec!['L', 'R'], 1, node_id("BBB").unwrap(), 3, true);
+        assert_eq!(frame.starts_with(&paint("L", Colour::GREY, true)), true);
+        assert_eq!(frame.contains(&paint("R", Colour::RED, true)), true);
+        assert_eq!(frame.ends_with(&format!("{}  step 3", paint("BBB", Colour::YELLOW, true))), true);
+    }
+}
+
+fn count_steps(path: &str, nodes: &HashMap<i16, Node>, path_start: usize, start_node: i16, any_z: bool, context: &Context) -> Result<Option<i64>, SolveError> {
     let path_chars: Vec<char> = path.chars().collect();
     let mut path_index = path_start;
     let mut current_node = start_node;
-    let mut step_count = 0;
+    let mut step_count: i64 = 0;
     while is_unfinished(current_node, any_z) || step_count == 0 {
         let node = nodes.get(&current_node);
         if node.is_none() {
-            return None;
+            return Ok(None);
         }
         let dir = path_chars[path_index];
         match dir {
             'L' => current_node = node.unwrap().left,
             'R' => current_node = node.unwrap().right,
-            _ => return None,
+            _ => return Ok(None),
+        }
+        step_count = checked_add(step_count, 1)?;
+        context.checkpoint("count_steps", step_count as u64, None)?;
+        if let Some(animation) = &context.animation {
+            animation.frame(step_count as usize, |ansi| draw_step(&path_chars, path_index, current_node, step_count, ansi));
         }
-        step_count += 1;
         path_index += 1;
         if path_index == path_chars.len() {
             path_index = 0;
         }
     }
-    return Some(step_count);
+    return Ok(Some(step_count));
 }
 
 #[cfg(test)]
@@ -149,7 +215,7 @@ mod count_steps_tests {
             left: ZZZ_NODE,
             right: ggg_node,
         });
-        assert_eq!(count_steps(path, &nodes, 0, AAA_NODE, false), Some(2));
+        assert_eq!(count_steps(path, &nodes, 0, AAA_NODE, false, &Context::default()), Ok(Some(2)));
     }
 
     #[test]
@@ -167,15 +233,15 @@ mod count_steps_tests {
             left: AAA_NODE,
             right: ZZZ_NODE,
         });
-        assert_eq!(count_steps(path, &nodes, 0, AAA_NODE, false), Some(6));
+        assert_eq!(count_steps(path, &nodes, 0, AAA_NODE, false, &Context::default()), Ok(Some(6)));
     }
 }
 
 fn read_file(filename: &str) -> (String, HashMap<i16, Node>) {
-    let mut lines = line_iterator(filename);
-    let path = lines.next().unwrap();
+    let sections = read_sections(filename, |_| false);
+    let path = sections[0].lines[0].clone();
     let mut nodes: HashMap<i16, Node> = HashMap::new();
-    for line in lines {
+    for line in sections.iter().skip(1).flat_map(|section| section.lines.iter()) {
         let maybe_node = parse_node(line.as_str());
         if maybe_node.is_some() {
             let node = maybe_node.unwrap();
@@ -229,9 +295,9 @@ mod read_file_tests {
     }
 }
 
-fn count_steps_for_part1(filename: &str) -> i64 {
+fn count_steps_for_part1(filename: &str, context: &Context) -> Result<i64, SolveError> {
     let (path, nodes) = read_file(filename);
-    return count_steps(path.as_str(), &nodes, 0, AAA_NODE, false).unwrap();
+    return Ok(count_steps(path.as_str(), &nodes, 0, AAA_NODE, false, context)?.unwrap());
 }
 
 #[cfg(test)]
@@ -240,17 +306,17 @@ mod count_steps_for_part1_tests {
 
     #[test]
     fn test_count_steps_for_part1_using_test1() {
-        assert_eq!(count_steps_for_part1("data/day08/test1.txt"), 2);
+        assert_eq!(count_steps_for_part1("data/day08/test1.txt", &Context::default()), Ok(2));
     }
 
     #[test]
     fn test_count_steps_for_part1_using_test2() {
-        assert_eq!(count_steps_for_part1("data/day08/test2.txt"), 6);
+        assert_eq!(count_steps_for_part1("data/day08/test2.txt", &Context::default()), Ok(6));
     }
 }
 
 fn find_starting_nodes(nodes: &HashMap<i16, Node>) -> Vec<i16> {
-    return nodes.keys().filter_map(|a| match a % 26 { 0 => Some(a), _ => None }).map(|a| *a).collect();
+    return nodes.keys().filter(|a| *a % 26 == 0).copied().collect();
 }
 
 #[cfg(test)]
@@ -270,95 +336,17 @@ mod find_starting_nodes_tests {
     }
 }
 
-fn factors(initial_number: i64) -> HashMap<i64, i64> {
-    let mut factors: HashMap<i64, i64> = HashMap::new();
-    let root = (initial_number as f64).sqrt() as i64;
-    let mut number = initial_number;
-    let mut index = 2;
-    while index <= root {
-        let mut count = 0;
-        while number % index == 0 {
-            number = number / index;
-            count = count + 1;
-        }
-        if count != 0 {
-            factors.insert(index, count);
-        }
-        index = index + 1;
-    }
-    if number > 1 {
-        factors.insert(number, 1);
-    }
-    return factors;
-}
-
-#[cfg(test)]
-mod factors_tests {
-    use super::*;
-
-    #[test]
-    fn test_factors() {
-        let fac = factors(140);
-        assert_eq!(fac.len(), 3);
-        assert_eq!(fac.get(&2).unwrap(), &2);
-        assert_eq!(fac.get(&5).unwrap(), &1);
-        assert_eq!(fac.get(&7).unwrap(), &1);
-    }
-}
-
-fn lcm(a: i64, b: i64) -> i64 {
-    let mut common_factors: HashMap<i64, i64> = HashMap::new();
-    let factors_in_a = factors(a);
-    let factors_in_b = factors(b);
-    for (a_factor, a_factor_count) in factors_in_a {
-        let b_factor_value_maybe = factors_in_b.get(&a_factor);
-        if b_factor_value_maybe.is_none() {
-            common_factors.insert(a_factor, a_factor_count);
-        } else {
-            let b_factor_count = *b_factor_value_maybe.unwrap();
-            if a_factor_count > b_factor_count {
-                common_factors.insert(a_factor, a_factor_count);
-            } else {
-                common_factors.insert(a_factor, b_factor_count);
-            }
-        }
-    }
-    for (b_factor, b_factor_count) in factors_in_b {
-        let existing_value = common_factors.get(&b_factor);
-        if existing_value.is_none() {
-            common_factors.insert(b_factor, b_factor_count);
-        }
-    }
-    let mut result = 1;
-    for (factor, count) in common_factors {
-        result = result * factor * count;
-    }
-    return result;
-}
-
-#[cfg(test)]
-mod lcm_tests {
-    use super::*;
-
-    #[test]
-    fn test_lcm() {
-        assert_eq!(lcm(6, 4), 12);
-    }
-}
-
-fn count_steps_for_part2(filename: &str) -> i64 {
+fn count_steps_for_part2<T: Clone + Integer + Signed + CheckedMul + CheckedSub + From<i64>>(filename: &str, context: &Context) -> Result<T, SolveError> {
     let (path, nodes) = read_file(filename);
     let mut starting_nodes = find_starting_nodes(&nodes);
     starting_nodes.sort();
-    let mut steps: Vec<i64> = vec![];
+    let mut steps: Vec<T> = vec![];
     for starting_node in find_starting_nodes(&nodes) {
-        steps.push(count_steps(path.as_str(), &nodes, 0, starting_node, true).unwrap());
-    }
-    let mut min_steps = 1;
-    for count in steps {
-        min_steps = lcm(min_steps, count);
+        let count = count_steps(path.as_str(), &nodes, 0, starting_node, true, context)?.unwrap();
+        debug!("{} reaches a Z node after {} steps", node_name(starting_node), count);
+        steps.push(T::from(count));
     }
-    return min_steps;
+    return lcm_all(steps).ok_or_else(|| SolveError::Overflow(OverflowError { operation: "least common multiple of step counts".to_string() }));
 }
 
 #[cfg(test)]
@@ -367,14 +355,22 @@ mod count_steps_for_part2_tests {
 
     #[test]
     fn test_count_steps_for_part2_test3() {
-        assert_eq!(count_steps_for_part2("data/day08/test3.txt"), 6);
+        assert_eq!(count_steps_for_part2::<i64>("data/day08/test3.txt", &Context::default()), Ok(6));
+    }
+
+    #[test]
+    fn test_count_steps_for_part2_big() {
+        assert_eq!(count_steps_for_part2::<BigInt>("data/day08/test3.txt", &Context::default()), Ok(BigInt::from(6)));
     }
 }
 
-pub fn part1() {
-    println!("Day 8 Part 1 result: {}", count_steps_for_part1("data/day08/input.txt"));
+pub fn part1(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(Answer::from(count_steps_for_part1(filename, context)?));
 }
 
-pub fn part2() {
-    println!("Day 8 Part 2 result: {}", count_steps_for_part2("data/day08/input.txt"));
+pub fn part2(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(count_steps_for_part2::<i64>(filename, context)?),
+        Arithmetic::Big => Answer::from(count_steps_for_part2::<BigInt>(filename, context)?),
+    });
 }
@@ -1,11 +1,11 @@
-use crate::aoc_common::lib::{get_series_of_ints, sum, sum_up};
-
-fn dereference_i64(reference: &i64) -> i64 {
-    return *reference;
-}
+use num::BigInt;
+use crate::aoc_common::answer::Answer;
+use crate::aoc_common::checked::{checked_add, checked_sub, Accumulator, Arithmetic, OverflowError};
+use crate::aoc_common::context::{Context, SolveError};
+use crate::aoc_common::lib::{get_series_of_ints, sum_up};
 
 fn is_sequence_all_zeroes(sequence: &Vec<i64>) -> bool {
-    return sequence.iter().map(dereference_i64).reduce(sum).unwrap() == 0;
+    return sequence.iter().all(|n| *n == 0);
 }
 
 #[cfg(test)]
@@ -18,6 +18,18 @@ mod is_sequence_all_zeroes_tests {
         assert_eq!(is_sequence_all_zeroes(&sequence), false);
     }
 
+    #[test]
+    fn test_is_sequence_all_zeroes_sums_to_zero() {
+        let sequence: Vec<i64> = vec![1, -1];
+        assert_eq!(is_sequence_all_zeroes(&sequence), false);
+    }
+
+    #[test]
+    fn test_is_sequence_all_zeroes_large_values() {
+        let sequence: Vec<i64> = vec![i64::MAX, i64::MAX];
+        assert_eq!(is_sequence_all_zeroes(&sequence), false);
+    }
+
     #[test]
     fn test_is_sequence_all_zeroes_zero() {
         let sequence: Vec<i64> = vec![0, 0, 0, 0, 0];
@@ -25,15 +37,15 @@ mod is_sequence_all_zeroes_tests {
     }
 }
 
-fn get_next_sequence(sequence: &Vec<i64>) -> (Vec<i64>, bool) {
+fn get_next_sequence(sequence: &Vec<i64>) -> Result<(Vec<i64>, bool), OverflowError> {
     let mut index: usize = 1;
     let mut next_sequence: Vec<i64> = vec![];
     while index < sequence.len() {
-        next_sequence.push(sequence[index] - sequence[index - 1]);
+        next_sequence.push(checked_sub(sequence[index], sequence[index - 1])?);
         index = index + 1;
     }
     let done = is_sequence_all_zeroes(&next_sequence);
-    return (next_sequence, done);
+    return Ok((next_sequence, done));
 }
 
 #[cfg(test)]
@@ -44,43 +56,48 @@ mod get_next_sequence_tests {
     fn test_get_next_sequence_flat() {
         let initial_sequence: Vec<i64> = vec![3, 3, 3, 3, 3, 3];
         let expected_next_sequence: Vec<i64> = vec![0, 0, 0, 0, 0];
-        assert_eq!(get_next_sequence(&initial_sequence), (expected_next_sequence, true));
+        assert_eq!(get_next_sequence(&initial_sequence), Ok((expected_next_sequence, true)));
     }
 
     #[test]
     fn test_get_next_sequence_increment_by_three() {
         let initial_sequence: Vec<i64> = vec![0, 3, 6, 9, 12, 15];
         let expected_next_sequence: Vec<i64> = vec![3, 3, 3, 3, 3];
-        assert_eq!(get_next_sequence(&initial_sequence), (expected_next_sequence, false));
+        assert_eq!(get_next_sequence(&initial_sequence), Ok((expected_next_sequence, false)));
     }
 
     #[test]
     fn test_get_next_sequence_increment_increasing() {
         let initial_sequence: Vec<i64> = vec![1, 3, 6, 10, 15, 21];
         let expected_next_sequence: Vec<i64> = vec![2, 3, 4, 5, 6];
-        assert_eq!(get_next_sequence(&initial_sequence), (expected_next_sequence, false));
+        assert_eq!(get_next_sequence(&initial_sequence), Ok((expected_next_sequence, false)));
     }
 
     #[test]
     fn test_get_next_sequence_increment_another_pattern() {
         let initial_sequence: Vec<i64> = vec![10, 13, 16, 21, 30, 45];
         let expected_next_sequence: Vec<i64> = vec![3, 3, 5, 9, 15];
-        assert_eq!(get_next_sequence(&initial_sequence), (expected_next_sequence, false));
+        assert_eq!(get_next_sequence(&initial_sequence), Ok((expected_next_sequence, false)));
+    }
+
+    #[test]
+    fn test_get_next_sequence_overflow() {
+        assert_eq!(get_next_sequence(&vec![i64::MIN, i64::MAX]).is_err(), true);
     }
 }
 
-fn get_sequences(line: &str) -> Vec<Vec<i64>> {
+fn get_sequences(line: &str) -> Result<Vec<Vec<i64>>, OverflowError> {
     let mut sequences: Vec<Vec<i64>> = vec![];
     sequences.push(get_series_of_ints::<i64>(line));
     let mut done = false;
     while !done {
-        let (next_sequence, done2) = get_next_sequence(&sequences.last().unwrap());
+        let (next_sequence, done2) = get_next_sequence(sequences.last().unwrap())?;
         done = done2;
         if !done {
             sequences.push(next_sequence);
         }
     }
-    return sequences;
+    return Ok(sequences);
 }
 
 #[cfg(test)]
@@ -90,23 +107,29 @@ mod get_sequences_tests {
     #[test]
     fn test_get_sequences() {
         let initial_sequence = "1 3 6 10 15 21";
-        let result = get_sequences(initial_sequence);
+        let result = get_sequences(initial_sequence).unwrap();
         assert_eq!(result.len(), 3);
         assert_eq!(result[0], vec![1, 3, 6, 10, 15, 21]);
         assert_eq!(result[1], vec![2, 3, 4, 5, 6]);
         assert_eq!(result[2], vec![1, 1, 1, 1]);
     }
+
+    #[test]
+    fn test_get_sequences_differences_summing_to_zero() {
+        let result = get_sequences("0 1 0").unwrap();
+        assert_eq!(result, vec![vec![0, 1, 0], vec![1, -1], vec![-2]]);
+    }
 }
 
-fn extrapolate_next_value(sequences: Vec<Vec<i64>>) -> i64 {
+fn extrapolate_next_value(sequences: Vec<Vec<i64>>) -> Result<i64, OverflowError> {
     let mut index = sequences.len();
     let mut result = 0;
     while index > 0 {
         index = index - 1;
         let last = *sequences[index].last().unwrap();
-        result = last + result;
+        result = checked_add(last, result)?;
     }
-    return result;
+    return Ok(result);
 }
 
 #[cfg(test)]
@@ -120,12 +143,12 @@ mod extrapolate_next_value_tests {
             vec![2, 3, 4, 5, 6],
             vec![1, 1, 1, 1],
         ];
-        assert_eq!(extrapolate_next_value(sequences), 28);
+        assert_eq!(extrapolate_next_value(sequences), Ok(28));
     }
 }
 
-fn solve_for_part1(filename: &str) -> i64 {
-    return sum_up(filename, |a| extrapolate_next_value(get_sequences(a)), sum);
+fn solve_for_part1<T: Accumulator + From<i64>>(filename: &str) -> Result<T, OverflowError> {
+    return sum_up(filename, |a| extrapolate_next_value(get_sequences(a)?));
 }
 
 #[cfg(test)]
@@ -134,19 +157,19 @@ mod solve_for_part1_tests {
 
     #[test]
     fn test_solve_for_part1() {
-        assert_eq!(solve_for_part1("data/day09/test.txt"), 114);
+        assert_eq!(solve_for_part1::<i64>("data/day09/test.txt"), Ok(114));
     }
 }
 
-fn extrapolate_previous_value(sequences: Vec<Vec<i64>>) -> i64 {
+fn extrapolate_previous_value(sequences: Vec<Vec<i64>>) -> Result<i64, OverflowError> {
     let mut index = sequences.len();
     let mut result = 0;
     while index > 0 {
         index = index - 1;
         let last = *sequences[index].first().unwrap();
-        result = last - result;
+        result = checked_sub(last, result)?;
     }
-    return result;
+    return Ok(result);
 }
 
 #[cfg(test)]
@@ -161,12 +184,12 @@ mod extrapolate_previous_value_tests {
             vec![0, 2, 4, 6],
             vec![2, 2, 2],
         ];
-        assert_eq!(extrapolate_previous_value(sequences), 5);
+        assert_eq!(extrapolate_previous_value(sequences), Ok(5));
     }
 }
 
-fn solve_for_part2(filename: &str) -> i64 {
-    return sum_up(filename, |a| extrapolate_previous_value(get_sequences(a)), sum);
+fn solve_for_part2<T: Accumulator + From<i64>>(filename: &str) -> Result<T, OverflowError> {
+    return sum_up(filename, |a| extrapolate_previous_value(get_sequences(a)?));
 }
 
 #[cfg(test)]
@@ -175,14 +198,25 @@ mod solve_for_part2_tests {
 
     #[test]
     fn test_solve_for_part2() {
-        assert_eq!(solve_for_part2("data/day09/test.txt"), 2);
+        assert_eq!(solve_for_part2::<i64>("data/day09/test.txt"), Ok(2));
+    }
+
+    #[test]
+    fn test_solve_for_part2_big() {
+        assert_eq!(solve_for_part2::<BigInt>("data/day09/test.txt"), Ok(BigInt::from(2)));
     }
 }
 
-pub fn part1() {
-    println!("Day 9 Part 1 result: {}", solve_for_part1("data/day09/input.txt"));
+pub fn part1(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(solve_for_part1::<i64>(filename)?),
+        Arithmetic::Big => Answer::from(solve_for_part1::<BigInt>(filename)?),
+    });
 }
 
-pub fn part2() {
-    println!("Day 9 Part 2 result: {}", solve_for_part2("data/day09/input.txt"));
+pub fn part2(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    return Ok(match context.arithmetic {
+        Arithmetic::Checked => Answer::from(solve_for_part2::<i64>(filename)?),
+        Arithmetic::Big => Answer::from(solve_for_part2::<BigInt>(filename)?),
+    });
 }
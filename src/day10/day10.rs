@@ -1,4 +1,12 @@
+use std::collections::HashSet;
+use std::io;
+use crate::aoc_common::answer::Answer;
+use crate::aoc_common::context::{Context, SolveError};
+use crate::aoc_common::geometry::{Direction, Point};
 use crate::aoc_common::lib::line_iterator;
+use crate::aoc_common::render::{write_image, Colour};
+use crate::aoc_common::terminal::paint;
+use crate::debug;
 
 #[derive(Debug, Default, Eq, PartialEq)]
 struct PipeSection {
@@ -26,12 +34,6 @@ impl PipeSection {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct Position { x: usize, y: usize }
-
-#[derive(Debug, Eq, PartialEq)]
-enum Direction { NORTH, EAST, SOUTH, WEST }
-
 #[cfg(test)]
 mod pipe_section_constructor_tests {
     use super::*;
@@ -134,11 +136,11 @@ mod get_map_tests {
     }
 }
 
-fn find_start(map: &Vec<Vec<PipeSection>>) -> Option<Position> {
+fn find_start(map: &Vec<Vec<PipeSection>>) -> Option<Point> {
     for y in 0..map.len() {
         for x in 0..map[y].len() {
             if map[y][x].start {
-                return Some(Position { x, y });
+                return Some(Point::new(x as i64, y as i64));
             }
         }
     }
@@ -156,64 +158,66 @@ mod find_start_tests {
 
     #[test]
     fn test_find_start_test1() {
-        assert_eq!(find_start(&get_map("data/day10/test1.txt")), Some(Position { x: 1, y: 1 }));
+        assert_eq!(find_start(&get_map("data/day10/test1.txt")), Some(Point::new(1, 1)));
     }
 
     #[test]
     fn test_find_start_test3() {
-        assert_eq!(find_start(&get_map("data/day10/test3.txt")), Some(Position { x: 0, y: 2 }));
+        assert_eq!(find_start(&get_map("data/day10/test3.txt")), Some(Point::new(0, 2)));
     }
 }
 
-fn move_dir(from: &Position, dir: &Direction) -> Position {
-    match dir {
-        Direction::NORTH => Position { x: from.x, y: from.y - 1 },
-        Direction::EAST  => Position { x: from.x + 1, y: from.y },
-        Direction::SOUTH => Position { x: from.x, y: from.y + 1 },
-        Direction::WEST  => Position { x: from.x - 1, y: from.y },
+fn tile_at<'a>(map: &'a Vec<Vec<PipeSection>>, position: &Point) -> Option<&'a PipeSection> {
+    if position.x < 0 || position.y < 0 {
+        return None;
     }
+    return map.get(position.y as usize).and_then(|row| row.get(position.x as usize));
 }
 
 #[cfg(test)]
-mod move_dir_tests {
+mod tile_at_tests {
     use super::*;
 
     #[test]
-    fn test_move_north() {
-        assert_eq!(move_dir(&Position { x: 4, y: 7 }, &Direction::NORTH), Position { x: 4, y: 6 });
+    fn test_tile_at_start() {
+        assert_eq!(tile_at(&get_map("data/day10/test1.txt"), &Point::new(1, 1)), Some(&PipeSection::new('S')));
     }
 
     #[test]
-    fn test_move_east() {
-        assert_eq!(move_dir(&Position { x: 4, y: 7 }, &Direction::EAST), Position { x: 5, y: 7 });
+    fn test_tile_at_north_of_map() {
+        assert_eq!(tile_at(&get_map("data/day10/test1.txt"), &Point::new(1, -1)), None);
     }
 
     #[test]
-    fn test_move_south() {
-        assert_eq!(move_dir(&Position { x: 4, y: 7 }, &Direction::SOUTH), Position { x: 4, y: 8 });
+    fn test_tile_at_west_of_map() {
+        assert_eq!(tile_at(&get_map("data/day10/test1.txt"), &Point::new(-1, 1)), None);
     }
 
     #[test]
-    fn test_move_west() {
-        assert_eq!(move_dir(&Position { x: 4, y: 7 }, &Direction::WEST), Position { x: 3, y: 7 });
+    fn test_tile_at_south_east_of_map() {
+        assert_eq!(tile_at(&get_map("data/day10/test1.txt"), &Point::new(5, 5)), None);
     }
 }
 
-fn can_move(map: &Vec<Vec<PipeSection>>, from: &Position, dir: &Direction) -> bool {
-    let next_position = move_dir(from, dir);
-    if next_position.y >= map.len() || next_position.x >= map[next_position.y].len() {
+fn connects(pipe_section: &PipeSection, dir: Direction) -> bool {
+    match dir {
+        Direction::North => pipe_section.north,
+        Direction::East => pipe_section.east,
+        Direction::South => pipe_section.south,
+        Direction::West => pipe_section.west,
+    }
+}
+
+fn can_move(map: &Vec<Vec<PipeSection>>, from: &Point, dir: &Direction) -> bool {
+    let next_pipe_section = tile_at(map, &from.step(*dir));
+    if next_pipe_section.is_none() {
         return false;
     }
-    let next_pipe_section = &map[next_position.y][next_position.x];
+    let next_pipe_section = next_pipe_section.unwrap();
     if next_pipe_section.start {
         return true;
     }
-    match dir {
-        Direction::NORTH => next_pipe_section.south,
-        Direction::EAST => next_pipe_section.west,
-        Direction::SOUTH => next_pipe_section.north,
-        Direction::WEST => next_pipe_section.east,
-    }
+    return connects(next_pipe_section, dir.opposite());
 }
 
 #[cfg(test)]
@@ -222,54 +226,52 @@ mod can_move_tests {
 
     #[test]
     fn test_can_move_north_from_start() {
-        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Position { x: 1, y: 1 }, &Direction::NORTH), false);
+        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Point::new(1, 1), &Direction::North), false);
     }
 
     #[test]
     fn test_can_move_east_from_start() {
-        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Position { x: 1, y: 1 }, &Direction::EAST), true);
+        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Point::new(1, 1), &Direction::East), true);
     }
 
     #[test]
     fn test_can_move_south_from_start() {
-        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Position { x: 1, y: 1 }, &Direction::SOUTH), true);
+        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Point::new(1, 1), &Direction::South), true);
     }
 
     #[test]
     fn test_can_move_west_from_start() {
-        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Position { x: 1, y: 1 }, &Direction::WEST), false);
+        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Point::new(1, 1), &Direction::West), false);
     }
 
     #[test]
     fn test_can_move_west_to_start() {
-        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Position { x: 2, y: 1 }, &Direction::WEST), true);
+        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Point::new(2, 1), &Direction::West), true);
     }
 
     #[test]
     fn test_can_move_west_from_top_right_to_pipe() {
-        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Position { x: 3, y: 1 }, &Direction::WEST), true);
+        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Point::new(3, 1), &Direction::West), true);
     }
 
     #[test]
     fn test_can_move_south_from_top_right_to_pipe() {
-        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Position { x: 3, y: 1 }, &Direction::SOUTH), true);
+        assert_eq!(can_move(&get_map("data/day10/test1.txt"), &Point::new(3, 1), &Direction::South), true);
     }
-}
 
-fn next_dir(pipe_section: &PipeSection, from_dir: &Direction) -> Option<Direction> {
-    if *from_dir != Direction::SOUTH && pipe_section.north {
-        return Some(Direction::NORTH);
-    }
-    if *from_dir != Direction::WEST && pipe_section.east {
-        return Some(Direction::EAST);
-    }
-    if *from_dir != Direction::NORTH && pipe_section.south {
-        return Some(Direction::SOUTH);
+    #[test]
+    fn test_can_move_west_from_start_on_left_edge() {
+        assert_eq!(can_move(&get_map("data/day10/test3.txt"), &Point::new(0, 2), &Direction::West), false);
     }
-    if *from_dir != Direction::EAST && pipe_section.west {
-        return Some(Direction::WEST);
+
+    #[test]
+    fn test_can_move_north_from_top_row() {
+        assert_eq!(can_move(&get_map("data/day10/test3.txt"), &Point::new(2, 0), &Direction::North), false);
     }
-    return None;
+}
+
+fn next_dir(pipe_section: &PipeSection, from_dir: &Direction) -> Option<Direction> {
+    return Direction::ALL.into_iter().find(|dir| *dir != from_dir.opposite() && connects(pipe_section, *dir));
 }
 
 #[cfg(test)]
@@ -278,42 +280,122 @@ mod next_dir_tests {
 
     #[test]
     fn test_next_dir_south_west_pipe_from_south() {
-        assert_eq!(next_dir(&PipeSection::new('7'), &Direction::NORTH), Some(Direction::WEST));
+        assert_eq!(next_dir(&PipeSection::new('7'), &Direction::North), Some(Direction::West));
     }
 
     #[test]
     fn test_next_dir_south_west_pipe_from_west() {
-        assert_eq!(next_dir(&PipeSection::new('7'), &Direction::EAST), Some(Direction::SOUTH));
+        assert_eq!(next_dir(&PipeSection::new('7'), &Direction::East), Some(Direction::South));
     }
 
     #[test]
     fn test_next_dir_start() {
-        assert_eq!(next_dir(&PipeSection::new('S'), &Direction::WEST), None);
+        assert_eq!(next_dir(&PipeSection::new('S'), &Direction::West), None);
     }
 }
 
-fn count_loop_steps(map: &Vec<Vec<PipeSection>>, start: &Position, dir: Direction) -> Option<usize> {
-    if !can_move(&map, &start, &dir) {
-        return None;
+fn draw_loop_walk(map: &Vec<Vec<PipeSection>>, visited: &HashSet<Point>, step: usize, position: &Point, ansi: bool) -> String {
+    if !ansi {
+        return format!("step {}: ({}, {}) {}", step, position.x, position.y, tile_at(map, position).map_or('?', |tile| tile.chr));
+    }
+    let mut lines: Vec<String> = vec![];
+    for y in 0..map.len() {
+        let mut line = String::new();
+        for x in 0..map[y].len() {
+            let point = Point::new(x as i64, y as i64);
+            let colour = if point == *position {
+                Colour::RED
+            } else if visited.contains(&point) {
+                Colour::YELLOW
+            } else {
+                Colour::GREY
+            };
+            line.push_str(&paint(&map[y][x].chr.to_string(), colour, true));
+        }
+        lines.push(line);
+    }
+    lines.push(format!("step {}", step));
+    return lines.join("\n");
+}
+
+#[cfg(test)]
+mod draw_loop_walk_tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_loop_walk_plain() {
+        let map = get_map("data/day10/test1.txt");
+        let visited = HashSet::from([Point::new(1, 1), Point::new(2, 1)]);
+        assert_eq!(draw_loop_walk(&map, &visited, 2, &Point::new(3, 1), false), "step 2: (3, 1) 7");
+    }
+
+    #[test]
+    fn test_draw_loop_walk_ansi() {
+        let map = get_map("data/day10/test1.txt");
+        let visited = HashSet::from([Point::new(1, 1)]);
+        let frame = draw_loop_walk(&map, &visited, 1, &Point::new(2, 1), true);
+        assert_eq!(frame.lines().count(), 6);
+        assert_eq!(frame.contains(&paint("S", Colour::YELLOW, true)), true);
+        assert_eq!(frame.contains(&paint("-", Colour::RED, true)), true);
+        assert_eq!(frame.ends_with("step 1"), true);
+    }
+}
+
+fn trace_loop(map: &Vec<Vec<PipeSection>>, start: &Point, dir: Direction, context: &Context) -> Result<Option<Vec<Point>>, SolveError> {
+    if !can_move(map, start, &dir) {
+        return Ok(None);
+    }
+    let mut path: Vec<Point> = vec![*start];
+    let mut visited: HashSet<Point> = HashSet::new();
+    if context.animation.is_some() {
+        visited.insert(*start);
     }
-    let mut current_position = move_dir(&start, &dir);
+    let mut current_position = start.step(dir);
     let mut current_from_dir = dir;
-    let mut current_section = &map[current_position.y][current_position.x];
-    let mut steps = 1;
+    let mut current_section = tile_at(map, &current_position).unwrap();
     while !current_section.start {
-        let next_dir = next_dir(current_section, &current_from_dir);
-        if next_dir.is_none() {
-            return None;
+        path.push(current_position);
+        context.checkpoint("trace_loop", path.len() as u64, None)?;
+        if let Some(animation) = &context.animation {
+            animation.frame(path.len(), |ansi| draw_loop_walk(map, &visited, path.len(), &current_position, ansi));
+            visited.insert(current_position);
         }
-        current_from_dir = next_dir.unwrap();
-        if !can_move(&map, &current_position, &current_from_dir) {
-            return None;
+        current_from_dir = match next_dir(current_section, &current_from_dir) {
+            Some(dir) => dir,
+            None => return Ok(None),
+        };
+        if !can_move(map, &current_position, &current_from_dir) {
+            return Ok(None);
         }
-        current_position = move_dir(&current_position, &current_from_dir);
-        current_section = &map[current_position.y][current_position.x];
-        steps = steps + 1;
+        current_position = current_position.step(current_from_dir);
+        current_section = tile_at(map, &current_position).unwrap();
     }
-    return Some(steps);
+    return Ok(Some(path));
+}
+
+#[cfg(test)]
+mod trace_loop_tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_loop_dead_end() {
+        let map = get_map("data/day10/test1.txt");
+        assert_eq!(trace_loop(&map, &Point::new(1, 1), Direction::North, &Context::default()), Ok(None));
+    }
+
+    #[test]
+    fn test_trace_loop_east_from_start() {
+        let map = get_map("data/day10/test1.txt");
+        let path = trace_loop(&map, &Point::new(1, 1), Direction::East, &Context::default()).unwrap().unwrap();
+        assert_eq!(path.len(), 8);
+        assert_eq!(path[0], Point::new(1, 1));
+        assert_eq!(path[1], Point::new(2, 1));
+        assert_eq!(path[7], Point::new(1, 2));
+    }
+}
+
+fn count_loop_steps(map: &Vec<Vec<PipeSection>>, start: &Point, dir: Direction, context: &Context) -> Result<Option<usize>, SolveError> {
+    return Ok(trace_loop(map, start, dir, context)?.map(|path| path.len()));
 }
 
 #[cfg(test)]
@@ -324,45 +406,45 @@ mod count_loop_steps_tests {
     fn test_count_loop_steps_north_from_start() {
         let map = get_map("data/day10/test1.txt");
         let start = find_start(&map).unwrap();
-        assert_eq!(count_loop_steps(&map, &start, Direction::NORTH), None);
+        assert_eq!(count_loop_steps(&map, &start, Direction::North, &Context::default()), Ok(None));
     }
 
     #[test]
     fn test_count_loop_steps_east_from_start() {
         let map = get_map("data/day10/test1.txt");
         let start = find_start(&map).unwrap();
-        assert_eq!(count_loop_steps(&map, &start, Direction::EAST), Some(8));
+        assert_eq!(count_loop_steps(&map, &start, Direction::East, &Context::default()), Ok(Some(8)));
     }
 
     #[test]
     fn test_count_loop_steps_south_from_start() {
         let map = get_map("data/day10/test1.txt");
         let start = find_start(&map).unwrap();
-        assert_eq!(count_loop_steps(&map, &start, Direction::SOUTH), Some(8));
+        assert_eq!(count_loop_steps(&map, &start, Direction::South, &Context::default()), Ok(Some(8)));
     }
 
     #[test]
     fn test_count_loop_steps_west_from_start() {
         let map = get_map("data/day10/test1.txt");
         let start = find_start(&map).unwrap();
-        assert_eq!(count_loop_steps(&map, &start, Direction::WEST), None);
+        assert_eq!(count_loop_steps(&map, &start, Direction::West, &Context::default()), Ok(None));
     }
 }
 
-fn solve_for_part1(filename: &str) -> usize {
+fn solve_for_part1(filename: &str, context: &Context) -> Result<usize, SolveError> {
     let map = get_map(filename);
     let start = find_start(&map).unwrap();
-    let directions = [Direction::NORTH, Direction::EAST, Direction::SOUTH, Direction::WEST];
-    for dir in directions {
+    for dir in Direction::ALL {
         if can_move(&map, &start, &dir) {
-            let total_steps = count_loop_steps(&map, &start, dir);
+            let total_steps = count_loop_steps(&map, &start, dir, context)?;
             if total_steps.is_some() {
                 let total = total_steps.unwrap();
-                return total / 2;
+                debug!("loop from ({}, {}) heading {:?} has {} tiles", start.x, start.y, dir, total);
+                return Ok(total / 2);
             }
         }
     }
-    return 0;
+    return Ok(0);
 }
 
 #[cfg(test)]
@@ -371,33 +453,114 @@ mod solve_for_part1_tests {
 
     #[test]
     fn test_solve_for_part1_test1() {
-        assert_eq!(solve_for_part1("data/day10/test1.txt"), 4);
+        assert_eq!(solve_for_part1("data/day10/test1.txt", &Context::default()), Ok(4));
     }
 
     #[test]
     fn test_solve_for_part1_test2() {
-        assert_eq!(solve_for_part1("data/day10/test2.txt"), 4);
+        assert_eq!(solve_for_part1("data/day10/test2.txt", &Context::default()), Ok(4));
     }
 
     #[test]
     fn test_solve_for_part1_test3() {
-        assert_eq!(solve_for_part1("data/day10/test3.txt"), 8);
+        assert_eq!(solve_for_part1("data/day10/test3.txt", &Context::default()), Ok(8));
     }
 
     #[test]
     fn test_solve_for_part1_test4() {
-        assert_eq!(solve_for_part1("data/day10/test4.txt"), 8);
+        assert_eq!(solve_for_part1("data/day10/test4.txt", &Context::default()), Ok(8));
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Tile {
+    Start,
+    Loop,
+    Inside,
+    Outside,
+}
+
+fn find_loop(map: &Vec<Vec<PipeSection>>, start: &Point) -> Option<Vec<Point>> {
+    return Direction::ALL.into_iter().find_map(|dir| trace_loop(map, start, dir, &Context::default()).ok().flatten());
+}
+
+fn classify_tiles(map: &Vec<Vec<PipeSection>>, path: &Vec<Point>) -> Vec<Vec<Tile>> {
+    let on_loop: HashSet<Point> = path.iter().copied().collect();
+    let start = path[0];
+    let start_connects_north = path.len() > 1
+        && (path[1] == start.step(Direction::North) || path[path.len() - 1] == start.step(Direction::North));
+    let mut tiles: Vec<Vec<Tile>> = vec![];
+    for y in 0..map.len() {
+        let mut inside = false;
+        let mut row: Vec<Tile> = vec![];
+        for x in 0..map[y].len() {
+            let position = Point::new(x as i64, y as i64);
+            if position == start {
+                row.push(Tile::Start);
+            } else if on_loop.contains(&position) {
+                row.push(Tile::Loop);
+            } else {
+                row.push(if inside { Tile::Inside } else { Tile::Outside });
+                continue;
+            }
+            let north = if position == start { start_connects_north } else { map[y][x].north };
+            if north {
+                inside = !inside;
+            }
+        }
+        tiles.push(row);
+    }
+    return tiles;
+}
+
+#[cfg(test)]
+mod classify_tiles_tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_tiles_test1() {
+        let map = get_map("data/day10/test1.txt");
+        let path = find_loop(&map, &find_start(&map).unwrap()).unwrap();
+        let tiles = classify_tiles(&map, &path);
+        assert_eq!(tiles[0], vec![Tile::Outside; 5]);
+        assert_eq!(tiles[1], vec![Tile::Outside, Tile::Start, Tile::Loop, Tile::Loop, Tile::Outside]);
+        assert_eq!(tiles[2], vec![Tile::Outside, Tile::Loop, Tile::Inside, Tile::Loop, Tile::Outside]);
+    }
+
+    #[test]
+    fn test_classify_tiles_test4_junk_pipes() {
+        let map = get_map("data/day10/test4.txt");
+        let path = find_loop(&map, &find_start(&map).unwrap()).unwrap();
+        let tiles = classify_tiles(&map, &path);
+        assert_eq!(tiles.iter().flatten().filter(|tile| **tile == Tile::Inside).count(), 1);
+        assert_eq!(tiles[0][0], Tile::Outside);
+    }
+}
+
+pub fn render(filename: &str, path: &str, _context: &Context) -> io::Result<()> {
+    let map = get_map(filename);
+    let start = find_start(&map).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("{}: no start tile", filename)))?;
+    let pipe_loop = find_loop(&map, &start).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("{}: no loop through the start tile", filename)))?;
+    let tiles = classify_tiles(&map, &pipe_loop);
+    let width = map.iter().map(|row| row.len()).max().unwrap_or(0);
+    return write_image(path, width, map.len(), 8, |x, y| match tiles[y].get(x) {
+        Some(Tile::Start) => Colour::RED,
+        Some(Tile::Loop) => Colour::YELLOW,
+        Some(Tile::Inside) => Colour::GREEN,
+        Some(Tile::Outside) | None => Colour::NAVY,
+    });
+}
+
 fn solve_for_part2(_filename: &str) -> i64 {
     return 0;
 }
 
-pub fn part1() {
-    println!("Day 10 Part 1 result: {}", solve_for_part1("data/day10/input.txt"));
+pub fn part1(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    context.require_checked("day10")?;
+    return Ok(Answer::from(solve_for_part1(filename, context)?));
 }
 
-pub fn part2() {
-    println!("Day 10 Part 2 result: {}", solve_for_part2("data/day10/input.txt"));
+pub fn part2(filename: &str, context: &Context) -> Result<Answer, SolveError> {
+    context.require_checked("day10")?;
+    return Ok(Answer::from(solve_for_part2(filename)));
 }
@@ -0,0 +1,13 @@
+use crate::aoc_common::answer::Answer;
+use crate::aoc_common::context::Context;
+use crate::DAY_FUNCTIONS;
+
+fn check_example(day: usize, part: usize, filename: &str, expected: &str) {
+    let expected = expected.parse::<Answer>().unwrap_or_else(|_| panic!("{}: invalid expected answer for part {}", filename, part));
+    match DAY_FUNCTIONS[day - 1][part - 1](filename, &Context::default()) {
+        Ok(actual) => assert!(actual == expected, "{}: part {} expected {}, got {}", filename, part, expected, actual),
+        Err(error) => panic!("{}: part {} failed: {}", filename, part, error),
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
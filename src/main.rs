@@ -9,8 +9,29 @@ mod day07;
 mod day08;
 mod day09;
 mod day10;
+#[cfg(test)]
+mod examples;
 
-const DAY_FUNCTIONS: [[fn();2];10] = [
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use aoc_common::answer::Answer;
+use aoc_common::bench::Measurement;
+use aoc_common::checked::Arithmetic;
+use aoc_common::config::Config;
+use aoc_common::context::{Context, SolveError};
+use aoc_common::lib::print_result;
+use aoc_common::log::{self, Filter, Level};
+use aoc_common::terminal::Animation;
+
+type Solver = fn(&str, &Context) -> Result<Answer, SolveError>;
+type Renderer = fn(&str, &str, &Context) -> std::io::Result<()>;
+type Benchmark = fn(&Context) -> Result<Vec<Measurement>, SolveError>;
+type QueryRunner = fn(&str, &str) -> Result<String, SolveError>;
+type Report = fn(&str, &Context) -> Result<String, SolveError>;
+
+const DAY_FUNCTIONS: [[Solver;2];10] = [
     [day01::day01::part1, day01::day01::part2],
     [day02::day02::part1, day02::day02::part2],
     [day03::day03::part1, day03::day03::part2],
@@ -23,28 +44,308 @@ const DAY_FUNCTIONS: [[fn();2];10] = [
     [day10::day10::part1, day10::day10::part2],
 ];
 
+const DAY_PARAMETERS: [&[&str];10] = [
+    day01::day01::PARAMETERS,
+    day02::day02::PARAMETERS,
+    day03::day03::PARAMETERS,
+    &[],
+    &[],
+    &[],
+    &[],
+    &[],
+    &[],
+    &[],
+];
+
+const DAY_RENDERERS: [Option<Renderer>;10] = [
+    None,
+    None,
+    Some(day03::day03::render),
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    Some(day10::day10::render),
+];
+
+const DAY_BENCHMARKS: [Option<Benchmark>;10] = [
+    Some(day01::day01::benchmark),
+    None,
+    Some(day03::day03::benchmark),
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+];
+
+const DAY_QUERIES: [Option<QueryRunner>;10] = [
+    None,
+    Some(day02::day02::query),
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+    None,
+];
+
+const DAY_REPORTS: [&[(&str, Report)];10] = [
+    &[],
+    &[("games", day02::day02::games_report), ("summary", day02::day02::summary_report), ("inference", day02::day02::inference_report)],
+    &[],
+    &[],
+    &[],
+    &[],
+    &[],
+    &[],
+    &[],
+    &[],
+];
+
+fn numeric_option(flag: &str, value: Option<String>) -> u64 {
+    match value.as_deref().map(str::parse::<u64>) {
+        Some(Ok(number)) => number,
+        _ => {
+            eprintln!("{} needs a whole number", flag);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_solver(solver: Solver, filename: String, context: Context) -> Result<Answer, SolveError> {
+    let timeout = match context.timeout {
+        Some(timeout) => timeout,
+        None => return solver(&filename, &context),
+    };
+    let cancel_flag = context.cancel_flag();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(solver(&filename, &context));
+    });
+    return match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            cancel_flag.store(true, Ordering::Relaxed);
+            Err(SolveError::TimedOut(timeout))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            eprintln!("Solver stopped without producing a result");
+            std::process::exit(101);
+        }
+    };
+}
+
+fn required_option(flag: &str, value: Option<String>, expected: &str) -> String {
+    if value.is_none() {
+        eprintln!("{} needs {}", flag, expected);
+        std::process::exit(1);
+    }
+    return value.unwrap();
+}
+
 fn main() {
-    let arg = std::env::args().nth(1).expect("no pattern given");
+    let mut context = Context::default();
+    let mut animate = false;
+    let mut delay_ms = 50;
+    let mut every = 1;
+    let mut render_path: Option<String> = None;
+    let mut config_path: Option<String> = None;
+    let mut data_dir: Option<String> = None;
+    let mut input: Option<String> = None;
+    let mut overrides: Vec<(String, String)> = vec![];
+    let mut pattern: Option<String> = None;
+    let mut verbosity = 0;
+    let mut bench = false;
+    let mut query: Option<String> = None;
+    let mut report_name: Option<String> = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v' || c == 'q') {
+            verbosity += arg.matches('v').count() as i32 - arg.matches('q').count() as i32;
+        } else if arg == "--bench" {
+            bench = true;
+        } else if arg == "--big" {
+            context.arithmetic = Arithmetic::Big;
+        } else if arg == "--animate" {
+            animate = true;
+        } else if arg == "--delay" {
+            delay_ms = numeric_option("--delay", args.next());
+        } else if arg == "--every" {
+            every = numeric_option("--every", args.next());
+        } else if arg == "--timeout" {
+            context = context.with_timeout(Duration::from_secs(numeric_option("--timeout", args.next())));
+        } else if arg == "--progress" {
+            context = context.with_progress_interval(Duration::from_secs(numeric_option("--progress", args.next())));
+        } else if arg == "--render" {
+            render_path = Some(required_option("--render", args.next(), "an output path ending in .ppm or .svg"));
+        } else if arg == "--query" {
+            query = Some(required_option("--query", args.next(), "an expression such as \"red <= 12 && max(green) >= 5\""));
+        } else if arg == "--report" {
+            report_name = Some(required_option("--report", args.next(), "a report name"));
+        } else if arg == "--config" {
+            config_path = Some(required_option("--config", args.next(), "a path to a config file"));
+        } else if arg == "--data-dir" {
+            data_dir = Some(required_option("--data-dir", args.next(), "a directory"));
+        } else if arg == "--input" {
+            input = Some(required_option("--input", args.next(), "an input file name"));
+        } else if arg == "--set" {
+            let setting = required_option("--set", args.next(), "name=value");
+            match setting.split_once('=') {
+                Some((name, value)) => overrides.push((name.trim().to_string(), value.to_string())),
+                None => {
+                    eprintln!("--set needs name=value, found: {}", setting);
+                    std::process::exit(1);
+                }
+            }
+        } else {
+            pattern = Some(arg);
+        }
+    }
+    let filter = match std::env::var(log::ENV_VAR) {
+        Ok(spec) => Filter::parse(&spec).unwrap_or_else(|error| {
+            eprintln!("Ignoring {}: {}", log::ENV_VAR, error);
+            Filter::default()
+        }),
+        Err(_) => Filter::default(),
+    };
+    let mut filter = filter.with_verbosity(verbosity);
+    if context.progress_interval.is_some() {
+        filter = filter.with_minimum("aoc_common::context", Level::Info);
+    }
+    log::set_filter(filter);
+    if animate {
+        context.animation = Some(Animation::new(Duration::from_millis(delay_ms), every as usize));
+    }
+    let arg = pattern.expect("no pattern given");
     let strings: Vec<&str> = arg.split('.').collect();
-    if strings.len() < 2 {
+    if bench && DAY_BENCHMARKS.get(strings[0].parse::<usize>().unwrap_or(0).wrapping_sub(1)).copied().flatten().is_none() {
+        eprintln!("Day {} has no benchmark", strings[0]);
+        std::process::exit(1);
+    }
+    let solving = query.is_none() && report_name.is_none() && !bench;
+    if strings.len() < 2 && solving {
         eprintln!("Argument should be <day#>.<part#>");
         std::process::exit(1);
     }
 
     let day = strings[0].parse::<i32>().unwrap();
-    let part = strings[1].parse::<i32>().unwrap();
-
     let max_days = DAY_FUNCTIONS.len() as i32;
     if day <= 0 || day > max_days {
         eprintln!("Day must be between 1 and {}", max_days);
         std::process::exit(1);
     }
-    if part != 1 && part != 2 {
-        eprintln!("Part must be 1 or 2");
+    if query.is_some() && DAY_QUERIES[(day - 1) as usize].is_none() {
+        eprintln!("Day {} has no query language", day);
         std::process::exit(1);
     }
 
-    println!("Day: {}, part: {}", day, part);
+    let report = report_name.as_ref().map(|name| {
+        let reports = DAY_REPORTS[(day - 1) as usize];
+        match reports.iter().find(|(report, _)| report == name) {
+            Some((_, report)) => *report,
+            None => {
+                let available: Vec<&str> = reports.iter().map(|(report, _)| *report).collect();
+                if available.is_empty() {
+                    eprintln!("Day {} has no reports", day);
+                } else {
+                    eprintln!("Day {} has no report \"{}\"; expected one of: {}", day, name, available.join(", "));
+                }
+                std::process::exit(1);
+            }
+        }
+    });
+
+    let part = if solving { strings[1].parse::<i32>().unwrap() } else { 0 };
+    if solving && part != 1 && part != 2 {
+        eprintln!("Part must be 1 or 2");
+        std::process::exit(1);
+    }
+    if solving {
+        println!("Day: {}, part: {}", day, part);
+    }
 
-    DAY_FUNCTIONS[(day - 1) as usize][(part - 1) as usize]();
+    let config = match config_path {
+        Some(path) => Config::load(&path),
+        None => Config::load_optional("aoc.toml"),
+    };
+    let mut config = config.unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    });
+    debug!("Using configuration from {}", config.source);
+    if let Some(data_dir) = data_dir {
+        config.data_dir = data_dir;
+    }
+    if let Some(input) = input {
+        config.set_parameter(day as usize, "input", &input);
+    }
+    for (name, value) in overrides {
+        config.set_parameter(day as usize, &name, &value);
+    }
+    context.parameters = config.day_parameters(day as usize, DAY_PARAMETERS[(day - 1) as usize]).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    });
+    if let (true, Some(benchmark)) = (bench, DAY_BENCHMARKS[(day - 1) as usize]) {
+        match benchmark(&context) {
+            Ok(measurements) => println!("{}", aoc_common::bench::report(&measurements)),
+            Err(error) => {
+                eprintln!("Day {} benchmark: {}", day, error);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    let filename = config.input_path(day as usize);
+    if !std::path::Path::new(&filename).is_file() {
+        eprintln!("Input file {} not found; set data_dir or input in {} or use --data-dir/--input", filename, config.source);
+        std::process::exit(1);
+    }
+    if let Some(path) = render_path {
+        match DAY_RENDERERS[(day - 1) as usize] {
+            Some(render) => {
+                if let Err(error) = render(&filename, &path, &context) {
+                    eprintln!("Could not render day {}: {}", day, error);
+                    std::process::exit(1);
+                }
+                println!("Rendered {} to {}", filename, path);
+            }
+            None => {
+                eprintln!("Day {} has no renderer", day);
+                std::process::exit(1);
+            }
+        }
+    }
+    debug!("Reading {} with parameters {:?}", filename, context.parameters);
+    if let (Some(expression), Some(run)) = (query, DAY_QUERIES[(day - 1) as usize]) {
+        info!("Day: {}, query: {}", day, expression);
+        match run(&filename, &expression) {
+            Ok(output) => println!("{}", output),
+            Err(error) => {
+                eprintln!("Day {} query: {}", day, error);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    if let (Some(name), Some(report)) = (report_name, report) {
+        info!("Day: {}, report: {}", day, name);
+        match report(&filename, &context) {
+            Ok(output) => println!("{}", output),
+            Err(error) => {
+                eprintln!("Day {} report {}: {}", day, name, error);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    let solver = DAY_FUNCTIONS[(day - 1) as usize][(part - 1) as usize];
+    print_result(day, part, run_solver(solver, filename, context));
 }